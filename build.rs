@@ -1,7 +1,11 @@
-use std::env;
+use std::{cmp, env};
 
 const CFG_KEY: &str = "py_sys_config";
 
+/// The newest Python 3 minor version whose `Py_3_x` cfg is declared
+/// even when building against an older version.
+const LAST_KNOWN_MINOR_VERSION: u32 = 13;
+
 #[cfg(feature = "python27-sys")]
 const PYTHONSYS_ENV_VAR: &str = "DEP_PYTHON27_PYTHON_FLAGS";
 
@@ -48,4 +52,25 @@ fn main() {
             }
         }
     }
+
+    // Replicate the Py_3_x version flags and Py_LIMITED_API of python3-sys, so that
+    // version-dependent APIs can be used with the same cfg syntax.
+    let mut max_minor = LAST_KNOWN_MINOR_VERSION;
+    if let Ok(minor) = env::var("DEP_PYTHON3_PYTHON_MINOR_VERSION") {
+        let minor: u32 = minor.parse().expect("invalid python minor version");
+        for i in 4..(minor + 1) {
+            println!("cargo:rustc-cfg=Py_3_{}", i);
+        }
+        max_minor = cmp::max(max_minor, minor);
+    }
+    if env::var_os("DEP_PYTHON3_PYTHON_LIMITED_API").is_some() {
+        println!("cargo:rustc-cfg=Py_LIMITED_API");
+    }
+
+    // Declare every cfg this script can set, for rustc's `unexpected_cfgs` lint.
+    println!("cargo:rustc-check-cfg=cfg({}, values(any()))", CFG_KEY);
+    println!("cargo:rustc-check-cfg=cfg(Py_LIMITED_API)");
+    for i in 4..(max_minor + 1) {
+        println!("cargo:rustc-check-cfg=cfg(Py_3_{})", i);
+    }
 }
@@ -32,7 +32,7 @@ pub use crate::enumobject::*;
 pub use crate::eval::*;
 pub use crate::fileobject::*;
 pub use crate::floatobject::*;
-pub use crate::frameobject::PyFrameObject;
+pub use crate::frameobject::{PyFrameObject, PyFrame_Check, PyFrame_GetLineNumber, PyFrame_Type};
 pub use crate::funcobject::*;
 pub use crate::genobject::*;
 pub use crate::import::*;
@@ -1,11 +1,13 @@
-use libc::{c_char, c_double, c_int, c_long, c_longlong, c_ulong, c_ulonglong, c_void, size_t};
+use libc::{
+    c_char, c_double, c_int, c_long, c_longlong, c_uchar, c_ulong, c_ulonglong, c_void, size_t,
+};
 
 use crate::object::*;
 use crate::pyport::Py_ssize_t;
 
-ignore! {
-    #[repr(C)]
-    struct PyLongObject { /* representation hidden */ }
+#[repr(C)]
+pub struct PyLongObject {
+    _private: [u8; 0],
 }
 
 #[cfg_attr(windows, link(name = "pythonXY"))]
@@ -60,24 +62,25 @@ extern "C" {
 
     pub fn PyLong_GetInfo() -> *mut PyObject;
 
+    pub fn _PyLong_NumBits(v: *mut PyObject) -> size_t;
+    pub fn _PyLong_FromByteArray(
+        bytes: *const c_uchar,
+        n: size_t,
+        little_endian: c_int,
+        is_signed: c_int,
+    ) -> *mut PyObject;
+    pub fn _PyLong_AsByteArray(
+        v: *mut PyLongObject,
+        bytes: *mut c_uchar,
+        n: size_t,
+        little_endian: c_int,
+        is_signed: c_int,
+    ) -> c_int;
+
     ignore! {
         pub fn _PyLong_AsInt(arg1: *mut PyObject) -> c_int;
         pub fn _PyLong_Frexp(a: *mut PyLongObject, e: *mut Py_ssize_t) -> c_double;
         pub fn _PyLong_Sign(v: *mut PyObject) -> c_int;
-        pub fn _PyLong_NumBits(v: *mut PyObject) -> size_t;
-        pub fn _PyLong_FromByteArray(
-            bytes: *const c_uchar,
-            n: size_t,
-            little_endian: c_int,
-            is_signed: c_int,
-        ) -> *mut PyObject;
-        pub fn _PyLong_AsByteArray(
-            v: *mut PyLongObject,
-            bytes: *mut c_uchar,
-            n: size_t,
-            little_endian: c_int,
-            is_signed: c_int,
-        ) -> c_int;
         pub fn _PyLong_Format(
             aa: *mut PyObject,
             base: c_int,
@@ -386,11 +386,15 @@ fn configure_from_path(expected_version: &PythonVersion) -> Result<String, Strin
     {
         if env::var_os("CARGO_FEATURE_PEP_384").is_some() {
             println!("cargo:rustc-cfg=Py_LIMITED_API");
+            // Exported to dependents as DEP_PYTHON3_PYTHON_LIMITED_API.
+            println!("cargo:python_limited_api=1");
         }
         if let Some(minor) = some_minor {
             for i in 4..(minor + 1) {
                 println!("cargo:rustc-cfg=Py_3_{}", i);
             }
+            // Exported to dependents as DEP_PYTHON3_PYTHON_MINOR_VERSION.
+            println!("cargo:python_minor_version={}", minor);
         }
     }
 
@@ -35,6 +35,10 @@ extern "C" {
         format: *const c_char,
         ...
     ) -> *mut PyObject;
+    #[cfg(not(Py_LIMITED_API))]
+    pub fn PyEval_SetProfile(func: Option<crate::pystate::Py_tracefunc>, obj: *mut PyObject);
+    #[cfg(not(Py_LIMITED_API))]
+    pub fn PyEval_SetTrace(func: Option<crate::pystate::Py_tracefunc>, obj: *mut PyObject);
     pub fn PyEval_GetBuiltins() -> *mut PyObject;
     pub fn PyEval_GetGlobals() -> *mut PyObject;
     pub fn PyEval_GetLocals() -> *mut PyObject;
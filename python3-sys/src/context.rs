@@ -0,0 +1,43 @@
+use libc::{c_char, c_int};
+
+use crate::object::*;
+
+#[cfg_attr(windows, link(name = "pythonXY"))]
+extern "C" {
+    pub static mut PyContext_Type: PyTypeObject;
+    pub static mut PyContextVar_Type: PyTypeObject;
+    pub static mut PyContextToken_Type: PyTypeObject;
+}
+
+#[inline]
+pub unsafe fn PyContext_CheckExact(o: *mut PyObject) -> c_int {
+    (Py_TYPE(o) == &mut PyContext_Type) as c_int
+}
+
+#[inline]
+pub unsafe fn PyContextVar_CheckExact(o: *mut PyObject) -> c_int {
+    (Py_TYPE(o) == &mut PyContextVar_Type) as c_int
+}
+
+#[inline]
+pub unsafe fn PyContextToken_CheckExact(o: *mut PyObject) -> c_int {
+    (Py_TYPE(o) == &mut PyContextToken_Type) as c_int
+}
+
+#[cfg_attr(windows, link(name = "pythonXY"))]
+extern "C" {
+    pub fn PyContext_New() -> *mut PyObject;
+    pub fn PyContext_Copy(ctx: *mut PyObject) -> *mut PyObject;
+    pub fn PyContext_CopyCurrent() -> *mut PyObject;
+    pub fn PyContext_Enter(ctx: *mut PyObject) -> c_int;
+    pub fn PyContext_Exit(ctx: *mut PyObject) -> c_int;
+
+    pub fn PyContextVar_New(name: *const c_char, default_value: *mut PyObject) -> *mut PyObject;
+    pub fn PyContextVar_Get(
+        var: *mut PyObject,
+        default_value: *mut PyObject,
+        value: *mut *mut PyObject,
+    ) -> c_int;
+    pub fn PyContextVar_Set(var: *mut PyObject, value: *mut PyObject) -> *mut PyObject;
+    pub fn PyContextVar_Reset(var: *mut PyObject, token: *mut PyObject) -> c_int;
+}
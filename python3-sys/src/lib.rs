@@ -32,6 +32,8 @@ pub use crate::code::*;
 pub use crate::codecs::*;
 pub use crate::compile::*;
 pub use crate::complexobject::*;
+#[cfg(all(Py_3_7, not(Py_LIMITED_API)))]
+pub use crate::context::*;
 pub use crate::descrobject::*;
 pub use crate::dictobject::*;
 pub use crate::enumobject::*;
@@ -40,7 +42,9 @@ pub use crate::fileobject::*;
 #[cfg(Py_3_5)]
 pub use crate::fileutils::*;
 pub use crate::floatobject::*;
-pub use crate::frameobject::PyFrameObject;
+pub use crate::frameobject::{PyFrameObject, PyFrame_GetLineNumber};
+#[cfg(not(Py_LIMITED_API))]
+pub use crate::frameobject::{PyFrame_Check, PyFrame_Type};
 pub use crate::import::*;
 #[cfg(all(Py_3_8, not(Py_LIMITED_API)))]
 pub use crate::initconfig::*;
@@ -214,9 +218,8 @@ mod pyerrors;
 // TODO supports PEP-384 only; needs adjustment for Python 3.3 and 3.5
 mod pystate;
 
-// TODO
-// #[cfg(Py_3_7)]
-// mod context;
+#[cfg(all(Py_3_7, not(Py_LIMITED_API)))]
+mod context;
 
 #[cfg(Py_LIMITED_API)]
 mod pyarena {}
@@ -47,6 +47,45 @@ extern "C" {
     pub fn PyObject_Size(o: *mut PyObject) -> Py_ssize_t;
 }
 
+#[cfg(Py_3_8)]
+pub const PY_VECTORCALL_ARGUMENTS_OFFSET: libc::size_t =
+    1 << (8 * core::mem::size_of::<libc::size_t>() - 1);
+
+#[cfg(Py_3_8)]
+#[inline]
+pub unsafe fn PyVectorcall_NARGS(n: libc::size_t) -> Py_ssize_t {
+    (n & !PY_VECTORCALL_ARGUMENTS_OFFSET) as Py_ssize_t
+}
+
+#[cfg(all(Py_3_8, not(Py_LIMITED_API)))]
+#[inline]
+pub unsafe fn PyVectorcall_Function(callable: *mut PyObject) -> Option<vectorcallfunc> {
+    let tp = Py_TYPE(callable);
+    if PyType_HasFeature(tp, Py_TPFLAGS_HAVE_VECTORCALL) == 0 {
+        return None;
+    }
+    let offset = (*tp).tp_vectorcall_offset;
+    debug_assert!(offset > 0);
+    *((callable as *const c_char).offset(offset) as *const Option<vectorcallfunc>)
+}
+
+#[cfg(all(Py_3_9, not(Py_LIMITED_API)))]
+#[cfg_attr(windows, link(name = "pythonXY"))]
+extern "C" {
+    pub fn PyObject_Vectorcall(
+        callable: *mut PyObject,
+        args: *const *mut PyObject,
+        nargsf: libc::size_t,
+        kwnames: *mut PyObject,
+    ) -> *mut PyObject;
+    pub fn PyObject_VectorcallMethod(
+        name: *mut PyObject,
+        args: *const *mut PyObject,
+        nargsf: libc::size_t,
+        kwnames: *mut PyObject,
+    ) -> *mut PyObject;
+}
+
 #[inline]
 pub unsafe fn PyObject_Length(o: *mut PyObject) -> Py_ssize_t {
     PyObject_Size(o)
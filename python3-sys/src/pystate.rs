@@ -52,6 +52,32 @@ extern "C" {
     pub fn PyThreadState_GetID(tstate: *mut PyThreadState) -> u64;
 }
 
+#[cfg(not(Py_LIMITED_API))]
+pub type Py_tracefunc = unsafe extern "C" fn(
+    obj: *mut PyObject,
+    frame: *mut crate::frameobject::PyFrameObject,
+    what: libc::c_int,
+    arg: *mut PyObject,
+) -> libc::c_int;
+
+/* The following values are used for 'what' for tracefunc functions: */
+#[cfg(not(Py_LIMITED_API))]
+pub const PyTrace_CALL: libc::c_int = 0;
+#[cfg(not(Py_LIMITED_API))]
+pub const PyTrace_EXCEPTION: libc::c_int = 1;
+#[cfg(not(Py_LIMITED_API))]
+pub const PyTrace_LINE: libc::c_int = 2;
+#[cfg(not(Py_LIMITED_API))]
+pub const PyTrace_RETURN: libc::c_int = 3;
+#[cfg(not(Py_LIMITED_API))]
+pub const PyTrace_C_CALL: libc::c_int = 4;
+#[cfg(not(Py_LIMITED_API))]
+pub const PyTrace_C_EXCEPTION: libc::c_int = 5;
+#[cfg(not(Py_LIMITED_API))]
+pub const PyTrace_C_RETURN: libc::c_int = 6;
+#[cfg(all(Py_3_7, not(Py_LIMITED_API)))]
+pub const PyTrace_OPCODE: libc::c_int = 7;
+
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub enum PyGILState_STATE {
@@ -65,6 +91,7 @@ extern "C" {
     pub fn PyGILState_Ensure() -> PyGILState_STATE;
     pub fn PyGILState_Release(arg1: PyGILState_STATE) -> ();
     pub fn PyGILState_GetThisThreadState() -> *mut PyThreadState;
+    pub fn PyGILState_Check() -> libc::c_int;
 }
 
 #[inline(always)]
@@ -19,13 +19,13 @@
 //! This module contains logic for parsing a python argument list.
 //! See also the macros `py_argparse!`, `py_fn!` and `py_method!`.
 
-use std::ptr;
+use std::{mem, ptr, slice};
 
 use crate::conversion::{RefFromPyObject, ToPyObject};
 use crate::err::{self, PyResult};
 use crate::ffi;
 use crate::objects::{exc, PyDict, PyObject, PyString, PyTuple};
-use crate::python::{Python, PythonObject};
+use crate::python::{PyClone, Python, PythonObject};
 
 /// Description of a python parameter; used for `parse_args()`.
 pub struct ParamDescription<'a> {
@@ -57,10 +57,90 @@ pub fn parse_args(
     args: &PyTuple,
     kwargs: Option<&PyDict>,
     output: &mut [Option<PyObject>],
+) -> PyResult<()> {
+    parse_args_impl(
+        py,
+        fname,
+        params,
+        args.as_slice(py),
+        kwargs.map(Keywords::Dict),
+        output,
+    )
+}
+
+/// Like `parse_args()`, but for the arguments of a vectorcall:
+///  * args:    Positional arguments, followed by the values of the keyword arguments
+///  * kwnames: Names of the keyword arguments (the last `kwnames.len()` entries of `args`)
+#[doc(hidden)]
+pub fn parse_vectorcall_args(
+    py: Python,
+    fname: Option<&str>,
+    params: &[ParamDescription],
+    args: &[PyObject],
+    kwnames: Option<&PyTuple>,
+    output: &mut [Option<PyObject>],
+) -> PyResult<()> {
+    match kwnames {
+        Some(names) => {
+            let nargs = args.len() - names.len(py);
+            let keywords = Keywords::Vectorcall(names, &args[nargs..]);
+            parse_args_impl(py, fname, params, &args[..nargs], Some(keywords), output)
+        }
+        None => parse_args_impl(py, fname, params, args, None, output),
+    }
+}
+
+enum Keywords<'a> {
+    Dict(&'a PyDict),
+    /// Names and values of the keyword arguments of a vectorcall.
+    Vectorcall(&'a PyTuple, &'a [PyObject]),
+}
+
+impl<'a> Keywords<'a> {
+    fn len(&self, py: Python) -> usize {
+        match *self {
+            Keywords::Dict(dict) => dict.len(py),
+            Keywords::Vectorcall(_, values) => values.len(),
+        }
+    }
+
+    fn get(&self, py: Python, name: &str) -> PyResult<Option<PyObject>> {
+        match *self {
+            Keywords::Dict(dict) => Ok(dict.get_item(py, name)),
+            Keywords::Vectorcall(names, values) => {
+                for (key, value) in names.as_slice(py).iter().zip(values) {
+                    if key.cast_as::<PyString>(py)?.to_string(py)? == name {
+                        return Ok(Some(value.clone_ref(py)));
+                    }
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn names(&self, py: Python) -> Vec<PyObject> {
+        match *self {
+            Keywords::Dict(dict) => dict.items(py).into_iter().map(|(key, _)| key).collect(),
+            Keywords::Vectorcall(names, _) => names
+                .as_slice(py)
+                .iter()
+                .map(|name| name.clone_ref(py))
+                .collect(),
+        }
+    }
+}
+
+fn parse_args_impl(
+    py: Python,
+    fname: Option<&str>,
+    params: &[ParamDescription],
+    args: &[PyObject],
+    kwargs: Option<Keywords>,
+    output: &mut [Option<PyObject>],
 ) -> PyResult<()> {
     assert!(params.len() == output.len());
-    let nargs = args.len(py);
-    let nkeywords = kwargs.map_or(0, |d| d.len(py));
+    let nargs = args.len();
+    let nkeywords = kwargs.as_ref().map_or(0, |k| k.len(py));
     if nargs + nkeywords > params.len() {
         return Err(err::PyErr::new::<exc::TypeError, _>(
             py,
@@ -77,7 +157,11 @@ pub fn parse_args(
     let mut used_keywords = 0;
     // Iterate through the parameters and assign values to output:
     for (i, (p, out)) in params.iter().zip(output).enumerate() {
-        match kwargs.and_then(|d| d.get_item(py, p.name())) {
+        let kwarg = match kwargs {
+            Some(ref kwargs) => kwargs.get(py, p.name())?,
+            None => None,
+        };
+        match kwarg {
             Some(kwarg) => {
                 *out = Some(kwarg);
                 used_keywords += 1;
@@ -94,7 +178,7 @@ pub fn parse_args(
             }
             None => {
                 if i < nargs {
-                    *out = Some(args.get_item(py, i));
+                    *out = Some(args[i].clone_ref(py));
                 } else {
                     *out = None;
                     if !p.is_optional {
@@ -113,7 +197,7 @@ pub fn parse_args(
     }
     if used_keywords != nkeywords {
         // check for extraneous keyword arguments
-        for (key, _value) in kwargs.unwrap().items(py) {
+        for key in kwargs.unwrap().names(py) {
             let key = key.cast_as::<PyString>(py)?.to_string(py)?;
             if !params.iter().any(|p| p.name == key) {
                 return Err(err::PyErr::new::<exc::TypeError, _>(
@@ -147,8 +231,13 @@ pub fn parse_args(
 ///   The types used must implement the `FromPyObject` trait.
 ///   If no type is specified, the parameter implicitly uses
 ///   `&PyObject` (format 1), `&PyTuple` (format 4) or `&PyDict` (format 6).
-///   If a default value is specified, it must be a compile-time constant
-///   of type `ty`.
+///   If a default value is specified, it must be an expression of type `ty`.
+///   The default expression is evaluated anew on each call in which the argument
+///   is omitted (unlike in Python, where defaults are evaluated once when the function
+///   is defined), so mutable defaults such as `items: PyList = PyList::new(py, &[])`
+///   are not shared between calls.
+///   For reference types (`&ty` and `Option<&ty>`), the default must be a constant
+///   with `'static` lifetime.
 ///  * `body`: expression of type `PyResult<_>`.
 ///     The extracted argument values are available in this scope.
 ///
@@ -396,6 +485,123 @@ macro_rules! py_argparse_raw {
     }};
 }
 
+// Like py_argparse_raw!(), but accepts the arguments of a `vectorcallfunc`:
+// `$args` (`*const *mut ffi::PyObject`), `$nargsf` and `$kwnames` (`*mut ffi::PyObject`).
+#[macro_export]
+#[doc(hidden)]
+macro_rules! py_argparse_vectorcall {
+    ($py:ident, $fname:expr, $args:expr, $nargsf:expr, $kwnames:expr, $plist:tt $body:block) => {{
+        let kwnames: Option<$crate::PyTuple> = $crate::argparse::get_kwnames($py, $kwnames);
+        let args = $crate::argparse::vectorcall_args($py, $args, $nargsf, kwnames.as_ref());
+        let ret = $crate::py_argparse_vectorcall_impl!(
+            $py,
+            $fname,
+            args,
+            kwnames.as_ref(),
+            $body,
+            $plist
+        );
+        $crate::PyDrop::release_ref(kwnames, $py);
+        ret
+    }};
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! py_argparse_vectorcall_impl {
+    // special case: function signature is (*args, **kwargs),
+    // so the arguments have to be collected into a tuple and a dict.
+    ($py:expr, $fname:expr, $args:expr, $kwnames:expr, $body:block,
+        [
+            { $pargs:ident   : $pargs_type:ty   = [ {*}  {} {} ] }
+            { $pkwargs:ident : $pkwargs_type:ty = [ {**} {} {} ] }
+        ]
+    ) => {{
+        let py: $crate::Python = $py;
+        match $crate::argparse::vectorcall_to_tuple_dict(py, $args, $kwnames) {
+            Ok((args, kwargs)) => {
+                $crate::py_argparse_impl!(py, $fname, &args, kwargs.as_ref(), $body, [
+                    { $pargs : $pargs_type = [ {*} {} {} ] }
+                    { $pkwargs : $pkwargs_type = [ {**} {} {} ] }
+                ])
+            }
+            Err(e) => Err(e)
+        }
+    }};
+
+    // normal argparse logic
+    ($py:expr, $fname:expr, $args:expr, $kwnames:expr, $body:block,
+        [ $( { $pname:ident : $ptype:ty = $detail:tt } )* ]
+    ) => {{
+        const PARAMS: &'static [$crate::argparse::ParamDescription<'static>] = &[
+            $(
+                $crate::py_argparse_param_description! { $pname : $ptype = $detail }
+            ),*
+        ];
+        let py: $crate::Python = $py;
+        let mut output = [$( $crate::py_replace_expr!($pname None) ),*];
+        match $crate::argparse::parse_vectorcall_args(py, $fname, PARAMS, $args, $kwnames, &mut output) {
+            Ok(()) => {
+                let mut _iter = output.iter();
+                let val = $crate::py_argparse_extract!( py, _iter, $body,
+                    [ $( { $pname : $ptype = $detail } )* ]);
+                val
+            },
+            Err(e) => Err(e)
+        }
+    }};
+}
+
+/// Set in `nargsf` if the callee may temporarily overwrite `args[-1]`.
+const VECTORCALL_ARGUMENTS_OFFSET: usize = 1 << (8 * mem::size_of::<usize>() - 1);
+
+#[inline]
+#[doc(hidden)]
+pub unsafe fn get_kwnames(py: Python, ptr: *mut ffi::PyObject) -> Option<PyTuple> {
+    if ptr.is_null() {
+        None
+    } else {
+        Some(PyObject::from_borrowed_ptr(py, ptr).unchecked_cast_into())
+    }
+}
+
+/// Gets the positional arguments and the keyword argument values of a vectorcall as a slice.
+#[inline]
+#[doc(hidden)]
+pub unsafe fn vectorcall_args<'a>(
+    py: Python,
+    args: *const *mut ffi::PyObject,
+    nargsf: usize,
+    kwnames: Option<&PyTuple>,
+) -> &'a [PyObject] {
+    let len = (nargsf & !VECTORCALL_ARGUMENTS_OFFSET) + kwnames.map_or(0, |names| names.len(py));
+    if len == 0 {
+        &[]
+    } else {
+        // `PyObject` has the same layout as `*mut ffi::PyObject`.
+        slice::from_raw_parts(args as *const PyObject, len)
+    }
+}
+
+#[doc(hidden)]
+pub fn vectorcall_to_tuple_dict(
+    py: Python,
+    args: &[PyObject],
+    kwnames: Option<&PyTuple>,
+) -> PyResult<(PyTuple, Option<PyDict>)> {
+    match kwnames {
+        Some(names) => {
+            let nargs = args.len() - names.len(py);
+            let kwargs = PyDict::new(py);
+            for (name, value) in names.as_slice(py).iter().zip(&args[nargs..]) {
+                kwargs.set_item(py, name, value)?;
+            }
+            Ok((PyTuple::new(py, &args[..nargs]), Some(kwargs)))
+        }
+        None => Ok((PyTuple::new(py, args), None)),
+    }
+}
+
 #[inline]
 #[doc(hidden)]
 pub unsafe fn get_kwargs(py: Python, ptr: *mut ffi::PyObject) -> Option<PyDict> {
@@ -477,7 +683,8 @@ macro_rules! py_argparse_extract {
     ( $py:expr, $iter:expr, $body:block,
         [ { $pname:ident : $ptype:ty = [ {} {$default:expr} {} ] } $($tail:tt)* ]
     ) => {
-        match $iter.next().unwrap().as_ref().map(|obj| obj.extract::<_>($py)).unwrap_or(Ok($default)) {
+        // The default value is only evaluated if the argument was not provided.
+        match $iter.next().unwrap().as_ref().map_or_else(|| Ok($default), |obj| obj.extract::<_>($py)) {
             Ok($pname) => $crate::py_argparse_extract!($py, $iter, $body, [$($tail)*]),
             Err(e) => Err(e)
         }
@@ -579,7 +786,7 @@ mod test {
         let mut called = false;
         let tuple = ("abc",).to_py_object(py);
         py_argparse!(py, None, &tuple, None, (x) {
-            assert_eq!(*x, tuple.get_item(py, 0));
+            assert_eq!(*x, tuple.get_item(py, 0).unwrap());
             called = true;
             Ok(())
         })
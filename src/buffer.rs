@@ -154,6 +154,58 @@ pub unsafe trait Element {
     fn is_compatible_format(format: &CStr) -> bool;
 }
 
+/// Trait implemented for element types that can be used in exported buffers.
+pub unsafe trait BufferElement: Element {
+    /// Gets the `struct` module format string describing this type,
+    /// for use when exporting a buffer of elements of this type.
+    ///
+    /// The format must describe an item of `mem::size_of::<Self>()` bytes,
+    /// and `is_compatible_format(format())` must be true.
+    fn format() -> &'static CStr;
+}
+
+/// Item size and format of the elements of an exported buffer.
+///
+/// Filling in the `itemsize` and `format` fields of a `Py_buffer` from a `BufferInfo`
+/// ensures that they are consistent with each other and with the Rust element type;
+/// a mismatch between the two causes consumers such as numpy to misinterpret the memory.
+///
+/// # Example
+/// ```
+/// use cpython::buffer::BufferInfo;
+///
+/// let info = BufferInfo::of::<f64>();
+/// assert_eq!(info.item_size(), 8);
+/// assert_eq!(info.format().to_str().unwrap(), "d");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BufferInfo {
+    item_size: usize,
+    format: &'static CStr,
+}
+
+impl BufferInfo {
+    /// Gets the buffer info for elements of type `T`.
+    pub fn of<T: BufferElement>() -> BufferInfo {
+        BufferInfo {
+            item_size: mem::size_of::<T>(),
+            format: T::format(),
+        }
+    }
+
+    /// Gets the size of a single element, in bytes (the `itemsize` field of `Py_buffer`).
+    #[inline]
+    pub fn item_size(&self) -> usize {
+        self.item_size
+    }
+
+    /// Gets the `struct` module format string of an element (the `format` field of `Py_buffer`).
+    #[inline]
+    pub fn format(&self) -> &'static CStr {
+        self.format
+    }
+}
+
 fn validate(b: &ffi::Py_buffer) {
     // shape and stride information must be provided when we use PyBUF_FULL_RO
     assert!(!b.shape.is_null());
@@ -634,7 +686,7 @@ impl<T: Copy> ReadOnlyCell<T> {
 }
 
 macro_rules! impl_element(
-    ($t:ty, $f:ident) => {
+    ($t:ty, $f:ident, $format:expr) => {
         unsafe impl Element for $t {
             fn is_compatible_format(format: &CStr) -> bool {
                 let slice = format.to_bytes();
@@ -644,30 +696,77 @@ macro_rules! impl_element(
                 ElementType::from_format(format) == ElementType::$f { bytes: mem::size_of::<$t>() }
             }
         }
+
+        unsafe impl BufferElement for $t {
+            fn format() -> &'static CStr {
+                unsafe { CStr::from_bytes_with_nul_unchecked($format) }
+            }
+        }
     }
 );
 
-impl_element!(u8, UnsignedInteger);
-impl_element!(u16, UnsignedInteger);
-impl_element!(u32, UnsignedInteger);
-impl_element!(u64, UnsignedInteger);
-impl_element!(usize, UnsignedInteger);
-impl_element!(i8, SignedInteger);
-impl_element!(i16, SignedInteger);
-impl_element!(i32, SignedInteger);
-impl_element!(i64, SignedInteger);
-impl_element!(isize, SignedInteger);
-impl_element!(f32, Float);
-impl_element!(f64, Float);
+// Native format characters; `I` and `Q` are 4 and 8 bytes on all supported platforms,
+// unlike `L` (the size of a C `long`). `n` and `N` are not used for `isize`/`usize`
+// because Python 2.7 does not support them.
+impl_element!(u8, UnsignedInteger, b"B\0");
+impl_element!(u16, UnsignedInteger, b"H\0");
+impl_element!(u32, UnsignedInteger, b"I\0");
+impl_element!(u64, UnsignedInteger, b"Q\0");
+#[cfg(target_pointer_width = "32")]
+impl_element!(usize, UnsignedInteger, b"I\0");
+#[cfg(target_pointer_width = "64")]
+impl_element!(usize, UnsignedInteger, b"Q\0");
+impl_element!(i8, SignedInteger, b"b\0");
+impl_element!(i16, SignedInteger, b"h\0");
+impl_element!(i32, SignedInteger, b"i\0");
+impl_element!(i64, SignedInteger, b"q\0");
+#[cfg(target_pointer_width = "32")]
+impl_element!(isize, SignedInteger, b"i\0");
+#[cfg(target_pointer_width = "64")]
+impl_element!(isize, SignedInteger, b"q\0");
+impl_element!(f32, Float, b"f\0");
+impl_element!(f64, Float, b"d\0");
 
 #[cfg(test)]
 mod test {
-    use super::PyBuffer;
+    use super::{BufferElement, BufferInfo, PyBuffer};
     use crate::conversion::ToPyObject;
     use crate::objectprotocol::ObjectProtocol;
     use crate::objects::{PyIterator, PyList, PySequence, PyTuple};
     use crate::python::{PyDrop, Python, PythonObject};
 
+    #[test]
+    fn test_buffer_info() {
+        fn check<T: BufferElement>(py: Python) {
+            let info = BufferInfo::of::<T>();
+            assert_eq!(info.item_size(), std::mem::size_of::<T>());
+            assert!(T::is_compatible_format(info.format()));
+            let format = info.format().to_str().unwrap();
+            let size: usize = py
+                .import("struct")
+                .unwrap()
+                .call(py, "calcsize", (format,), None)
+                .unwrap()
+                .extract(py)
+                .unwrap();
+            assert_eq!(size, info.item_size(), "format {}", format);
+        }
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        check::<u8>(py);
+        check::<u16>(py);
+        check::<u32>(py);
+        check::<u64>(py);
+        check::<usize>(py);
+        check::<i8>(py);
+        check::<i16>(py);
+        check::<i32>(py);
+        check::<i64>(py);
+        check::<isize>(py);
+        check::<f32>(py);
+        check::<f64>(py);
+    }
+
     #[test]
     fn test_compatible_size() {
         // for the cast in PyBuffer::shape()
@@ -0,0 +1,132 @@
+// Copyright (c) 2017 Daniel Grunwald
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this
+// software and associated documentation files (the "Software"), to deal in the Software
+// without restriction, including without limitation the rights to use, copy, modify, merge,
+// publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons
+// to whom the Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED,
+// INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR
+// PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE
+// FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
+// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Helpers for diagnosing reference leaks and memory usage.
+//!
+//! These functions go through the Python `sys` and `tracemalloc` modules,
+//! so they are comparatively slow and intended for tests and debugging.
+
+use crate::err::PyResult;
+use crate::objects::PyObject;
+use crate::python::Python;
+
+/// Returns the reference count of `obj`.
+///
+/// This calls `sys.getrefcount(obj)` and subtracts the temporary reference
+/// held for the duration of that call, so the result is the number of
+/// references that existed before calling this function.
+pub fn getrefcount(py: Python, obj: &PyObject) -> PyResult<isize> {
+    let sys = py.import("sys")?;
+    let count: isize = sys.call(py, "getrefcount", (obj,), None)?.extract(py)?;
+    Ok(count - 1)
+}
+
+/// A single entry of the allocation statistics returned by [tracemalloc_top].
+#[cfg(feature = "python3-sys")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AllocationStat {
+    /// Name of the source file that performed the allocations.
+    pub filename: String,
+    /// Line number within `filename`.
+    pub lineno: usize,
+    /// Total size of the memory blocks allocated at this line, in bytes.
+    pub size: usize,
+    /// Number of memory blocks allocated at this line.
+    pub count: usize,
+}
+
+/// Starts tracing Python memory allocations, storing up to `nframe` frames per traceback.
+/// This is equivalent to the Python expression `tracemalloc.start(nframe)`.
+#[cfg(feature = "python3-sys")]
+pub fn tracemalloc_start(py: Python, nframe: usize) -> PyResult<()> {
+    py.import("tracemalloc")?
+        .call(py, "start", (nframe,), None)?;
+    Ok(())
+}
+
+/// Stops tracing Python memory allocations and clears the collected traces.
+/// This is equivalent to the Python expression `tracemalloc.stop()`.
+#[cfg(feature = "python3-sys")]
+pub fn tracemalloc_stop(py: Python) -> PyResult<()> {
+    py.import("tracemalloc")?
+        .call(py, "stop", crate::NoArgs, None)?;
+    Ok(())
+}
+
+/// Takes a snapshot of the traced memory allocations and returns the `limit`
+/// source lines that allocated the most memory, largest first.
+///
+/// Fails with `RuntimeError` if tracing was not started using [tracemalloc_start].
+#[cfg(feature = "python3-sys")]
+pub fn tracemalloc_top(py: Python, limit: usize) -> PyResult<Vec<AllocationStat>> {
+    use crate::objectprotocol::ObjectProtocol;
+    use crate::objects::PyList;
+
+    let snapshot = py
+        .import("tracemalloc")?
+        .call(py, "take_snapshot", crate::NoArgs, None)?;
+    let stats: PyList = snapshot
+        .call_method(py, "statistics", ("lineno",), None)?
+        .cast_into(py)?;
+    let mut result = Vec::new();
+    for stat in stats.iter(py).take(limit) {
+        let frame = stat.getattr(py, "traceback")?.get_item(py, 0)?;
+        result.push(AllocationStat {
+            filename: frame.getattr(py, "filename")?.extract(py)?,
+            lineno: frame.getattr(py, "lineno")?.extract(py)?,
+            size: stat.getattr(py, "size")?.extract(py)?,
+            count: stat.getattr(py, "count")?.extract(py)?,
+        });
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::objects::PyList;
+    use crate::python::{PyClone, Python, PythonObject};
+
+    #[test]
+    fn test_getrefcount() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj = PyList::new(py, &[]).into_object();
+        let before = super::getrefcount(py, &obj).unwrap();
+        let list = PyList::new(py, &[obj.clone_ref(py)]);
+        assert_eq!(super::getrefcount(py, &obj).unwrap(), before + 1);
+        drop(list);
+        assert_eq!(super::getrefcount(py, &obj).unwrap(), before);
+    }
+
+    #[test]
+    #[cfg(feature = "python3-sys")]
+    fn test_tracemalloc() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        super::tracemalloc_start(py, 1).unwrap();
+        let data = py
+            .eval("[bytearray(1000) for _ in range(100)]", None, None)
+            .unwrap();
+        let top = super::tracemalloc_top(py, 5).unwrap();
+        super::tracemalloc_stop(py).unwrap();
+        assert!(!top.is_empty() && top.len() <= 5);
+        assert!(top.iter().map(|s| s.size).sum::<usize>() >= 100 * 1000);
+        drop(data);
+        assert!(super::tracemalloc_top(py, 5).is_err());
+    }
+}
@@ -18,7 +18,7 @@
 
 use libc::c_char;
 use std::ffi::CString;
-use std::ptr;
+use std::{fmt, io, ptr};
 
 use crate::conversion::ToPyObject;
 use crate::ffi;
@@ -177,6 +177,61 @@ impl PyErr {
         PyErr::new_helper(py, py.get_type::<T>(), value.to_py_object(py).into_object())
     }
 
+    /// Creates a new PyErr of type `T`, with a message formatted on the Rust side.
+    ///
+    /// This is equivalent to `PyErr::new::<T, _>(py, format!(...))`.
+    ///
+    /// Example:
+    ///  `return Err(PyErr::new_fmt::<exc::ValueError>(py, format_args!("bad value: {}", x)));`
+    pub fn new_fmt<T>(py: Python, args: fmt::Arguments) -> PyErr
+    where
+        T: PythonObjectWithTypeObject,
+    {
+        PyErr::new::<T, _>(py, fmt::format(args))
+    }
+
+    /// Converts a Rust I/O error into the corresponding Python exception.
+    ///
+    /// On Python 3, errors carrying an OS error code become `OSError(errno, strerror)`,
+    /// which Python maps to the matching subclass (`FileNotFoundError`, `PermissionError`, ...)
+    /// and which has the `errno` and `strerror` attributes set.
+    /// On Windows, the code is passed as `winerror`, from which Python derives `errno`.
+    /// Errors without an OS error code are mapped to a subclass based on `io::ErrorKind`.
+    ///
+    /// On Python 2, `IOError(errno, strerror)` is used for all errors.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{Python, PyErr};
+    ///
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let io_err = std::fs::File::open("/no/such/file").unwrap_err();
+    /// let err = PyErr::from_io_error(py, &io_err);
+    /// # #[cfg(feature = "python3-sys")]
+    /// assert!(err.matches(py, py.get_type::<cpython::exc::FileNotFoundError>()));
+    /// ```
+    pub fn from_io_error(py: Python, err: &io::Error) -> PyErr {
+        let message = err.to_string();
+        let result = match err.raw_os_error() {
+            Some(code) => {
+                // Rust appends the code to the OS message; Python reports it separately.
+                let suffix = format!(" (os error {})", code);
+                let strerror = message.trim_end_matches(&suffix[..]);
+                #[cfg(all(windows, feature = "python3-sys"))]
+                let args = (0, strerror, py.None(), code);
+                #[cfg(not(all(windows, feature = "python3-sys")))]
+                let args = (code, strerror);
+                io_error_type(py, None).call(py, args, None)
+            }
+            None => io_error_type(py, Some(err.kind())).call(py, (message,), None),
+        };
+        match result {
+            Ok(instance) => PyErr::from_instance(py, instance),
+            Err(e) => e,
+        }
+    }
+
     /// Gets whether an error is present in the Python interpreter's global state.
     #[inline]
     pub fn occurred(_: Python) -> bool {
@@ -215,6 +270,8 @@ impl PyErr {
     /// Retrieves the current error from the Python interpreter's global state.
     /// The error is cleared from the Python interpreter.
     /// If no error is set, returns a `SystemError`.
+    ///
+    /// The error is not normalized; see `fetch_lazy()`.
     pub fn fetch(py: Python) -> PyErr {
         let mut ptype: *mut ffi::PyObject = ptr::null_mut();
         let mut pvalue: *mut ffi::PyObject = ptr::null_mut();
@@ -225,6 +282,68 @@ impl PyErr {
         }
     }
 
+    /// Retrieves the current error from the Python interpreter's global state,
+    /// or returns `None` if no error is set.
+    /// The error is cleared from the Python interpreter.
+    ///
+    /// The error is taken as-is, without normalization: `pvalue` may be `None`,
+    /// or a constructor argument (tuple) rather than an instance of `ptype`.
+    /// This avoids creating the exception instance when only the type is needed
+    /// (e.g. to check it using `matches()`, which never normalizes).
+    /// Call `instance()` or `normalize()` to create the instance when it is needed.
+    pub fn fetch_lazy(py: Python) -> Option<PyErr> {
+        if PyErr::occurred(py) {
+            Some(PyErr::fetch(py))
+        } else {
+            None
+        }
+    }
+
+    /// Retrieves the exception currently being handled (as in `sys.exc_info()`),
+    /// without modifying the interpreter state.
+    pub(crate) fn handled(py: Python) -> Option<PyErr> {
+        #[cfg(feature = "python3-sys")]
+        unsafe {
+            let mut ptype: *mut ffi::PyObject = ptr::null_mut();
+            let mut pvalue: *mut ffi::PyObject = ptr::null_mut();
+            let mut ptraceback: *mut ffi::PyObject = ptr::null_mut();
+            // Returns new references; the exception state is left untouched.
+            ffi::PyErr_GetExcInfo(&mut ptype, &mut pvalue, &mut ptraceback);
+            PyErr::handled_from_ffi_tuple(
+                py,
+                PyObject::from_owned_ptr_opt(py, ptype),
+                PyObject::from_owned_ptr_opt(py, pvalue),
+                PyObject::from_owned_ptr_opt(py, ptraceback),
+            )
+        }
+        #[cfg(feature = "python27-sys")]
+        unsafe {
+            let tstate = ffi::PyThreadState_Get();
+            PyErr::handled_from_ffi_tuple(
+                py,
+                PyObject::from_borrowed_ptr_opt(py, (*tstate).exc_type),
+                PyObject::from_borrowed_ptr_opt(py, (*tstate).exc_value),
+                PyObject::from_borrowed_ptr_opt(py, (*tstate).exc_traceback),
+            )
+        }
+    }
+
+    fn handled_from_ffi_tuple(
+        py: Python,
+        ptype: Option<PyObject>,
+        pvalue: Option<PyObject>,
+        ptraceback: Option<PyObject>,
+    ) -> Option<PyErr> {
+        match ptype {
+            Some(ptype) if ptype != py.None() => Some(PyErr {
+                ptype,
+                pvalue: pvalue.filter(|v| *v != py.None()),
+                ptraceback: ptraceback.filter(|tb| *tb != py.None()),
+            }),
+            _ => None,
+        }
+    }
+
     unsafe fn new_from_ffi_tuple(
         py: Python,
         ptype: *mut ffi::PyObject,
@@ -306,12 +425,20 @@ impl PyErr {
     }
 
     /// Print a standard traceback to sys.stderr.
+    ///
+    /// This consumes the error, so the Python error indicator is left cleared.
+    /// Equivalent to `PyErr_PrintEx(0)`.
     pub fn print(self, py: Python) {
         self.restore(py);
         unsafe { ffi::PyErr_PrintEx(0) }
     }
 
-    /// Print a standard traceback to sys.stderr.
+    /// Print a standard traceback to sys.stderr,
+    /// and store the exception in `sys.last_type`, `sys.last_value` and `sys.last_traceback`.
+    ///
+    /// This matches what the interactive interpreter does for uncaught exceptions,
+    /// and allows post-mortem debugging via `pdb.pm()`.
+    /// Equivalent to `PyErr_PrintEx(1)`.
     pub fn print_and_set_sys_last_vars(self, py: Python) {
         self.restore(py);
         unsafe { ffi::PyErr_PrintEx(1) }
@@ -392,6 +519,9 @@ impl PyErr {
 
     /// Writes the error back to the Python interpreter's global state.
     /// This is the opposite of `PyErr::fetch()`.
+    ///
+    /// To restore an error that is still needed afterwards (e.g. to re-raise the
+    /// original error once a retry loop gives up), restore a copy made with `clone_ref()`.
     #[inline]
     pub fn restore(self, py: Python) {
         let PyErr {
@@ -410,6 +540,9 @@ impl PyErr {
 
     /// Issue a warning message.
     /// May return a PyErr if warnings-as-errors is enabled.
+    ///
+    /// See the [warnings](warnings/index.html) module for controlling the warning filters
+    /// and for recording the issued warnings.
     pub fn warn(py: Python, category: &PyObject, message: &str, stacklevel: i32) -> PyResult<()> {
         let message = CString::new(message).unwrap();
         unsafe {
@@ -433,6 +566,11 @@ impl PyDrop for PyErr {
     }
 }
 
+/// Creates a copy of the error holding new references to the exception type, value and traceback.
+///
+/// If the error is not normalized, each copy creates its own exception instance
+/// when it is normalized. Call `instance()` before cloning if all copies must share
+/// the same exception object.
 impl PyClone for PyErr {
     fn clone_ref(&self, py: Python) -> PyErr {
         PyErr {
@@ -443,6 +581,31 @@ impl PyClone for PyErr {
     }
 }
 
+/// Returns the exception type used for I/O errors of the given kind.
+/// `None` stands for errors with an OS error code, which Python maps to a subclass itself.
+#[cfg(feature = "python3-sys")]
+fn io_error_type(py: Python, kind: Option<io::ErrorKind>) -> PyType {
+    use std::io::ErrorKind;
+    match kind {
+        Some(ErrorKind::NotFound) => py.get_type::<exc::FileNotFoundError>(),
+        Some(ErrorKind::PermissionDenied) => py.get_type::<exc::PermissionError>(),
+        Some(ErrorKind::AlreadyExists) => py.get_type::<exc::FileExistsError>(),
+        Some(ErrorKind::WouldBlock) => py.get_type::<exc::BlockingIOError>(),
+        Some(ErrorKind::Interrupted) => py.get_type::<exc::InterruptedError>(),
+        Some(ErrorKind::TimedOut) => py.get_type::<exc::TimeoutError>(),
+        Some(ErrorKind::BrokenPipe) => py.get_type::<exc::BrokenPipeError>(),
+        Some(ErrorKind::ConnectionRefused) => py.get_type::<exc::ConnectionRefusedError>(),
+        Some(ErrorKind::ConnectionReset) => py.get_type::<exc::ConnectionResetError>(),
+        Some(ErrorKind::ConnectionAborted) => py.get_type::<exc::ConnectionAbortedError>(),
+        _ => py.get_type::<exc::OSError>(),
+    }
+}
+
+#[cfg(feature = "python27-sys")]
+fn io_error_type(py: Python, _kind: Option<io::ErrorKind>) -> PyType {
+    py.get_type::<exc::IOError>()
+}
+
 /// Converts `PythonObjectDowncastError` to Python `TypeError`.
 impl<'p> std::convert::From<PythonObjectDowncastError<'p>> for PyErr {
     fn from(err: PythonObjectDowncastError<'p>) -> PyErr {
@@ -469,6 +632,46 @@ pub unsafe fn result_from_owned_ptr(py: Python, p: *mut ffi::PyObject) -> PyResu
     }
 }
 
+/// Converts a Rust `Result` into the return value of a Python FFI function:
+/// a new reference to the converted `Ok` value, or `null` with the `Err` set as
+/// the current Python error.
+///
+/// This is the inverse of checking the result of a Python API call, and is useful
+/// at the boundary of hand-written `extern "C"` functions called by Python.
+///
+/// # Example
+/// ```
+/// use cpython::{into_py_or_raise, exc, PyErr, PyObject, Python};
+///
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let parse_port = |s: &str| {
+///     let result = s
+///         .parse::<u16>()
+///         .map_err(|e| PyErr::new::<exc::ValueError, _>(py, e.to_string()));
+///     into_py_or_raise(py, result)
+/// };
+///
+/// let port = unsafe { PyObject::from_owned_ptr(py, parse_port("8080")) };
+/// assert_eq!(port.extract::<u16>(py).unwrap(), 8080);
+///
+/// assert!(parse_port("http").is_null());
+/// assert!(PyErr::fetch(py).matches(py, py.get_type::<exc::ValueError>()));
+/// ```
+pub fn into_py_or_raise<T, E>(py: Python, result: Result<T, E>) -> *mut ffi::PyObject
+where
+    T: ToPyObject,
+    E: Into<PyErr>,
+{
+    match result {
+        Ok(value) => value.into_py_object(py).into_object().steal_ptr(),
+        Err(err) => {
+            err.into().restore(py);
+            ptr::null_mut()
+        }
+    }
+}
+
 fn panic_after_error(_py: Python) -> ! {
     unsafe {
         ffi::PyErr_Print();
@@ -520,7 +723,7 @@ pub fn error_on_minusone(py: Python, result: libc::c_int) -> PyResult<()> {
 #[cfg(test)]
 mod tests {
     use crate::objects::exc;
-    use crate::{PyErr, Python};
+    use crate::{ObjectProtocol, PyClone, PyErr, Python, PythonObject, ToPyObject};
 
     #[test]
     fn set_typeerror() {
@@ -530,4 +733,158 @@ mod tests {
         assert!(PyErr::occurred(py));
         drop(PyErr::fetch(py));
     }
+
+    #[test]
+    fn fetch_lazy() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        assert!(py.err_occurred().is_none());
+        assert!(PyErr::fetch_lazy(py).is_none());
+
+        let value = "message".to_py_object(py).into_object();
+        PyErr::new_lazy_init(py.get_type::<exc::KeyError>(), Some(value)).restore(py);
+        let ty = py.err_occurred().unwrap();
+        assert!(ty == py.get_type::<exc::KeyError>());
+        assert!(PyErr::occurred(py)); // still set
+
+        let mut err = PyErr::fetch_lazy(py).unwrap();
+        assert!(!PyErr::occurred(py));
+        assert!(err.matches(py, py.get_type::<exc::LookupError>()));
+        // Not normalized: the value is still the constructor argument.
+        let pvalue = err.pvalue.as_ref().unwrap();
+        assert!(!py.get_type::<exc::KeyError>().is_instance(py, pvalue));
+        err.normalize(py);
+        let pvalue = err.pvalue.as_ref().unwrap();
+        assert!(py.get_type::<exc::KeyError>().is_instance(py, pvalue));
+    }
+
+    #[test]
+    fn clone_and_restore() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let mut err = PyErr::new::<exc::ValueError, _>(py, "original");
+        let instance = err.instance(py);
+        let saved = err.clone_ref(py);
+
+        // Inspecting and dropping the original does not affect the copy.
+        assert!(err.matches(py, py.get_type::<exc::ValueError>()));
+        drop(err);
+
+        saved.restore(py);
+        let mut restored = PyErr::fetch(py);
+        assert!(restored.instance(py) == instance);
+    }
+
+    #[test]
+    fn into_py_or_raise() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let ptr = super::into_py_or_raise(py, Ok::<_, PyErr>(42));
+        let obj = unsafe { crate::PyObject::from_owned_ptr(py, ptr) };
+        assert_eq!(obj.extract::<i32>(py).unwrap(), 42);
+
+        let err = PyErr::new::<exc::ValueError, _>(py, "bad");
+        let ptr = super::into_py_or_raise(py, Err::<i32, _>(err));
+        assert!(ptr.is_null());
+        assert!(PyErr::fetch(py).matches(py, py.get_type::<exc::ValueError>()));
+    }
+
+    #[test]
+    fn new_fmt() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let x = 42;
+        let mut err = PyErr::new_fmt::<exc::ValueError>(py, format_args!("bad value: {}", x));
+        assert!(err.matches(py, py.get_type::<exc::ValueError>()));
+        let msg: String = err
+            .instance(py)
+            .str(py)
+            .unwrap()
+            .to_string(py)
+            .unwrap()
+            .into_owned();
+        assert_eq!(msg, "bad value: 42");
+    }
+
+    #[test]
+    #[cfg(all(unix, feature = "python3-sys"))]
+    fn from_io_error() {
+        use std::io;
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let mut err = PyErr::from_io_error(py, &io::Error::from_raw_os_error(libc::ENOENT));
+        assert!(err.matches(py, py.get_type::<exc::FileNotFoundError>()));
+        let instance = err.instance(py);
+        let errno: i32 = instance.getattr(py, "errno").unwrap().extract(py).unwrap();
+        assert_eq!(errno, libc::ENOENT);
+        let strerror: String = instance
+            .getattr(py, "strerror")
+            .unwrap()
+            .extract(py)
+            .unwrap();
+        assert!(!strerror.contains("os error"), "{}", strerror);
+
+        let err = PyErr::from_io_error(py, &io::Error::from_raw_os_error(libc::EACCES));
+        assert!(err.matches(py, py.get_type::<exc::PermissionError>()));
+
+        let custom = io::Error::new(io::ErrorKind::AlreadyExists, "already there");
+        let mut err = PyErr::from_io_error(py, &custom);
+        assert!(err.matches(py, py.get_type::<exc::FileExistsError>()));
+        let msg: String = err
+            .instance(py)
+            .str(py)
+            .unwrap()
+            .to_string(py)
+            .unwrap()
+            .into_owned();
+        assert_eq!(msg, "already there");
+
+        let other = io::Error::new(io::ErrorKind::Other, "other");
+        let err = PyErr::from_io_error(py, &other);
+        assert!(err.matches(py, py.get_type::<exc::OSError>()));
+        assert!(!err.matches(py, py.get_type::<exc::FileNotFoundError>()));
+    }
+
+    /// Replaces `sys.stderr` with an object collecting the output in `text`;
+    /// the original is kept in `saved`.
+    const CAPTURE_STDERR: &str = "
+import sys
+class Capture(object):
+    text = ''
+    def write(self, s):
+        self.text += s
+    def flush(self):
+        pass
+saved, sys.stderr = sys.stderr, Capture()
+";
+
+    #[test]
+    fn print_and_set_sys_last_vars() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        // capture the traceback instead of printing it to the test output
+        let d = crate::PyDict::new(py);
+        py.run(CAPTURE_STDERR, Some(&d), None).unwrap();
+        PyErr::new::<exc::ValueError, _>(py, "printed error").print_and_set_sys_last_vars(py);
+        assert!(!PyErr::occurred(py));
+        py.run(
+            "captured, sys.stderr = sys.stderr.text, saved",
+            Some(&d),
+            None,
+        )
+        .unwrap();
+        let captured: String = d.get_item(py, "captured").unwrap().extract(py).unwrap();
+        assert!(
+            captured.contains("ValueError: printed error"),
+            "{}",
+            captured
+        );
+        let sys = py.import("sys").unwrap();
+        let last_value = sys.get(py, "last_value").unwrap();
+        assert!(py
+            .get_type::<exc::ValueError>()
+            .is_instance(py, &last_value));
+    }
 }
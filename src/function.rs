@@ -18,13 +18,16 @@
 
 use std::ffi::{CStr, CString};
 use std::panic;
-use std::{any, io, marker, mem, ptr};
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::{any, cell, io, marker, mem, ptr};
 
 use crate::conversion::ToPyObject;
-use crate::err::{self, PyResult};
+use crate::err::{self, PyErr, PyResult};
 use crate::ffi;
+use crate::objectprotocol::ObjectProtocol;
 use crate::objects::{exc, PyDict, PyObject, PyString, PyTuple};
-use crate::python::{PyDrop, Python, PythonObject};
+use crate::python::{PyClone, PyDrop, Python, PythonObject};
+use crate::pythonrun::GILProtected;
 
 #[macro_export]
 #[doc(hidden)]
@@ -73,10 +76,14 @@ macro_rules! py_method_def {
 ///
 /// Form 1:
 ///
-///  * `py` must be an expression of type `Python`
+///  * `py` must be an expression of type `Python`.
+///    If it is an identifier, default value expressions in the parameter list
+///    can refer to it (see below).
 ///  * `f` must be the name of a function that is compatible with the specified
 ///    parameter list, except that a single parameter of type `Python` is prepended.
 ///    The function must return `PyResult<T>` for some `T` that implements `ToPyObject`.
+///    The `Ok` value is converted to a Python object using `ToPyObject::into_py_object()`,
+///    so functions can directly return e.g. `Vec<i32>`, tuples, `Option<T>` or `PyObject`.
 ///
 /// Form 2:
 ///
@@ -86,6 +93,16 @@ macro_rules! py_method_def {
 ///  * The function return type must be `PyResult<T>` for some `T` that
 ///   implements `ToPyObject`.
 ///
+/// # Default values
+///
+/// As described for `py_argparse!()`, a default value expression is evaluated on each call
+/// in which the argument is omitted, with the `py` identifier bound to the `Python` token
+/// of that call. Defaults can therefore create Python objects, such as
+/// `items: PyList = PyList::new(py, &[])`, and each call gets a fresh object.
+/// Note that a default cannot capture local variables of the function creating the callable:
+/// to use a pre-existing Python object as a default, store it in a `static` (e.g. in
+/// a `GILProtected`) and return a new reference to it with `clone_ref(py)`.
+///
 /// # Errors
 ///
 /// * If argument parsing fails, the Rust function will not be called and an
@@ -93,7 +110,22 @@ macro_rules! py_method_def {
 ///   when the Python value does not match the expected type;
 ///   the implementation of `FromPyObject` for your type may document additional
 ///   errors).
-/// * If the Rust function panics, a Python `SystemError` will be raised.
+/// * If the Rust function panics, the panic is caught before it can unwind into
+///   Python, and a Python `RuntimeError` will be raised
+///   (this can be customized using [set_panic_handler](fn.set_panic_handler.html)).
+///
+/// # Annotations
+///
+/// The returned object is a built-in function (`builtin_function_or_method`), like the functions
+/// of C extension modules. Such objects have no `__dict__`, so `py_fn!` cannot attach
+/// `__annotations__` (or a `__signature__`) to them, and assigning these attributes
+/// from Python raises `AttributeError`.
+///
+/// To make the signature visible to `inspect.signature()` and to tools that inspect
+/// runtime objects, use [annotate_function](fn.annotate_function.html) to wrap the
+/// function in a Python function that carries the annotations.
+///
+/// For static type checking, write a stub file (`.pyi`) for the extension module.
 ///
 /// # Example
 /// ```
@@ -117,6 +149,9 @@ macro_rules! py_method_def {
 /// ```
 #[macro_export]
 macro_rules! py_fn {
+    ($py:ident, $f:ident $plist:tt ) => {
+        $crate::py_argparse_parse_plist! { py_fn_impl { $py, $f } $plist }
+    };
     ($py:expr, $f:ident $plist:tt ) => {
         $crate::py_argparse_parse_plist! { py_fn_impl { $py, $f } $plist }
     };
@@ -128,7 +163,30 @@ macro_rules! py_fn {
 #[macro_export]
 #[doc(hidden)]
 macro_rules! py_fn_impl {
-    // Form 1: reference existing function
+    // Form 1: reference existing function.
+    // If `py` is an identifier, default value expressions can refer to it.
+    { $py:ident, $f:ident [ $( { $pname:ident : $ptype:ty = $detail:tt } )* ] } => {{
+        unsafe extern "C" fn wrap(
+            _slf: *mut $crate::_detail::ffi::PyObject,
+            args: *mut $crate::_detail::ffi::PyObject,
+            kwargs: *mut $crate::_detail::ffi::PyObject)
+        -> *mut $crate::_detail::ffi::PyObject
+        {
+            $crate::_detail::handle_callback(
+                stringify!($f), $crate::_detail::PyObjectCallbackConverter,
+                |$py| {
+                    $crate::py_argparse_raw!($py, Some(stringify!($f)), args, kwargs,
+                        [ $( { $pname : $ptype = $detail } )* ]
+                        {
+                            $f($py $(, $pname )* )
+                        })
+                })
+        }
+        unsafe {
+            $crate::_detail::py_fn_impl($py,
+                $crate::py_method_def!(stringify!($f), 0, wrap))
+        }
+    }};
     { $py:expr, $f:ident [ $( { $pname:ident : $ptype:ty = $detail:tt } )* ] } => {{
         unsafe extern "C" fn wrap(
             _slf: *mut $crate::_detail::ffi::PyObject,
@@ -162,6 +220,284 @@ pub unsafe fn py_fn_impl(py: Python, method_def: *mut ffi::PyMethodDef) -> PyObj
     err::from_owned_ptr_or_panic(py, ffi::PyCFunction_New(method_def, ptr::null_mut()))
 }
 
+/// Creates a Python callable object that invokes a Rust closure.
+///
+/// Syntax: `py_closure!(py, closure)`
+///
+///  * `py` must be an expression of type `Python`.
+///  * `closure` must implement
+///    `FnMut(Python, &PyTuple, Option<&PyDict>) -> PyResult<T> + Send + 'static`
+///    for some `T` that implements `ToPyObject`.
+///    It receives the positional arguments and the (optional) keyword arguments
+///    of each call.
+///
+/// Unlike `py_fn!`, the closure may capture Rust state (for example a handle to
+/// a database connection); the state is owned by the resulting Python object and
+/// is dropped together with it.
+/// Because Python objects can be passed to other threads,
+/// the closure must be `Send + 'static`.
+///
+/// The closure must not call itself recursively (through Python code);
+/// such a nested call will raise `RuntimeError` instead.
+///
+/// # Example
+/// ```
+/// use cpython::{Python, PyDict, py_closure};
+///
+/// fn main() {
+///     let gil = Python::acquire_gil();
+///     let py = gil.python();
+///     let mut counter = 0;
+///     let next_id = py_closure!(py, move |_py, _args, _kwargs| {
+///         counter += 1;
+///         Ok(counter)
+///     });
+///     let dict = PyDict::new(py);
+///     dict.set_item(py, "next_id", next_id).unwrap();
+///     py.run("assert next_id() == 1 and next_id() == 2", None, Some(&dict)).unwrap();
+/// }
+/// ```
+#[macro_export]
+macro_rules! py_closure {
+    ($py:expr, $f:expr) => {
+        $crate::_detail::py_closure_impl($py, $f)
+    };
+}
+
+/// Releases the GIL while evaluating a block of Rust code.
+///
+/// Other Python threads can run while the block executes; the GIL is reacquired
+/// afterwards, and also if the block panics. Use this in `py_fn!` functions and
+/// `py_class!` methods that perform long-running Rust computations.
+/// The block cannot use `py` or any other Python objects, as the GIL is not held.
+///
+/// The macro has two forms:
+///
+///  * `release_gil!(py, { ... })` evaluates to the value of the block.
+///    This is shorthand for `py.allow_threads(|| { ... })`.
+///  * `release_gil!(py, { ... }, map_err)`: the block must evaluate to a `Result<T, E>`.
+///    `map_err` is called as `map_err(py, e)` to convert an error into a `PyErr`
+///    once the GIL is reacquired, and the error is returned from the surrounding
+///    function, which must return `PyResult`. Otherwise the macro evaluates to the `T` value.
+///    Within the block, `?` can be used for errors convertible into `E`.
+///
+/// As the block is evaluated in a closure, `return` and `?` inside it leave the block,
+/// not the surrounding function.
+///
+/// # Example
+/// ```
+/// use cpython::{exc, py_fn, release_gil, ObjectProtocol, PyErr, PyResult, Python};
+/// use std::num::ParseIntError;
+///
+/// fn sum_of_squares(py: Python, n: u64) -> PyResult<u64> {
+///     Ok(release_gil!(py, { (1..=n).map(|i| i * i).sum::<u64>() }))
+/// }
+///
+/// fn parse_sum(py: Python, text: String) -> PyResult<u64> {
+///     let sum = release_gil!(py, {
+///         let mut sum = 0;
+///         for word in text.split_whitespace() {
+///             sum += word.parse::<u64>()?;
+///         }
+///         Ok(sum)
+///     }, |py, e: ParseIntError| PyErr::new::<exc::ValueError, _>(py, e.to_string()));
+///     Ok(sum)
+/// }
+///
+/// fn main() {
+///     let gil = Python::acquire_gil();
+///     let py = gil.python();
+///     let f = py_fn!(py, sum_of_squares(n: u64));
+///     let v: u64 = f.call(py, (3,), None).unwrap().extract(py).unwrap();
+///     assert_eq!(v, 14);
+///     let f = py_fn!(py, parse_sum(text: String));
+///     let v: u64 = f.call(py, ("1 2 3",), None).unwrap().extract(py).unwrap();
+///     assert_eq!(v, 6);
+///     assert!(f.call(py, ("1 x",), None).is_err());
+/// }
+/// ```
+#[macro_export]
+macro_rules! release_gil {
+    ($py:expr, $body:block) => {
+        $crate::Python::allow_threads($py, || $body)
+    };
+    ($py:expr, $body:block, $map_err:expr) => {{
+        let py: $crate::Python = $py;
+        match $crate::Python::allow_threads(py, || $body) {
+            Ok(value) => value,
+            Err(e) => {
+                let err: $crate::PyErr = ($map_err)(py, e);
+                return Err(err.into());
+            }
+        }
+    }};
+}
+
+/// Wraps `func` in a Python function whose `__annotations__` are a copy of `annotations`.
+///
+/// Built-in functions such as those created by `py_fn!` cannot carry annotations,
+/// so `func` itself is left unchanged: the return value is a new Python-level
+/// `def wrapper(*args, **kwargs)` that forwards all arguments to `func`, and copies its
+/// `__name__`, `__qualname__`, `__module__` and `__doc__`. Use the wrapper in place of
+/// `func`, e.g. when adding it to a module; calling it adds the overhead of a Python call.
+/// `annotations` maps parameter names (and `"return"` for the return type) to the
+/// annotations, which are usually type objects or strings.
+///
+/// On Python 3, the wrapper also gets a `__signature__` for `inspect.signature()`, listing
+/// the annotated parameters in the order of `annotations` as positional-or-keyword parameters.
+///
+/// # Example
+/// ```
+/// use cpython::{annotate_function, py_fn, PyDict, PyResult, Python};
+///
+/// fn multiply(_py: Python, lhs: i32, rhs: i32) -> PyResult<i32> {
+///     Ok(lhs * rhs)
+/// }
+///
+/// fn main() {
+///     let gil = Python::acquire_gil();
+///     let py = gil.python();
+///     let annotations = PyDict::new(py);
+///     let int = py.eval("int", None, None).unwrap();
+///     annotations.set_item(py, "lhs", &int).unwrap();
+///     annotations.set_item(py, "rhs", &int).unwrap();
+///     annotations.set_item(py, "return", &int).unwrap();
+///     let f = py_fn!(py, multiply(lhs: i32, rhs: i32));
+///     let f = annotate_function(py, f, &annotations).unwrap();
+///
+///     let dict = PyDict::new(py);
+///     dict.set_item(py, "multiply", f).unwrap();
+///     py.run("assert multiply(6, rhs=7) == 42", None, Some(&dict)).unwrap();
+///     py.run("assert multiply.__annotations__['return'] is int", None, Some(&dict)).unwrap();
+/// }
+/// ```
+pub fn annotate_function(py: Python, func: PyObject, annotations: &PyDict) -> PyResult<PyObject> {
+    annotate_helper(py)?.call(py, (func, annotations), None)
+}
+
+/// The Python function implementing `annotate_function()`, created on first use.
+static ANNOTATE: GILProtected<cell::RefCell<Option<PyObject>>> =
+    GILProtected::new(cell::RefCell::new(None));
+
+fn annotate_helper(py: Python) -> PyResult<PyObject> {
+    if let Some(ref annotate) = *ANNOTATE.get(py).borrow() {
+        return Ok(annotate.clone_ref(py));
+    }
+    const ANNOTATE_SRC: &str = "
+def annotate(func, annotations):
+    def wrapper(*args, **kwargs):
+        return func(*args, **kwargs)
+    for attr in ('__name__', '__qualname__', '__module__', '__doc__'):
+        try:
+            setattr(wrapper, attr, getattr(func, attr))
+        except (AttributeError, TypeError):
+            pass
+    wrapper.__annotations__ = dict(annotations)
+    try:
+        from inspect import Parameter, Signature
+    except ImportError:
+        return wrapper
+    wrapper.__signature__ = Signature(
+        [Parameter(name, Parameter.POSITIONAL_OR_KEYWORD, annotation=annotation)
+         for name, annotation in annotations.items() if name != 'return'],
+        return_annotation=annotations.get('return', Signature.empty))
+    return wrapper
+";
+    let globals = PyDict::new(py);
+    py.run(ANNOTATE_SRC, Some(&globals), None)?;
+    let annotate = match globals.get_item(py, "annotate") {
+        Some(annotate) => annotate,
+        None => {
+            return Err(PyErr::new::<exc::RuntimeError, _>(
+                py,
+                "annotate_function: failed to define the wrapper factory",
+            ))
+        }
+    };
+    *ANNOTATE.get(py).borrow_mut() = Some(annotate.clone_ref(py));
+    Ok(annotate)
+}
+
+/// Releases the Python objects cached by this module; called before finalizing Python.
+pub(crate) fn clear_cached_objects(py: Python) {
+    let annotate = ANNOTATE.get(py).borrow_mut().take();
+    drop(annotate);
+}
+
+type ClosureFn = Box<dyn FnMut(Python, &PyTuple, Option<&PyDict>) -> PyResult<PyObject> + Send>;
+
+/// Moves `value` into a new capsule object, which drops it when the capsule is destroyed.
+pub(crate) fn boxed_capsule<T: Send + 'static>(py: Python, value: T) -> PyObject {
+    unsafe extern "C" fn destructor<T>(capsule: *mut ffi::PyObject) {
+        let guard = AbortOnDrop("Cannot unwind out of capsule destructor");
+        drop(Box::from_raw(
+            capsule_data::<T>(capsule) as *const T as *mut T
+        ));
+        mem::forget(guard);
+    }
+    let data = Box::into_raw(Box::new(value));
+    unsafe {
+        let capsule = ffi::PyCapsule_New(
+            data as *mut libc::c_void,
+            ptr::null(),
+            Some(destructor::<T>),
+        );
+        if capsule.is_null() {
+            drop(Box::from_raw(data));
+        }
+        err::from_owned_ptr_or_panic(py, capsule)
+    }
+}
+
+/// Gets the value stored in a capsule created by `boxed_capsule::<T>()`.
+pub(crate) unsafe fn capsule_data<'a, T>(capsule: *mut ffi::PyObject) -> &'a T {
+    &*(ffi::PyCapsule_GetPointer(capsule, ptr::null()) as *const T)
+}
+
+unsafe extern "C" fn closure_wrap(
+    slf: *mut ffi::PyObject,
+    args: *mut ffi::PyObject,
+    kwargs: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    handle_callback("py_closure", PyObjectCallbackConverter, |py| {
+        let args = PyObject::from_borrowed_ptr(py, args).unchecked_cast_into::<PyTuple>();
+        let kwargs = PyObject::from_borrowed_ptr_opt(py, kwargs)
+            .map(|kwargs| kwargs.unchecked_cast_into::<PyDict>());
+        let callback = capsule_data::<cell::RefCell<ClosureFn>>(slf);
+        let mut callback = match callback.try_borrow_mut() {
+            Ok(f) => f,
+            Err(_) => {
+                return Err(PyErr::new::<exc::RuntimeError, _>(
+                    py,
+                    "Rust closure called recursively",
+                ))
+            }
+        };
+        (*callback)(py, &args, kwargs.as_ref())
+    })
+}
+
+pub fn py_closure_impl<F, T>(py: Python, mut f: F) -> PyObject
+where
+    F: FnMut(Python, &PyTuple, Option<&PyDict>) -> PyResult<T> + Send + 'static,
+    T: ToPyObject,
+{
+    let callback: ClosureFn =
+        Box::new(move |py: Python, args: &PyTuple, kwargs: Option<&PyDict>| {
+            f(py, args, kwargs).map(|v| v.into_py_object(py).into_object())
+        });
+    // `py_class!` cannot be used within this crate; instead, the closure is stored
+    // in a capsule that is passed as `self` to a built-in function.
+    let capsule = boxed_capsule(py, cell::RefCell::new(callback));
+    unsafe {
+        let method_def = crate::py_method_def!("closure", 0, closure_wrap);
+        err::from_owned_ptr_or_panic(
+            py,
+            ffi::PyCFunction_NewEx(method_def, capsule.as_ptr(), ptr::null_mut()),
+        )
+    }
+}
+
 pub trait CallbackConverter<S> {
     type R;
 
@@ -214,6 +550,7 @@ where
 {
     let ret = panic::catch_unwind(|| {
         let py = Python::assume_gil_acquired();
+        crate::pythonrun::drain_pending_decrefs(py);
         match f(py) {
             Ok(val) => C::convert(val, py),
             Err(e) => {
@@ -227,7 +564,7 @@ where
         Err(ref err) => {
             // Protect against panics in C::error_value() causing UB
             let guard = AbortOnDrop("handle_panic() / C::error_value()");
-            handle_panic(Python::assume_gil_acquired(), err);
+            handle_panic(Python::assume_gil_acquired(), &**err);
             let errval = C::error_value();
             mem::forget(guard);
             errval
@@ -235,13 +572,84 @@ where
     }
 }
 
-fn handle_panic(_py: Python, _panic: &dyn any::Any) {
-    let msg = cstr!("Rust panic");
-    unsafe {
-        ffi::PyErr_SetString(ffi::PyExc_SystemError, msg.as_ptr());
+/// Function that converts the payload of a Rust panic into a Python exception.
+///
+/// See [set_panic_handler](fn.set_panic_handler.html).
+pub type PanicHandler = fn(Python, &(dyn any::Any + Send)) -> PyErr;
+
+// Null means "use the default handler".
+static PANIC_HANDLER: AtomicPtr<PanicHandler> = AtomicPtr::new(ptr::null_mut());
+
+/// Sets the function used to convert Rust panics into Python exceptions.
+///
+/// When Rust code called from Python (functions created with `py_fn!`,
+/// methods and slots of `py_class!` classes) panics, the panic is caught
+/// at the boundary to Python using `std::panic::catch_unwind`: unwinding into
+/// the Python interpreter would be undefined behavior.
+/// The handler is then called with the panic payload, and the returned
+/// exception is raised in Python; the function returns `NULL` (or the
+/// appropriate error value) to the interpreter.
+///
+/// By default, a `RuntimeError` containing the panic message is raised.
+/// Passing `None` restores the default behavior.
+///
+/// The handler is global to the process. If the handler itself panics, the process is aborted.
+///
+/// Panics are only caught if the crate is compiled with `panic = "unwind"` (the default);
+/// with `panic = "abort"`, a panic always aborts the process.
+///
+/// # Example
+/// ```
+/// use cpython::{exc, panic_message, set_panic_handler, PyErr, Python};
+///
+/// fn panic_to_value_error(py: Python, payload: &(dyn std::any::Any + Send)) -> PyErr {
+///     let msg = panic_message(payload).unwrap_or("Rust panic").to_string();
+///     PyErr::new::<exc::ValueError, _>(py, msg)
+/// }
+///
+/// set_panic_handler(Some(panic_to_value_error));
+/// ```
+pub fn set_panic_handler(handler: Option<PanicHandler>) {
+    // The handler is boxed so that it can be stored in an `AtomicPtr`.
+    // Handlers are rarely replaced, so the previous box is leaked: another thread
+    // may still be using it.
+    let handler = match handler {
+        Some(f) => Box::into_raw(Box::new(f)),
+        None => ptr::null_mut(),
+    };
+    PANIC_HANDLER.store(handler, Ordering::SeqCst);
+}
+
+/// Returns the message of a panic payload, if the panic was created
+/// with a string message (e.g. by `panic!("...")`).
+pub fn panic_message(payload: &(dyn any::Any + Send)) -> Option<&str> {
+    if let Some(s) = payload.downcast_ref::<&'static str>() {
+        Some(s)
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        Some(s)
+    } else {
+        None
     }
 }
 
+fn default_panic_handler(py: Python, payload: &(dyn any::Any + Send)) -> PyErr {
+    let msg = match panic_message(payload) {
+        Some(msg) => format!("Rust panic: {}", msg),
+        None => "Rust panic".to_string(),
+    };
+    PyErr::new::<exc::RuntimeError, _>(py, msg)
+}
+
+fn handle_panic(py: Python, payload: &(dyn any::Any + Send)) {
+    let handler = PANIC_HANDLER.load(Ordering::SeqCst);
+    let handler = if handler.is_null() {
+        default_panic_handler
+    } else {
+        unsafe { *handler }
+    };
+    handler(py, payload).restore(py);
+}
+
 pub struct AbortOnDrop<'a>(pub &'a str);
 
 impl<'a> Drop for AbortOnDrop<'a> {
@@ -0,0 +1,267 @@
+// Copyright (c) 2016 Daniel Grunwald
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this
+// software and associated documentation files (the "Software"), to deal in the Software
+// without restriction, including without limitation the rights to use, copy, modify, merge,
+// publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons
+// to whom the Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED,
+// INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR
+// PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE
+// FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
+// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Exposing Rust iterators to Python as lazy iterator objects.
+
+use std::cell::{Cell, RefCell};
+use std::{mem, ptr};
+
+use crate::conversion::ToPyObject;
+use crate::err::{self, PyErr, PyResult};
+use crate::ffi;
+use crate::function::{handle_callback, AbortOnDrop};
+use crate::objects::{exc, PyObject};
+use crate::py_class::slots::IterNextResultConverter;
+use crate::python::{PyClone, Python, PythonObject, ToPythonPointer};
+use crate::pythonrun::GILProtected;
+
+type NextFn = Box<dyn FnMut(Python, &PyObject) -> Option<PyResult<PyObject>> + Send>;
+
+// `py_class!` cannot be used within this crate, so the iterator type is defined by hand.
+#[repr(C)]
+struct RustIterator {
+    ob_base: ffi::PyObject,
+    /// The object passed to `next_fn`; `None` once the iterator was cleared.
+    state: Option<PyObject>,
+    next_fn: RefCell<Option<NextFn>>,
+}
+
+#[derive(Clone, Copy)]
+struct TypeObjectPtr(*mut ffi::PyTypeObject);
+
+// The type object is only accessed while holding the GIL.
+unsafe impl Send for TypeObjectPtr {}
+
+static TYPE_OBJECT: GILProtected<Cell<Option<TypeObjectPtr>>> = GILProtected::new(Cell::new(None));
+
+/// Returns the `RustIterator` type object, creating it on first use.
+fn type_object(py: Python) -> PyResult<*mut ffi::PyTypeObject> {
+    if let Some(TypeObjectPtr(ty)) = TYPE_OBJECT.get(py).get() {
+        return Ok(ty);
+    }
+    let ty = Box::into_raw(Box::new(ffi::PyTypeObject {
+        tp_name: b"RustIterator\0".as_ptr() as *const libc::c_char,
+        tp_basicsize: mem::size_of::<RustIterator>() as ffi::Py_ssize_t,
+        tp_dealloc: Some(tp_dealloc),
+        tp_flags: ffi::Py_TPFLAGS_DEFAULT | ffi::Py_TPFLAGS_HAVE_GC,
+        tp_traverse: Some(tp_traverse),
+        tp_clear: Some(tp_clear),
+        tp_iter: Some(ffi::PyObject_SelfIter),
+        tp_iternext: Some(tp_iternext),
+        ..ffi::PyTypeObject_INIT
+    }));
+    unsafe {
+        (*ty).init_ob_type(&mut ffi::PyType_Type);
+        // On failure, the type object is leaked: it may already be referenced by Python.
+        if ffi::PyType_Ready(ty) != 0 {
+            return Err(PyErr::fetch(py));
+        }
+    }
+    TYPE_OBJECT.get(py).set(Some(TypeObjectPtr(ty)));
+    Ok(ty)
+}
+
+unsafe extern "C" fn tp_iternext(obj: *mut ffi::PyObject) -> *mut ffi::PyObject {
+    handle_callback("RustIterator.__next__", IterNextResultConverter, |py| {
+        let iter = obj as *mut RustIterator;
+        // `next_fn` may trigger a garbage collection that clears this iterator,
+        // so it gets its own reference to the state.
+        let state = match (*iter).state {
+            Some(ref state) => state.clone_ref(py),
+            None => return Ok(None),
+        };
+        // The Rust iterator may call back into Python code, which could
+        // try to advance this iterator again.
+        let mut next_fn = match (*iter).next_fn.try_borrow_mut() {
+            Ok(f) => f,
+            Err(_) => {
+                return Err(PyErr::new::<exc::ValueError, _>(
+                    py,
+                    "iterator already executing",
+                ))
+            }
+        };
+        match *next_fn {
+            Some(ref mut next_fn) => next_fn(py, &state).transpose(),
+            None => Ok(None),
+        }
+    })
+}
+
+unsafe extern "C" fn tp_traverse(
+    obj: *mut ffi::PyObject,
+    visit: ffi::visitproc,
+    arg: *mut libc::c_void,
+) -> libc::c_int {
+    match (*(obj as *mut RustIterator)).state {
+        Some(ref state) => visit(state.as_ptr(), arg),
+        None => 0,
+    }
+}
+
+unsafe extern "C" fn tp_clear(obj: *mut ffi::PyObject) -> libc::c_int {
+    let guard = AbortOnDrop("RustIterator.__clear__");
+    let iter = obj as *mut RustIterator;
+    // Both values are taken out before they are dropped,
+    // as dropping them may run Python code that uses the iterator.
+    let state = (*iter).state.take();
+    let next_fn = match (*iter).next_fn.try_borrow_mut() {
+        Ok(mut next_fn) => next_fn.take(),
+        // `next_fn` is running; it is dropped together with the iterator.
+        Err(_) => None,
+    };
+    drop(state);
+    drop(next_fn);
+    mem::forget(guard);
+    0
+}
+
+unsafe extern "C" fn tp_dealloc(obj: *mut ffi::PyObject) {
+    let guard = AbortOnDrop("Cannot unwind out of tp_dealloc");
+    let py = Python::assume_gil_acquired();
+    crate::py_class::enter_dealloc(py);
+    ffi::PyObject_GC_UnTrack(obj as *mut libc::c_void);
+    let iter = obj as *mut RustIterator;
+    ptr::drop_in_place(&mut (*iter).state);
+    ptr::drop_in_place(&mut (*iter).next_fn);
+    ffi::PyObject_GC_Del(obj as *mut libc::c_void);
+    crate::py_class::leave_dealloc(py);
+    mem::forget(guard);
+}
+
+fn create_iterator(py: Python, state: PyObject, next_fn: NextFn) -> PyResult<PyObject> {
+    let ty = type_object(py)?;
+    unsafe {
+        let obj = err::result_from_owned_ptr(py, ffi::PyType_GenericAlloc(ty, 0))?;
+        let iter = obj.as_ptr() as *mut RustIterator;
+        ptr::write(&mut (*iter).state, Some(state));
+        ptr::write(&mut (*iter).next_fn, RefCell::new(Some(next_fn)));
+        Ok(obj)
+    }
+}
+
+/// Wraps a Rust iterator into a Python iterator object.
+///
+/// The Rust iterator is advanced lazily, one item per call to `__next__`;
+/// no items are produced before Python asks for them.
+/// `StopIteration` is raised once the Rust iterator is exhausted.
+/// If the iterator yields an `Err`, the error is raised from `__next__`.
+///
+/// Because Python objects may be passed to other threads,
+/// the iterator must be `Send + 'static`.
+///
+/// # Example
+/// ```
+/// use cpython::{py_generator, Python, PyDict, PyResult, PyObject, py_fn};
+///
+/// fn squares(py: Python) -> PyResult<PyObject> {
+///     py_generator(py, (0..).map(|i: u64| Ok(i * i)))
+/// }
+///
+/// fn main() {
+///     let gil = Python::acquire_gil();
+///     let py = gil.python();
+///     let dict = PyDict::new(py);
+///     dict.set_item(py, "squares", py_fn!(py, squares())).unwrap();
+///     py.run("import itertools\n\
+///             assert list(itertools.islice(squares(), 4)) == [0, 1, 4, 9]",
+///            None, Some(&dict)).unwrap();
+/// }
+/// ```
+pub fn py_generator<I, T>(py: Python, mut iter: I) -> PyResult<PyObject>
+where
+    I: Iterator<Item = PyResult<T>> + Send + 'static,
+    T: ToPyObject,
+{
+    py_generator_from_fn(py, move |_| iter.next())
+}
+
+/// Wraps a closure into a Python iterator object.
+///
+/// Like `py_generator()`, but each item is produced by calling `next_fn` with the
+/// `Python` token, which allows the closure to access Python objects.
+/// Returning `None` ends the iteration.
+///
+/// Python objects captured by the closure are not visible to Python's garbage collector,
+/// so a reference cycle through them is never collected. In particular, to iterate over
+/// the data of a `py_class!` instance, use `py_generator_with_state()` with the instance
+/// as state instead of capturing a reference to it.
+///
+/// # Example
+/// ```
+/// use cpython::{py_generator_from_fn, PyObject, PyResult, PyString, Python};
+///
+/// fn countdown(py: Python, start: u32) -> PyResult<PyObject> {
+///     let mut n = start;
+///     py_generator_from_fn(py, move |py| {
+///         n = n.checked_sub(1)?;
+///         Some(Ok(PyString::new(py, &format!("{}...", n + 1))))
+///     })
+/// }
+/// # fn main() {}
+/// ```
+pub fn py_generator_from_fn<F, T>(py: Python, mut next_fn: F) -> PyResult<PyObject>
+where
+    F: FnMut(Python) -> Option<PyResult<T>> + Send + 'static,
+    T: ToPyObject,
+{
+    let next_fn: NextFn = Box::new(move |py: Python, _: &PyObject| {
+        next_fn(py).map(|item| item.map(|v| v.into_py_object(py).into_object()))
+    });
+    create_iterator(py, py.None(), next_fn)
+}
+
+/// Wraps a closure into a Python iterator object that holds a reference to `state`.
+///
+/// Like `py_generator_from_fn()`, but `next_fn` is also passed a reference to `state`.
+/// Unlike the Python objects captured by the closure, `state` is visible to Python's
+/// garbage collector, so reference cycles through it can be collected.
+/// Use this to iterate over a `py_class!` instance that may end up holding the
+/// iterator (directly or indirectly), with the instance as `state`.
+///
+/// # Example
+/// ```
+/// use cpython::{py_class, py_generator_with_state, PyClone, PyObject, PyResult};
+///
+/// py_class!(class Names |py| {
+///     data names: Vec<String>;
+///
+///     def iter_upper(&self) -> PyResult<PyObject> {
+///         let mut index = 0;
+///         py_generator_with_state(py, self.clone_ref(py), move |py, this: &Names| {
+///             let name = this.names(py).get(index)?;
+///             index += 1;
+///             Some(Ok(name.to_uppercase()))
+///         })
+///     }
+/// });
+/// # fn main() {}
+/// ```
+pub fn py_generator_with_state<S, F, T>(py: Python, state: S, mut next_fn: F) -> PyResult<PyObject>
+where
+    S: PythonObject,
+    F: FnMut(Python, &S) -> Option<PyResult<T>> + Send + 'static,
+    T: ToPyObject,
+{
+    let next_fn: NextFn = Box::new(move |py: Python, state: &PyObject| {
+        // `state` is the object passed in below, so it has type `S`.
+        let state = unsafe { S::unchecked_downcast_borrow_from(state) };
+        next_fn(py, state).map(|item| item.map(|v| v.into_py_object(py).into_object()))
+    });
+    create_iterator(py, state.into_object(), next_fn)
+}
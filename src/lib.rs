@@ -16,9 +16,6 @@
 // OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
 // DEALINGS IN THE SOFTWARE.
 
-#![cfg_attr(feature="nightly", feature(
-    specialization, // for impl FromPyObject<'s> for Vec<...> (#31844)
-))]
 #![allow(
     unused_imports, // because some imports are only necessary with python 2.x or 3.x
     clippy::missing_safety_doc,
@@ -99,7 +96,9 @@ pub(crate) use python3_sys as ffi;
 pub use ffi::Py_ssize_t;
 
 pub use crate::conversion::{FromPyObject, RefFromPyObject, ToPyObject};
-pub use crate::err::{PyErr, PyResult};
+pub use crate::err::{into_py_or_raise, PyErr, PyResult};
+pub use crate::function::{annotate_function, panic_message, set_panic_handler, PanicHandler};
+pub use crate::generator::{py_generator, py_generator_from_fn, py_generator_with_state};
 pub use crate::objectprotocol::ObjectProtocol;
 pub use crate::objects::*;
 pub use crate::py_class::CompareOp;
@@ -107,7 +106,9 @@ pub use crate::python::{
     PyClone, PyDrop, Python, PythonObject, PythonObjectDowncastError,
     PythonObjectWithCheckedDowncast, PythonObjectWithTypeObject,
 };
-pub use crate::pythonrun::{prepare_freethreaded_python, GILGuard, GILProtected};
+pub use crate::pythonrun::{
+    finalize_python, prepare_freethreaded_python, GILGuard, GILProtected, ThreadStateGuard,
+};
 pub use crate::sharedref::{
     PyLeakedRef, PyLeakedRefMut, PySharedRef, PySharedRefCell, UnsafePyLeaked,
 };
@@ -208,8 +209,10 @@ macro_rules! py_impl_from_py_object_for_python_object {
 pub mod argparse;
 pub mod buffer;
 mod conversion;
+pub mod diagnostics;
 mod err;
 mod function;
+mod generator;
 mod objectprotocol;
 mod objects;
 mod python;
@@ -217,6 +220,8 @@ mod pythonrun;
 //pub mod rustobject;
 pub mod py_class;
 mod sharedref;
+pub mod trace;
+pub mod warnings;
 
 #[cfg(feature = "serde-convert")]
 pub mod serde;
@@ -228,13 +233,14 @@ pub mod _detail {
         pub use crate::ffi::*;
     }
     pub mod libc {
-        pub use libc::{c_char, c_int, c_void};
+        pub use libc::{c_char, c_int, c_void, size_t};
     }
     pub use crate::err::{from_owned_ptr_or_panic, result_from_owned_ptr};
     pub use crate::function::{
-        handle_callback, py_fn_impl, AbortOnDrop, PyObjectCallbackConverter,
+        handle_callback, py_closure_impl, py_fn_impl, AbortOnDrop, PyObjectCallbackConverter,
         PythonObjectCallbackConverter,
     };
+    pub use crate::objectprotocol::assert_py_eq;
     pub use paste;
 }
 
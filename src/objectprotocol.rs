@@ -22,7 +22,7 @@ use std::fmt;
 use crate::conversion::ToPyObject;
 use crate::err::{self, PyErr, PyResult};
 use crate::ffi;
-use crate::objects::{PyDict, PyObject, PyString, PyTuple};
+use crate::objects::{exc, AttrName, PyBytes, PyDict, PyObject, PyString, PyTuple};
 use crate::python::{Python, PythonObject, ToPythonPointer};
 
 /// Trait that contains methods
@@ -51,6 +51,53 @@ pub trait ObjectProtocol: PythonObject {
         })
     }
 
+    /// Retrieves an attribute value if it exists.
+    /// This is equivalent to the Python expression 'getattr(self, attr_name, None)',
+    /// except that `Ok(None)` is returned only if the lookup raises `AttributeError`;
+    /// an attribute whose value is `None` is returned as `Ok(Some(None))`.
+    ///
+    /// Errors other than `AttributeError` are returned unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{ObjectProtocol, Python};
+    ///
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let obj = py.eval("[3, 1, 2]", None, None).unwrap();
+    /// assert!(obj.getattr_opt(py, "sort").unwrap().is_some());
+    /// assert!(obj.getattr_opt(py, "fileno").unwrap().is_none());
+    /// ```
+    fn getattr_opt<N>(&self, py: Python, attr_name: N) -> PyResult<Option<PyObject>>
+    where
+        N: ToPyObject,
+    {
+        match self.getattr(py, attr_name) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) => {
+                if e.matches(py, py.get_type::<exc::AttributeError>()) {
+                    Ok(None)
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    /// Retrieves an attribute value using a pre-created [AttrName](struct.AttrName.html).
+    /// This is equivalent to the Python expression 'self.attr_name'.
+    ///
+    /// Unlike `getattr(py, "name")`, this does not create a Python string for each call.
+    #[inline]
+    fn getattr_cached(&self, py: Python, attr_name: &AttrName) -> PyResult<PyObject> {
+        unsafe {
+            err::result_from_owned_ptr(
+                py,
+                ffi::PyObject_GetAttr(self.as_ptr(), attr_name.as_string().as_object().as_ptr()),
+            )
+        }
+    }
+
     /// Sets an attribute value.
     /// This is equivalent to the Python expression 'self.attr_name = value'.
     #[inline]
@@ -78,6 +125,92 @@ pub trait ObjectProtocol: PythonObject {
         })
     }
 
+    /// Retrieves an attribute value using the default attribute lookup,
+    /// bypassing any `__getattribute__` or `__getattr__` defined by the object's type.
+    /// This is equivalent to the Python expression `object.__getattribute__(self, attr_name)`.
+    ///
+    /// Use this in a `py_class!` `__getattribute__` implementation to delegate to
+    /// the normal lookup of methods, properties and instance attributes.
+    #[inline]
+    fn generic_getattr<N>(&self, py: Python, attr_name: N) -> PyResult<PyObject>
+    where
+        N: ToPyObject,
+    {
+        attr_name.with_borrowed_ptr(py, |attr_name| unsafe {
+            err::result_from_owned_ptr(py, ffi::PyObject_GenericGetAttr(self.as_ptr(), attr_name))
+        })
+    }
+
+    /// Sets an attribute value using the default attribute assignment,
+    /// bypassing any `__setattr__` defined by the object's type.
+    /// This is equivalent to the Python expression `object.__setattr__(self, attr_name, value)`.
+    #[inline]
+    fn generic_setattr<N, V>(&self, py: Python, attr_name: N, value: V) -> PyResult<()>
+    where
+        N: ToPyObject,
+        V: ToPyObject,
+    {
+        attr_name.with_borrowed_ptr(py, move |attr_name| {
+            value.with_borrowed_ptr(py, |value| unsafe {
+                err::error_on_minusone(
+                    py,
+                    ffi::PyObject_GenericSetAttr(self.as_ptr(), attr_name, value),
+                )
+            })
+        })
+    }
+
+    /// Retrieves the attribute dictionary (`__dict__`) of the object.
+    ///
+    /// Returns `None` if the object does not have a `__dict__`
+    /// (e.g. instances of classes using `__slots__`, or most builtin objects).
+    ///
+    /// If `__dict__` is not a real `dict` (e.g. the `mappingproxy` of a class),
+    /// its contents are copied into a new dict; in that case, modifying the
+    /// returned dict does not affect the object.
+    fn instance_dict(&self, py: Python) -> PyResult<Option<PyDict>> {
+        let dict = match self.getattr_opt(py, "__dict__")? {
+            Some(dict) => dict,
+            None => return Ok(None),
+        };
+        if dict.cast_as::<PyDict>(py).is_ok() {
+            Ok(Some(dict.cast_into(py)?))
+        } else {
+            let copy = py.get_type::<PyDict>().call(py, (dict,), None)?;
+            Ok(Some(copy.cast_into(py)?))
+        }
+    }
+
+    /// Retrieves the annotations (`__annotations__`) of a function, class or module.
+    ///
+    /// Returns `None` if the object does not have annotations.
+    ///
+    /// If `resolve` is `false`, the annotations are returned as stored: annotations
+    /// written as strings (or postponed using `from __future__ import annotations`,
+    /// see PEP 563) are returned as strings. Note that for classes, this may find
+    /// the annotations of a base class.
+    ///
+    /// If `resolve` is `true`, the annotations are evaluated using
+    /// `typing.get_type_hints(self)`, which resolves string annotations and,
+    /// for classes, merges the annotations of all base classes.
+    /// This requires the `typing` module (Python 3.5 and later).
+    fn annotations(&self, py: Python, resolve: bool) -> PyResult<Option<PyDict>> {
+        let annotations = match self.getattr_opt(py, "__annotations__")? {
+            Some(annotations) => annotations,
+            None => return Ok(None),
+        };
+        if resolve {
+            let typing = py.import("typing")?;
+            Ok(Some(
+                typing
+                    .call(py, "get_type_hints", (self.as_object(),), None)?
+                    .cast_into(py)?,
+            ))
+        } else {
+            Ok(Some(annotations.cast_into(py)?))
+        }
+    }
+
     /// Compares two Python objects.
     ///
     /// On Python 2, this is equivalent to the Python expression 'cmp(self, other)'.
@@ -167,6 +300,72 @@ pub trait ObjectProtocol: PythonObject {
         })
     }
 
+    /// Compares two Python objects, converting the result to `bool`.
+    ///
+    /// This is equivalent to the Python expression `bool(self <op> other)`;
+    /// see `rich_compare()` for the operators.
+    /// As with the `in` operator, `CompareOp::Eq` and `CompareOp::Ne` consider
+    /// an object to be equal to itself without calling `__eq__`.
+    ///
+    /// Comparing objects that do not support the operator raises `TypeError`
+    /// (on Python 2, objects of different types are ordered arbitrarily instead).
+    fn rich_compare_bool<O>(
+        &self,
+        py: Python,
+        other: O,
+        compare_op: crate::CompareOp,
+    ) -> PyResult<bool>
+    where
+        O: ToPyObject,
+    {
+        other.with_borrowed_ptr(py, |other| unsafe {
+            match ffi::PyObject_RichCompareBool(self.as_ptr(), other, compare_op as libc::c_int) {
+                -1 => Err(PyErr::fetch(py)),
+                result => Ok(result != 0),
+            }
+        })
+    }
+
+    /// Returns whether `self` is less than `other`.
+    /// This is equivalent to the Python expression `self < other`, converted to `bool`.
+    #[inline]
+    fn lt<O>(&self, py: Python, other: O) -> PyResult<bool>
+    where
+        O: ToPyObject,
+    {
+        self.rich_compare_bool(py, other, crate::CompareOp::Lt)
+    }
+
+    /// Returns whether `self` is less than or equal to `other`.
+    /// This is equivalent to the Python expression `self <= other`, converted to `bool`.
+    #[inline]
+    fn le<O>(&self, py: Python, other: O) -> PyResult<bool>
+    where
+        O: ToPyObject,
+    {
+        self.rich_compare_bool(py, other, crate::CompareOp::Le)
+    }
+
+    /// Returns whether `self` is greater than `other`.
+    /// This is equivalent to the Python expression `self > other`, converted to `bool`.
+    #[inline]
+    fn gt<O>(&self, py: Python, other: O) -> PyResult<bool>
+    where
+        O: ToPyObject,
+    {
+        self.rich_compare_bool(py, other, crate::CompareOp::Gt)
+    }
+
+    /// Returns whether `self` is greater than or equal to `other`.
+    /// This is equivalent to the Python expression `self >= other`, converted to `bool`.
+    #[inline]
+    fn ge<O>(&self, py: Python, other: O) -> PyResult<bool>
+    where
+        O: ToPyObject,
+    {
+        self.rich_compare_bool(py, other, crate::CompareOp::Ge)
+    }
+
     /// Compute the string representation of self.
     /// This is equivalent to the Python expression 'repr(self)'.
     #[inline]
@@ -174,6 +373,37 @@ pub trait ObjectProtocol: PythonObject {
         unsafe { err::result_cast_from_owned_ptr(py, ffi::PyObject_Repr(self.as_ptr())) }
     }
 
+    /// Compute the string representation of self, truncated to at most `max_len` characters.
+    ///
+    /// If `repr(self)` is longer than `max_len`, it is cut off and ends with `...`
+    /// (the ellipsis counts towards `max_len`). This is useful for logging objects
+    /// that may be very large, such as long lists.
+    ///
+    /// Note that the full representation is still computed before truncation;
+    /// use Python's `reprlib` module to avoid computing it for deeply nested containers.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{Python, ObjectProtocol, PythonObject, ToPyObject};
+    ///
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let list = (0..1000).collect::<Vec<i32>>().to_py_object(py).into_object();
+    /// assert_eq!(list.repr_truncated(py, 12).unwrap(), "[0, 1, 2,...");
+    /// ```
+    fn repr_truncated(&self, py: Python, max_len: usize) -> PyResult<String> {
+        const ELLIPSIS: &str = "...";
+        let repr = self.repr(py)?;
+        let repr = repr.to_string(py)?;
+        if repr.chars().count() <= max_len {
+            return Ok(repr.into_owned());
+        }
+        let keep = max_len.saturating_sub(ELLIPSIS.len());
+        let mut truncated: String = repr.chars().take(keep).collect();
+        truncated.push_str(&ELLIPSIS[..max_len - keep]);
+        Ok(truncated)
+    }
+
     /// Compute the string representation of self.
     /// This is equivalent to the Python expression 'str(self)'.
     #[inline]
@@ -181,6 +411,44 @@ pub trait ObjectProtocol: PythonObject {
         unsafe { err::result_cast_from_owned_ptr(py, ffi::PyObject_Str(self.as_ptr())) }
     }
 
+    /// Formats self according to `format_spec`, using the object's `__format__` method.
+    /// This is equivalent to the Python expression 'format(self, format_spec)'.
+    ///
+    /// For most types, an empty `format_spec` gives the same result as `str()`.
+    /// (Not to be confused with `PyString::format()`, which implements `str.format`.)
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{Python, ObjectProtocol, PythonObject, ToPyObject};
+    ///
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let pi = 3.14159f64.to_py_object(py).into_object();
+    /// assert_eq!(pi.py_format(py, ".2f").unwrap().to_string(py).unwrap(), "3.14");
+    /// ```
+    #[inline]
+    fn py_format(&self, py: Python, format_spec: &str) -> PyResult<PyString> {
+        let format_spec = PyString::new(py, format_spec);
+        unsafe {
+            err::result_cast_from_owned_ptr(
+                py,
+                ffi::PyObject_Format(self.as_ptr(), format_spec.as_object().as_ptr()),
+            )
+        }
+    }
+
+    /// Compute the bytes representation of self.
+    /// This is equivalent to the Python expression 'bytes(self)'.
+    ///
+    /// On Python 3, this calls `__bytes__` if the object defines it,
+    /// and otherwise falls back to the buffer protocol or to iterating over integers;
+    /// objects supporting none of these raise `TypeError`, as the builtin does.
+    /// On Python 2.7, `bytes` is an alias for `str`, so this is equivalent to `str(self)`.
+    #[inline]
+    fn to_bytes(&self, py: Python) -> PyResult<PyBytes> {
+        unsafe { err::result_cast_from_owned_ptr(py, ffi::PyObject_Bytes(self.as_ptr())) }
+    }
+
     /// Compute the unicode string representation of self.
     /// This is equivalent to the Python expression 'unistr(self)'.
     #[inline]
@@ -190,6 +458,10 @@ pub trait ObjectProtocol: PythonObject {
     }
 
     /// Determines whether this object is callable.
+    /// This is equivalent to the Python expression 'callable(self)'.
+    ///
+    /// A `true` result does not guarantee that calling the object succeeds,
+    /// but `false` means that `call()` will fail with `TypeError`.
     #[inline]
     fn is_callable(&self, _py: Python) -> bool {
         unsafe { ffi::PyCallable_Check(self.as_ptr()) != 0 }
@@ -212,6 +484,144 @@ pub trait ObjectProtocol: PythonObject {
         })
     }
 
+    /// Calls the object with keyword arguments given as `(name, value)` pairs.
+    /// This is equivalent to the Python expression: 'self(*args, **kwargs)'
+    ///
+    /// `args` is handled the same as in `call()`.
+    /// The keyword arguments are collected into a dict before the call;
+    /// use `call()` directly if you already have a `PyDict`.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{NoArgs, ObjectProtocol, Python};
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let dict = py.eval("dict", None, None).unwrap();
+    /// let d = dict.call_kw(py, NoArgs, vec![("a", 1), ("b", 2)]).unwrap();
+    /// assert_eq!(d.len(py).unwrap(), 2);
+    /// ```
+    #[inline]
+    fn call_kw<'k, A, I, V>(&self, py: Python, args: A, kwargs: I) -> PyResult<PyObject>
+    where
+        A: ToPyObject<ObjectType = PyTuple>,
+        I: IntoIterator<Item = (&'k str, V)>,
+        V: ToPyObject,
+    {
+        let dict = PyDict::new(py);
+        for (name, value) in kwargs {
+            dict.set_item(py, name, value)?;
+        }
+        self.call(py, args, Some(&dict))
+    }
+
+    /// Calls the object using the vectorcall convention.
+    /// This is equivalent to the Python expression: 'self(*args, **kwargs)'
+    ///
+    /// `args` contains the positional arguments followed by the values of the
+    /// keyword arguments; `kwnames` is a tuple of strings naming the last
+    /// `kwnames.len()` entries of `args`.
+    /// Fails with `ValueError` if `kwnames` is longer than `args`, and with `TypeError`
+    /// if a keyword name is not a `str` (exactly; subclasses are rejected) or occurs twice.
+    ///
+    /// On Python 3.9 and later, this uses `PyObject_Vectorcall`: callables implementing
+    /// the vectorcall protocol are invoked directly with a pointer to `args`, without
+    /// building an argument tuple or keyword dict. On older versions, this falls back
+    /// to a regular call.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{ObjectProtocol, PyTuple, Python, PythonObject, ToPyObject};
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let int = py.eval("int", None, None).unwrap();
+    /// let args = ["ff".to_py_object(py).into_object(), 16i32.to_py_object(py).into_object()];
+    /// let kwnames = PyTuple::new(py, &["base".to_py_object(py).into_object()]);
+    /// let result = int.vectorcall(py, &args, Some(&kwnames)).unwrap();
+    /// assert_eq!(result.extract::<i32>(py).unwrap(), 255);
+    /// ```
+    fn vectorcall(
+        &self,
+        py: Python,
+        args: &[PyObject],
+        kwnames: Option<&PyTuple>,
+    ) -> PyResult<PyObject> {
+        let nkwargs = kwnames.map_or(0, |names| names.len(py));
+        if nkwargs > args.len() {
+            return Err(PyErr::new::<exc::ValueError, _>(
+                py,
+                "vectorcall: more keyword names than arguments",
+            ));
+        }
+        if let Some(names) = kwnames {
+            check_kwnames(py, names)?;
+        }
+        let nargs = args.len() - nkwargs;
+
+        #[cfg(all(Py_3_9, not(Py_LIMITED_API)))]
+        unsafe {
+            // `PyObject` has the same layout as `*mut ffi::PyObject`, so the slice
+            // can be passed directly as the argument array.
+            // PY_VECTORCALL_ARGUMENTS_OFFSET is not set: args[-1] is not ours to modify.
+            let kwnames = if nkwargs > 0 {
+                kwnames.as_ptr()
+            } else {
+                std::ptr::null_mut()
+            };
+            let result = ffi::PyObject_Vectorcall(
+                self.as_ptr(),
+                args.as_ptr() as *const *mut ffi::PyObject,
+                nargs as libc::size_t,
+                kwnames,
+            );
+            err::result_from_owned_ptr(py, result)
+        }
+
+        #[cfg(not(all(Py_3_9, not(Py_LIMITED_API))))]
+        {
+            let positional = PyTuple::new(py, &args[..nargs]);
+            match kwnames {
+                Some(names) if nkwargs > 0 => {
+                    let dict = PyDict::new(py);
+                    for (name, value) in names.iter(py).zip(&args[nargs..]) {
+                        dict.set_item(py, name, value)?;
+                    }
+                    self.call(py, positional, Some(&dict))
+                }
+                _ => self.call(py, positional, None),
+            }
+        }
+    }
+
+    /// Calls the object with positional arguments taken from a slice.
+    /// This is equivalent to the Python expression: 'self(*args)'
+    ///
+    /// Unlike `call()`, this does not require building a `PyTuple` first:
+    /// on Python 3.9 and later, vectorcall-capable callables receive the slice directly
+    /// (see `vectorcall()`). An empty slice calls the object without arguments.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{ObjectProtocol, Python, PythonObject, ToPyObject};
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let max = py.eval("max", None, None).unwrap();
+    /// let args = [3i32.to_py_object(py).into_object(), 7i32.to_py_object(py).into_object()];
+    /// let result = max.call_args(py, &args).unwrap();
+    /// assert_eq!(result.extract::<i32>(py).unwrap(), 7);
+    /// ```
+    #[inline]
+    fn call_args(&self, py: Python, args: &[PyObject]) -> PyResult<PyObject> {
+        #[cfg(Py_3_9)]
+        {
+            if args.is_empty() {
+                return unsafe {
+                    err::result_from_owned_ptr(py, ffi::PyObject_CallNoArgs(self.as_ptr()))
+                };
+            }
+        }
+        self.vectorcall(py, args, None)
+    }
+
     /// Calls a method on the object.
     /// This is equivalent to the Python expression: 'self.name(*args, **kwargs)'
     ///
@@ -246,6 +656,109 @@ pub trait ObjectProtocol: PythonObject {
         self.getattr(py, name)?.call(py, args, kwargs)
     }
 
+    /// Calls a method on the object with positional arguments taken from a slice.
+    /// This is equivalent to the Python expression: 'self.name(*args)'
+    ///
+    /// This is the method call counterpart of `call_args()`. On Python 3.9 and later,
+    /// it uses `PyObject_VectorcallMethod`, which avoids creating both the argument tuple
+    /// and the bound method object.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{ObjectProtocol, Python, PythonObject, ToPyObject};
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let s = "a-b-c".to_py_object(py).into_object();
+    /// let args = ["-".to_py_object(py).into_object(), 1i32.to_py_object(py).into_object()];
+    /// let parts = s.call_method_args(py, "split", &args).unwrap();
+    /// assert_eq!(parts.extract::<Vec<String>>(py).unwrap(), ["a", "b-c"]);
+    /// ```
+    fn call_method_args(&self, py: Python, name: &str, args: &[PyObject]) -> PyResult<PyObject> {
+        #[cfg(all(Py_3_9, not(Py_LIMITED_API)))]
+        {
+            let name = PyString::new(py, name);
+            // The receiver is passed as the first element of the argument array.
+            let mut vector = Vec::with_capacity(args.len() + 1);
+            vector.push(self.as_ptr());
+            vector.extend(args.iter().map(|arg| arg.as_ptr()));
+            unsafe {
+                err::result_from_owned_ptr(
+                    py,
+                    ffi::PyObject_VectorcallMethod(
+                        name.as_object().as_ptr(),
+                        vector.as_ptr(),
+                        vector.len() as libc::size_t,
+                        std::ptr::null_mut(),
+                    ),
+                )
+            }
+        }
+        #[cfg(not(all(Py_3_9, not(Py_LIMITED_API))))]
+        {
+            self.getattr(py, name)?.call_args(py, args)
+        }
+    }
+
+    /// Calls a method on the object, with keyword arguments given as `(name, value)` pairs.
+    /// This is equivalent to the Python expression: 'self.name(*args, **kwargs)'
+    ///
+    /// `args` is handled the same as in `call_method()`; see `call_kw()` for details
+    /// on the keyword arguments.
+    ///
+    /// Errors raised by the call (e.g. a `TypeError` when passing a positional-only
+    /// parameter by keyword) are returned unchanged.
+    #[inline]
+    fn call_method_kw<'k, A, I, V>(
+        &self,
+        py: Python,
+        name: &str,
+        args: A,
+        kwargs: I,
+    ) -> PyResult<PyObject>
+    where
+        A: ToPyObject<ObjectType = PyTuple>,
+        I: IntoIterator<Item = (&'k str, V)>,
+        V: ToPyObject,
+    {
+        self.getattr(py, name)?.call_kw(py, args, kwargs)
+    }
+
+    /// Calls a method on the object if it exists.
+    /// This is equivalent to the Python expression: 'self.name(*args, **kwargs)',
+    /// except that `Ok(None)` is returned if looking up `self.name` raises `AttributeError`.
+    ///
+    /// Errors raised by the method itself (including `AttributeError`) are returned unchanged.
+    ///
+    /// Note that the method is looked up on the object, as with `getattr()`;
+    /// the interpreter looks up special methods such as `__fspath__` on the type instead.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{NoArgs, ObjectProtocol, Python};
+    ///
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let obj = py.eval("[3, 1, 2]", None, None).unwrap();
+    /// assert!(obj.call_method_opt(py, "sort", NoArgs, None).unwrap().is_some());
+    /// assert!(obj.call_method_opt(py, "fileno", NoArgs, None).unwrap().is_none());
+    /// ```
+    fn call_method_opt<A>(
+        &self,
+        py: Python,
+        name: &str,
+        args: A,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<Option<PyObject>>
+    where
+        A: ToPyObject<ObjectType = PyTuple>,
+    {
+        let method = match self.getattr_opt(py, name)? {
+            Some(method) => method,
+            None => return Ok(None),
+        };
+        method.call(py, args, kwargs).map(Some)
+    }
+
     /// Retrieves the hash code of the object.
     /// This is equivalent to the Python expression: 'hash(self)'
     #[inline]
@@ -282,7 +795,29 @@ pub trait ObjectProtocol: PythonObject {
         }
     }
 
+    /// Returns an estimate of the number of items in the object,
+    /// falling back to `default` if no estimate is available.
+    /// This is equivalent to the Python expression: 'operator.length_hint(self, default)'
+    ///
+    /// The result is only a hint, and may be used to pre-size a collection
+    /// before consuming an iterable.
+    #[inline]
+    fn length_hint(&self, py: Python, default: usize) -> PyResult<usize> {
+        #[cfg(feature = "python27-sys")]
+        let v = unsafe { ffi::_PyObject_LengthHint(self.as_ptr(), default as ffi::Py_ssize_t) };
+        #[cfg(feature = "python3-sys")]
+        let v = unsafe { ffi::PyObject_LengthHint(self.as_ptr(), default as ffi::Py_ssize_t) };
+        if v == -1 && PyErr::occurred(py) {
+            Err(PyErr::fetch(py))
+        } else {
+            Ok(v as usize)
+        }
+    }
+
     /// This is equivalent to the Python expression: 'self[key]'
+    ///
+    /// Works for any object implementing the mapping or sequence protocol.
+    /// `PyDict`, `PyList` and `PyTuple` provide faster specialized methods.
     #[inline]
     fn get_item<K>(&self, py: Python, key: K) -> PyResult<PyObject>
     where
@@ -320,6 +855,27 @@ pub trait ObjectProtocol: PythonObject {
         })
     }
 
+    /// Converts self to an integer using `__index__`.
+    /// This is equivalent to the Python expression 'operator.index(self)'.
+    ///
+    /// Unlike extracting a Rust integer directly (which also accepts e.g. `float`),
+    /// this only accepts objects that are integer-like, and fails with `TypeError` otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{Python, ObjectProtocol};
+    ///
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let obj = py.eval("True", None, None).unwrap();
+    /// assert_eq!(obj.to_index(py).unwrap().extract::<i64>(py).unwrap(), 1);
+    /// assert!(py.eval("1.5", None, None).unwrap().to_index(py).is_err());
+    /// ```
+    #[inline]
+    fn to_index(&self, py: Python) -> PyResult<PyObject> {
+        unsafe { err::result_from_owned_ptr(py, ffi::PyNumber_Index(self.as_ptr())) }
+    }
+
     /// Takes an object and returns an iterator for it.
     /// This is typically a new iterator but if the argument
     /// is an iterator, this returns itself.
@@ -328,10 +884,99 @@ pub trait ObjectProtocol: PythonObject {
         let obj = unsafe { err::result_from_owned_ptr(py, ffi::PyObject_GetIter(self.as_ptr())) }?;
         Ok(crate::objects::PyIterator::from_object(py, obj)?)
     }
+
+    /// Like `iter()`, but also returns the object's length hint
+    /// (see `length_hint()`, with a default of 0).
+    ///
+    /// The hint allows pre-sizing the collection the iterator is drained into.
+    /// It is only an estimate: the iterator may produce fewer or more items.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{Python, ObjectProtocol};
+    ///
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let obj = py.eval("range(5)", None, None).unwrap();
+    /// let (iter, hint) = obj.iter_with_hint(py).unwrap();
+    /// let mut items = Vec::with_capacity(hint);
+    /// for item in iter {
+    ///     items.push(item.unwrap().extract::<i32>(py).unwrap());
+    /// }
+    /// assert_eq!(items, vec![0, 1, 2, 3, 4]);
+    /// ```
+    #[inline]
+    fn iter_with_hint<'p>(
+        &self,
+        py: Python<'p>,
+    ) -> PyResult<(crate::objects::PyIterator<'p>, usize)> {
+        let hint = self.length_hint(py, 0)?;
+        Ok((self.iter(py)?, hint))
+    }
+
+    /// Drains the iterable into a `Vec`.
+    /// This is equivalent to the Python expression: 'list(self)'
+    ///
+    /// The vector is pre-sized using the length hint.
+    /// Iteration stops at the first error, which is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{Python, ObjectProtocol};
+    ///
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let obj = py.eval("(x * x for x in range(4))", None, None).unwrap();
+    /// let items = obj.collect_list(py).unwrap();
+    /// assert_eq!(items.len(), 4);
+    /// assert_eq!(items[3].extract::<i32>(py).unwrap(), 9);
+    /// ```
+    fn collect_list(&self, py: Python) -> PyResult<Vec<PyObject>> {
+        let (iter, hint) = self.iter_with_hint(py)?;
+        let mut items = Vec::with_capacity(hint);
+        for item in iter {
+            items.push(item?);
+        }
+        Ok(items)
+    }
 }
 
 impl ObjectProtocol for PyObject {}
 
+/// Checks that `kwnames` can be passed to `PyObject_Vectorcall`,
+/// which requires the keyword names to be unique exact strings.
+fn check_kwnames(py: Python, kwnames: &PyTuple) -> PyResult<()> {
+    let names = kwnames.as_slice(py);
+    for (i, name) in names.iter().enumerate() {
+        let is_str = unsafe {
+            #[cfg(feature = "python27-sys")]
+            {
+                ffi::PyString_CheckExact(name.as_ptr()) != 0
+                    || ffi::PyUnicode_CheckExact(name.as_ptr()) != 0
+            }
+            #[cfg(feature = "python3-sys")]
+            {
+                ffi::PyUnicode_CheckExact(name.as_ptr()) != 0
+            }
+        };
+        if !is_str {
+            return Err(PyErr::new::<exc::TypeError, _>(
+                py,
+                "vectorcall: keyword names must be strings",
+            ));
+        }
+        for earlier in &names[..i] {
+            if name.rich_compare_bool(py, earlier, crate::CompareOp::Eq)? {
+                return Err(PyErr::new::<exc::TypeError, _>(
+                    py,
+                    format!("vectorcall: keyword argument {} repeated", name),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
 impl fmt::Debug for PyObject {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         // TODO: we shouldn't use fmt::Error when repr() fails
@@ -352,12 +997,106 @@ impl fmt::Display for PyObject {
     }
 }
 
+/// Asserts that two values are equal, using the Python `==` operator.
+///
+/// Syntax: `assert_py_eq!(py, left, right)` or `assert_py_eq!(py, left, right, "format", args...)`
+///
+/// `left` and `right` can be Python objects or any other values that implement `ToPyObject`,
+/// so that e.g. `assert_py_eq!(py, obj, vec![1, 2])` works.
+/// The comparison uses `ObjectProtocol::rich_compare_bool()` with `CompareOp::Eq`;
+/// like the `in` operator, it considers an object to be equal to itself.
+///
+/// On failure, this panics with a message that contains the `repr()` of both values,
+/// similar to `assert_eq!`. If the comparison itself raises an exception, the panic message
+/// contains the exception instead.
+///
+/// # Example
+/// ```
+/// use cpython::{assert_py_eq, ObjectProtocol, Python};
+///
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let list = py.eval("[1, 2] + [3]", None, None).unwrap();
+/// assert_py_eq!(py, list, vec![1, 2, 3]);
+/// assert_py_eq!(py, list.len(py).unwrap(), 3, "unexpected length of {}", list);
+/// ```
+/// ```should_panic
+/// # use cpython::{assert_py_eq, Python};
+/// # let gil = Python::acquire_gil();
+/// # let py = gil.python();
+/// // panics with:
+/// // assertion failed: `(left == right)`
+/// //   left: `1`,
+/// //  right: `2`
+/// assert_py_eq!(py, 1, 2);
+/// ```
+#[macro_export]
+macro_rules! assert_py_eq {
+    ($py:expr, $left:expr, $right:expr $(,)?) => {
+        $crate::_detail::assert_py_eq($py, &$left, &$right, None)
+    };
+    ($py:expr, $left:expr, $right:expr, $($arg:tt)+) => {
+        $crate::_detail::assert_py_eq($py, &$left, &$right, Some(format_args!($($arg)+)))
+    };
+}
+
+#[doc(hidden)]
+pub fn assert_py_eq<L, R>(py: Python, left: &L, right: &R, message: Option<fmt::Arguments>)
+where
+    L: ToPyObject,
+    R: ToPyObject,
+{
+    fn describe_err(py: Python, mut err: PyErr) -> String {
+        let instance = err.instance(py);
+        format!(
+            "{}: {}",
+            err.get_type(py).name(py),
+            instance
+                .str(py)
+                .map(|s| s.to_string_lossy(py).into_owned())
+                .unwrap_or_default()
+        )
+    }
+    fn describe(py: Python, obj: &PyObject) -> String {
+        match obj.repr(py) {
+            Ok(repr) => repr.to_string_lossy(py).into_owned(),
+            Err(err) => format!("<repr() failed: {}>", describe_err(py, err)),
+        }
+    }
+
+    let left = left.to_py_object(py).into_object();
+    let right = right.to_py_object(py).into_object();
+    let suffix = match message {
+        Some(message) => format!(": {}", message),
+        None => String::new(),
+    };
+    match left.rich_compare_bool(py, &right, crate::CompareOp::Eq) {
+        Ok(true) => {}
+        Ok(false) => panic!(
+            "assertion failed: `(left == right)`\n  left: `{}`,\n right: `{}`{}",
+            describe(py, &left),
+            describe(py, &right),
+            suffix
+        ),
+        Err(err) => panic!(
+            "assertion failed: `(left == right)`: comparison raised {}\n  left: `{}`,\n right: `{}`{}",
+            describe_err(py, err),
+            describe(py, &left),
+            describe(py, &right),
+            suffix
+        ),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::ObjectProtocol;
     use crate::conversion::ToPyObject;
-    use crate::objects::{PyList, PyTuple};
+    #[cfg(feature = "python3-sys")]
+    use crate::objects::PyDict;
+    use crate::objects::{exc, NoArgs, PyList, PyTuple};
     use crate::python::{Python, PythonObject};
+    use std::cmp::Ordering;
 
     #[test]
     fn test_debug_string() {
@@ -375,6 +1114,20 @@ mod test {
         assert_eq!(format!("{}", v), "Hello\n");
     }
 
+    #[test]
+    fn test_repr_truncated() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let v = "Hello".to_py_object(py).into_object();
+        assert_eq!(v.repr_truncated(py, 7).unwrap(), "'Hello'");
+        assert_eq!(v.repr_truncated(py, 100).unwrap(), "'Hello'");
+        assert_eq!(v.repr_truncated(py, 6).unwrap(), "'He...");
+        assert_eq!(v.repr_truncated(py, 2).unwrap(), "..");
+        assert_eq!(v.repr_truncated(py, 0).unwrap(), "");
+        let list = vec![1; 1_000_000].to_py_object(py).into_object();
+        assert_eq!(list.repr_truncated(py, 10).unwrap(), "[1, 1, ...");
+    }
+
     #[test]
     fn test_compare() {
         use std::cmp::Ordering;
@@ -385,4 +1138,510 @@ mod test {
         assert_eq!(one.compare(py, 2).unwrap(), Ordering::Less);
         assert_eq!(one.compare(py, 0).unwrap(), Ordering::Greater);
     }
+
+    #[test]
+    fn test_ordering_comparisons() {
+        use crate::CompareOp;
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let one = 1i32.to_py_object(py).into_object();
+        assert!(one.lt(py, 2).unwrap());
+        assert!(!one.lt(py, 1).unwrap());
+        assert!(one.le(py, 1).unwrap());
+        assert!(!one.le(py, 0).unwrap());
+        assert!(one.gt(py, 0).unwrap());
+        assert!(!one.gt(py, 1).unwrap());
+        assert!(one.ge(py, 1).unwrap());
+        assert!(!one.ge(py, 2).unwrap());
+        assert!(one.rich_compare_bool(py, 1, CompareOp::Eq).unwrap());
+        assert!(one.rich_compare_bool(py, 2, CompareOp::Ne).unwrap());
+
+        let nan = py.eval("float('nan')", None, None).unwrap();
+        assert!(nan.rich_compare_bool(py, &nan, CompareOp::Eq).unwrap());
+        assert!(!nan.ge(py, &nan).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "python3-sys")]
+    fn test_ordering_comparisons_unsupported() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let one = 1i32.to_py_object(py).into_object();
+        let err = one.lt(py, "a").unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+        let obj = py.eval("object()", None, None).unwrap();
+        assert!(obj.ge(py, &obj).is_err());
+    }
+
+    #[test]
+    fn test_call_kw() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let f = py
+            .eval(
+                "lambda *args, **kwargs: (args, sorted(kwargs.items()))",
+                None,
+                None,
+            )
+            .unwrap();
+        let result = f.call_kw(py, (1,), vec![("b", 3), ("a", 2)]).unwrap();
+        assert_eq!(
+            result
+                .extract::<(Vec<i32>, Vec<(String, i32)>)>(py)
+                .unwrap(),
+            (vec![1], vec![("a".to_string(), 2), ("b".to_string(), 3)])
+        );
+    }
+
+    #[test]
+    fn test_length_hint() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = py.eval("[1, 2, 3]", None, None).unwrap();
+        assert_eq!(list.length_hint(py, 10).unwrap(), 3);
+        let it = py.eval("iter([1, 2, 3])", None, None).unwrap();
+        assert_eq!(it.length_hint(py, 10).unwrap(), 3);
+        let gen = py.eval("(x for x in [1, 2, 3])", None, None).unwrap();
+        assert_eq!(gen.length_hint(py, 10).unwrap(), 10);
+    }
+
+    #[test]
+    fn test_call_method_opt() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = PyList::new(py, &[]).into_object();
+        let res = list.call_method_opt(py, "append", (1,), None).unwrap();
+        assert!(res.unwrap() == py.None());
+        assert_eq!(list.len(py).unwrap(), 1);
+        assert!(list
+            .call_method_opt(py, "missing", NoArgs, None)
+            .unwrap()
+            .is_none());
+
+        // errors raised by the method are propagated, even AttributeError
+        let obj = py
+            .eval(
+                "type('A', (object,), {'f': lambda self: self.missing})()",
+                None,
+                None,
+            )
+            .unwrap();
+        let err = obj.call_method_opt(py, "f", NoArgs, None).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::AttributeError>()));
+    }
+
+    #[test]
+    fn test_getattr_opt() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj = py
+            .eval(
+                "type('A', (object,), {'a': None, 'b': property(lambda self: 1 // 0)})()",
+                None,
+                None,
+            )
+            .unwrap();
+        assert!(obj.getattr_opt(py, "a").unwrap().unwrap() == py.None());
+        assert!(obj.getattr_opt(py, "missing").unwrap().is_none());
+
+        // errors other than AttributeError are propagated
+        let err = obj.getattr_opt(py, "b").unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::ZeroDivisionError>()));
+    }
+
+    #[test]
+    fn test_is_callable() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        assert!(py.eval("len", None, None).unwrap().is_callable(py));
+        assert!(py.eval("object", None, None).unwrap().is_callable(py));
+        assert!(!py.eval("42", None, None).unwrap().is_callable(py));
+        assert!(!py.None().is_callable(py));
+    }
+
+    #[test]
+    fn test_to_bytes() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj = py.eval("bytearray(b'abc')", None, None).unwrap();
+        assert_eq!(obj.to_bytes(py).unwrap().data(py), b"abc");
+    }
+
+    #[test]
+    #[cfg(feature = "python3-sys")]
+    fn test_to_bytes_protocol() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj = py
+            .eval(
+                "type('B', (object,), {'__bytes__': lambda self: b'xyz'})()",
+                None,
+                None,
+            )
+            .unwrap();
+        assert_eq!(obj.to_bytes(py).unwrap().data(py), b"xyz");
+
+        let err = py.None().to_bytes(py).err().unwrap();
+        assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+    }
+
+    #[test]
+    fn test_iter_with_hint() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = py.eval("[1, 2, 3]", None, None).unwrap();
+        let (iter, hint) = list.iter_with_hint(py).unwrap();
+        assert_eq!(hint, 3);
+        assert_eq!(iter.count(), 3);
+        let gen = py.eval("(x for x in [1, 2, 3])", None, None).unwrap();
+        let (iter, hint) = gen.iter_with_hint(py).unwrap();
+        assert_eq!(hint, 0);
+        assert_eq!(iter.count(), 3);
+    }
+
+    #[test]
+    fn test_generic_getattr_setattr() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj = py
+            .eval(
+                "type('A', (object,), {'__getattribute__': lambda self, name: 42, \
+                 '__setattr__': lambda self, name, value: None})()",
+                None,
+                None,
+            )
+            .unwrap();
+        obj.setattr(py, "x", 1).unwrap();
+        assert_eq!(
+            obj.getattr(py, "x").unwrap().extract::<i32>(py).unwrap(),
+            42
+        );
+        assert!(obj.generic_getattr(py, "x").is_err());
+        obj.generic_setattr(py, "x", 1).unwrap();
+        assert_eq!(
+            obj.generic_getattr(py, "x")
+                .unwrap()
+                .extract::<i32>(py)
+                .unwrap(),
+            1
+        );
+        let err = obj.generic_getattr(py, "y").unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::AttributeError>()));
+    }
+
+    #[test]
+    fn test_instance_dict() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj = py.eval("type('A', (object,), {})()", None, None).unwrap();
+        obj.setattr(py, "x", 1).unwrap();
+        let dict = obj.instance_dict(py).unwrap().unwrap();
+        assert_eq!(
+            dict.get_item(py, "x").unwrap().extract::<i32>(py).unwrap(),
+            1
+        );
+        dict.set_item(py, "y", 2).unwrap();
+        assert_eq!(obj.getattr(py, "y").unwrap().extract::<i32>(py).unwrap(), 2);
+
+        let slotted = py
+            .eval("type('B', (object,), {'__slots__': ()})()", None, None)
+            .unwrap();
+        assert!(slotted.instance_dict(py).unwrap().is_none());
+        assert!(1i32
+            .to_py_object(py)
+            .into_object()
+            .instance_dict(py)
+            .unwrap()
+            .is_none());
+
+        let cls = py
+            .eval("type('C', (object,), {'z': 3})", None, None)
+            .unwrap();
+        let dict = cls.instance_dict(py).unwrap().unwrap();
+        assert_eq!(
+            dict.get_item(py, "z").unwrap().extract::<i32>(py).unwrap(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_annotations_missing() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj = 1i32.to_py_object(py).into_object();
+        assert!(obj.annotations(py, false).unwrap().is_none());
+        assert!(obj.annotations(py, true).unwrap().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "python3-sys")]
+    fn test_annotations() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let g = PyDict::new(py);
+        py.run(
+            "def f(x: 'int', y: float) -> str: pass\nclass C:\n    a: 'bytes'",
+            Some(&g),
+            None,
+        )
+        .unwrap();
+
+        let f = g.get_item(py, "f").unwrap();
+        let raw = f.annotations(py, false).unwrap().unwrap();
+        assert_eq!(
+            raw.get_item(py, "x")
+                .unwrap()
+                .extract::<String>(py)
+                .unwrap(),
+            "int"
+        );
+        let builtin = |name: &str| py.eval(name, None, None).unwrap();
+        assert!(raw.get_item(py, "y").unwrap() == builtin("float"));
+        let resolved = f.annotations(py, true).unwrap().unwrap();
+        assert!(resolved.get_item(py, "x").unwrap() == builtin("int"));
+        assert!(resolved.get_item(py, "return").unwrap() == builtin("str"));
+
+        let c = g.get_item(py, "C").unwrap();
+        let raw = c.annotations(py, false).unwrap().unwrap();
+        assert_eq!(
+            raw.get_item(py, "a")
+                .unwrap()
+                .extract::<String>(py)
+                .unwrap(),
+            "bytes"
+        );
+        let resolved = c.annotations(py, true).unwrap().unwrap();
+        assert!(resolved.get_item(py, "a").unwrap() == builtin("bytes"));
+    }
+
+    #[test]
+    fn test_call_method_kw() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj = py.eval("'{a}-{b}'", None, None).unwrap();
+        let result = obj
+            .call_method_kw(py, "format", NoArgs, vec![("a", 1), ("b", 2)])
+            .unwrap();
+        assert_eq!(result.extract::<String>(py).unwrap(), "1-2");
+
+        // `str.startswith` only accepts positional arguments;
+        // the TypeError from CPython must be propagated as-is.
+        let mut err = obj
+            .call_method_kw(py, "startswith", NoArgs, vec![("prefix", "x")])
+            .unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+        let msg = err
+            .instance(py)
+            .str(py)
+            .unwrap()
+            .to_string(py)
+            .unwrap()
+            .into_owned();
+        assert!(msg.contains("keyword"), "unexpected message: {}", msg);
+    }
+
+    #[test]
+    fn test_item_access() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = py.eval("[1, 2, 3]", None, None).unwrap();
+        assert_eq!(list.get_item(py, 1).unwrap().extract::<i32>(py).unwrap(), 2);
+        let slice = py.eval("slice(1, None)", None, None).unwrap();
+        assert_eq!(
+            list.get_item(py, slice)
+                .unwrap()
+                .extract::<Vec<i32>>(py)
+                .unwrap(),
+            vec![2, 3]
+        );
+        list.set_item(py, 0, 10).unwrap();
+        list.del_item(py, 2).unwrap();
+        assert_eq!(list.extract::<Vec<i32>>(py).unwrap(), vec![10, 2]);
+        let err = list.get_item(py, 5).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::IndexError>()));
+
+        let dict = py.eval("{}", None, None).unwrap();
+        dict.set_item(py, "a", 1).unwrap();
+        assert_eq!(
+            dict.get_item(py, "a").unwrap().extract::<i32>(py).unwrap(),
+            1
+        );
+        dict.del_item(py, "a").unwrap();
+        let err = dict.get_item(py, "a").unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::KeyError>()));
+
+        let err = py.None().get_item(py, 0).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+    }
+
+    #[test]
+    fn test_vectorcall() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let f = py
+            .eval("lambda *args, **kwargs: (args, kwargs)", None, None)
+            .unwrap();
+        let args = [
+            1i32.to_py_object(py).into_object(),
+            2i32.to_py_object(py).into_object(),
+            3i32.to_py_object(py).into_object(),
+        ];
+        let kwnames = PyTuple::new(py, &["c".to_py_object(py).into_object()]);
+        let result = f.vectorcall(py, &args, Some(&kwnames)).unwrap();
+        let expected = py.eval("((1, 2), {'c': 3})", None, None).unwrap();
+        assert_eq!(result.compare(py, &expected).unwrap(), Ordering::Equal);
+
+        let result = f.vectorcall(py, &args, None).unwrap();
+        let expected = py.eval("((1, 2, 3), {})", None, None).unwrap();
+        assert_eq!(result.compare(py, &expected).unwrap(), Ordering::Equal);
+
+        let len = py.eval("len", None, None).unwrap();
+        let result = len.vectorcall(py, &args[..0], None).unwrap_err();
+        assert!(result.matches(py, py.get_type::<exc::TypeError>()));
+
+        let kwnames = ("a", "b", "c", "d").to_py_object(py);
+        let err = f.vectorcall(py, &args, Some(&kwnames)).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::ValueError>()));
+
+        for kwnames in &[(1i32, "b").to_py_object(py), ("b", "b").to_py_object(py)] {
+            let err = f.vectorcall(py, &args, Some(kwnames)).unwrap_err();
+            assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+        }
+    }
+
+    #[test]
+    fn test_call_args() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let f = py.eval("lambda *args: args", None, None).unwrap();
+        let args = [
+            1i32.to_py_object(py).into_object(),
+            "a".to_py_object(py).into_object(),
+        ];
+        let result = f.call_args(py, &args).unwrap();
+        let expected = py.eval("(1, 'a')", None, None).unwrap();
+        assert_eq!(result.compare(py, &expected).unwrap(), Ordering::Equal);
+
+        let result = f.call_args(py, &[]).unwrap();
+        assert_eq!(result.cast_as::<PyTuple>(py).unwrap().len(py), 0);
+
+        let list = py.eval("list", None, None).unwrap();
+        let result = list.call_args(py, &[]).unwrap();
+        assert_eq!(result.cast_as::<PyList>(py).unwrap().len(py), 0);
+
+        let len = py.eval("len", None, None).unwrap();
+        let err = len.call_args(py, &args).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+    }
+
+    #[test]
+    fn test_call_method_args() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = py.eval("[3, 1, 2]", None, None).unwrap();
+        let args = [4i32.to_py_object(py).into_object()];
+        assert!(list
+            .call_method_args(py, "append", &args)
+            .unwrap()
+            .is_none(py));
+        assert!(list.call_method_args(py, "sort", &[]).unwrap().is_none(py));
+        let expected = py.eval("[1, 2, 3, 4]", None, None).unwrap();
+        assert_eq!(list.compare(py, &expected).unwrap(), Ordering::Equal);
+
+        let err = list.call_method_args(py, "missing", &[]).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::AttributeError>()));
+        let err = list.call_method_args(py, "append", &[]).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+    }
+
+    #[test]
+    fn test_assert_py_eq() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = py.eval("[1, 2, 3]", None, None).unwrap();
+        assert_py_eq!(py, list, vec![1, 2, 3]);
+        assert_py_eq!(py, list, list, "identical objects are equal");
+        assert_py_eq!(py, 1.0, 1);
+
+        let panic_message = |f: &dyn Fn()| {
+            let err = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_err();
+            crate::panic_message(&*err).unwrap().to_string()
+        };
+        assert_eq!(
+            panic_message(&|| assert_py_eq!(py, list, vec![1, 2])),
+            "assertion failed: `(left == right)`\n  left: `[1, 2, 3]`,\n right: `[1, 2]`"
+        );
+        assert_eq!(
+            panic_message(&|| assert_py_eq!(py, 1, 2, "value of {}", "x")),
+            "assertion failed: `(left == right)`\n  left: `1`,\n right: `2`: value of x"
+        );
+
+        let failing_eq = py
+            .eval(
+                "type('E', (object,), {'__eq__': lambda s, o: 1 // 0, '__repr__': lambda s: 'E()'})()",
+                None,
+                None,
+            )
+            .unwrap();
+        let message = panic_message(&|| assert_py_eq!(py, failing_eq, 1));
+        assert!(
+            message.starts_with(
+                "assertion failed: `(left == right)`: comparison raised ZeroDivisionError"
+            ),
+            "{}",
+            message
+        );
+        assert!(
+            message.ends_with("\n  left: `E()`,\n right: `1`"),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn test_collect_list() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj = py.eval("{'a': 1, 'b': 2}", None, None).unwrap();
+        let mut keys: Vec<String> = obj
+            .collect_list(py)
+            .unwrap()
+            .iter()
+            .map(|k| k.extract(py).unwrap())
+            .collect();
+        keys.sort();
+        assert_eq!(keys, vec!["a", "b"]);
+
+        let failing = py.eval("(1 // x for x in [1, 0, 1])", None, None).unwrap();
+        let err = failing.collect_list(py).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::ZeroDivisionError>()));
+
+        let err = py.None().collect_list(py).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+    }
+
+    #[test]
+    fn test_py_format() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let v = 255i32.to_py_object(py).into_object();
+        assert_eq!(
+            v.py_format(py, "#x").unwrap().to_string(py).unwrap(),
+            "0xff"
+        );
+        assert_eq!(
+            v.py_format(py, ">5").unwrap().to_string(py).unwrap(),
+            "  255"
+        );
+        assert_eq!(v.py_format(py, "").unwrap().to_string(py).unwrap(), "255");
+
+        let list = py.eval("[1, 'a']", None, None).unwrap();
+        assert_eq!(
+            list.py_format(py, "").unwrap().to_string(py).unwrap(),
+            list.str(py).unwrap().to_string(py).unwrap()
+        );
+
+        let err = v.py_format(py, "invalid").err().unwrap();
+        assert!(err.matches(py, py.get_type::<exc::ValueError>()));
+    }
 }
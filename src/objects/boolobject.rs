@@ -56,6 +56,7 @@ extract!(obj to bool;
     /// Converts a Python `bool` to a rust `bool`.
     ///
     /// Fails with `TypeError` if the input is not a Python `bool`.
+    /// In particular, integers such as `0` and `1` are not accepted.
     py => {
         Ok(obj.cast_as::<PyBool>(py)?.is_true())
     }
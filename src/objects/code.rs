@@ -0,0 +1,155 @@
+// Copyright (c) 2015 Daniel Grunwald
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this
+// software and associated documentation files (the "Software"), to deal in the Software
+// without restriction, including without limitation the rights to use, copy, modify, merge,
+// publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons
+// to whom the Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED,
+// INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR
+// PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE
+// FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
+// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+use std::ffi::CString;
+
+use crate::err::{self, PyResult};
+use crate::ffi;
+use crate::objects::{PyDict, PyObject};
+use crate::python::Python;
+
+/// Represents a Python code object, as returned by `Python::compile()`.
+///
+/// Compiling a snippet once and running the code object repeatedly
+/// avoids re-parsing the source on every execution.
+pub struct PyCode(PyObject);
+
+pyobject_newtype!(PyCode, PyCode_Check, PyCode_Type);
+
+/// The kind of source code passed to `Python::compile()`.
+///
+/// Corresponds to the `mode` argument of the Python `compile()` builtin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompileMode {
+    /// A single expression (`'eval'`); running the code returns its value.
+    Eval,
+    /// A sequence of statements (`'exec'`); running the code returns `None`.
+    Exec,
+    /// A single interactive statement (`'single'`); expression values are printed.
+    Single,
+}
+
+impl CompileMode {
+    #[inline]
+    fn start_token(self) -> libc::c_int {
+        match self {
+            CompileMode::Eval => ffi::Py_eval_input,
+            CompileMode::Exec => ffi::Py_file_input,
+            CompileMode::Single => ffi::Py_single_input,
+        }
+    }
+}
+
+impl PyCode {
+    /// Compiles Python source code into a code object.
+    /// This is equivalent to the Python expression `compile(code, filename, mode)`.
+    ///
+    /// `filename` is used in tracebacks and error messages.
+    /// Code that fails to compile results in a `SyntaxError`.
+    pub fn compile(py: Python, code: &str, filename: &str, mode: CompileMode) -> PyResult<PyCode> {
+        let code = CString::new(code).unwrap();
+        let filename = CString::new(filename).unwrap();
+        unsafe {
+            err::result_cast_from_owned_ptr(
+                py,
+                ffi::Py_CompileStringFlags(
+                    code.as_ptr(),
+                    filename.as_ptr(),
+                    mode.start_token(),
+                    std::ptr::null_mut(),
+                ),
+            )
+        }
+    }
+
+    /// Runs the code object in the given context and returns the result.
+    ///
+    /// For code compiled with `CompileMode::Eval`, this is the value of the expression;
+    /// otherwise it is `None`.
+    ///
+    /// `globals` and `locals` are handled as in `Python::eval()`:
+    /// if `globals` is `None`, it defaults to Python module `__main__`;
+    /// if `locals` is `None`, it defaults to the value of `globals`.
+    pub fn run(
+        &self,
+        py: Python,
+        globals: Option<&PyDict>,
+        locals: Option<&PyDict>,
+    ) -> PyResult<PyObject> {
+        let (globals, locals) = py.run_namespaces(globals, locals)?;
+        unsafe {
+            err::result_from_owned_ptr(
+                py,
+                ffi::PyEval_EvalCode(self.0.as_ptr() as *mut _, globals, locals),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CompileMode;
+    use crate::objectprotocol::ObjectProtocol;
+    use crate::objects::{exc, PyDict};
+    use crate::python::Python;
+
+    #[test]
+    fn test_compile_eval() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let code = py.compile("x * 2", "<test>", CompileMode::Eval).unwrap();
+        let d = PyDict::new(py);
+        for i in 0..3 {
+            d.set_item(py, "x", i).unwrap();
+            let v: i32 = code.run(py, None, Some(&d)).unwrap().extract(py).unwrap();
+            assert_eq!(v, i * 2);
+        }
+    }
+
+    #[test]
+    fn test_compile_exec() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let code = py
+            .compile("y = len(x)", "<test>", CompileMode::Exec)
+            .unwrap();
+        let g = PyDict::new(py);
+        g.set_item(py, "x", "abc").unwrap();
+        assert!(code.run(py, Some(&g), None).unwrap() == py.None());
+        let v: usize = g.get_item(py, "y").unwrap().extract(py).unwrap();
+        assert_eq!(v, 3);
+    }
+
+    #[test]
+    fn test_compile_error() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let mut err = py
+            .compile("x +", "config.py", CompileMode::Eval)
+            .err()
+            .unwrap();
+        assert!(err.matches(py, py.get_type::<exc::SyntaxError>()));
+        let filename: String = err
+            .instance(py)
+            .getattr(py, "filename")
+            .unwrap()
+            .extract(py)
+            .unwrap();
+        assert_eq!(filename, "config.py");
+    }
+}
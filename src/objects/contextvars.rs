@@ -0,0 +1,210 @@
+//! Wrappers for the `contextvars` module (Python 3.7 and later).
+
+use std::ffi::CString;
+use std::ptr;
+
+use super::exc;
+use super::object::PyObject;
+use crate::conversion::ToPyObject;
+use crate::err::{self, PyErr, PyResult};
+use crate::ffi;
+use crate::python::{Python, PythonObject, ToPythonPointer};
+
+/// Represents a Python `contextvars.Context`: a mapping from context variables to their values.
+pub struct PyContext(PyObject);
+
+pyobject_newtype!(PyContext, PyContext_CheckExact, PyContext_Type);
+
+/// Represents a Python `contextvars.ContextVar`.
+///
+/// # Example
+/// ```
+/// use cpython::{Python, PyContext, PyContextVar};
+///
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let var = PyContextVar::new(py, "request_id", None).unwrap();
+/// let ctx = PyContext::copy_current(py).unwrap();
+/// ctx.run(py, |py| {
+///     var.set(py, 42)?;
+///     assert_eq!(var.get(py)?.unwrap().extract::<i32>(py)?, 42);
+///     Ok(())
+/// }).unwrap();
+/// // The value was only set within `ctx`.
+/// assert!(var.get(py).unwrap().is_none());
+/// ```
+pub struct PyContextVar(PyObject);
+
+pyobject_newtype!(PyContextVar, PyContextVar_CheckExact, PyContextVar_Type);
+
+/// Represents a Python `contextvars.Token`, as returned by `PyContextVar::set()`.
+pub struct PyContextToken(PyObject);
+
+pyobject_newtype!(
+    PyContextToken,
+    PyContextToken_CheckExact,
+    PyContextToken_Type
+);
+
+impl PyContext {
+    /// Creates a new, empty context.
+    pub fn new(py: Python) -> PyResult<PyContext> {
+        unsafe { err::result_cast_from_owned_ptr(py, ffi::PyContext_New()) }
+    }
+
+    /// Returns a copy of the current thread's context.
+    /// This is equivalent to the Python expression `contextvars.copy_context()`.
+    pub fn copy_current(py: Python) -> PyResult<PyContext> {
+        unsafe { err::result_cast_from_owned_ptr(py, ffi::PyContext_CopyCurrent()) }
+    }
+
+    /// Returns a shallow copy of this context.
+    pub fn copy(&self, py: Python) -> PyResult<PyContext> {
+        unsafe { err::result_cast_from_owned_ptr(py, ffi::PyContext_Copy(self.0.as_ptr())) }
+    }
+
+    /// Runs `f` with this context as the current context, like `Context.run()` in Python.
+    ///
+    /// Changes made to context variables within `f` are stored in this context.
+    /// The previous context is restored afterwards, even if `f` panics.
+    /// Fails with `RuntimeError` if this context is already entered.
+    pub fn run<F, R>(&self, py: Python, f: F) -> PyResult<R>
+    where
+        F: FnOnce(Python) -> PyResult<R>,
+    {
+        struct ExitGuard<'a>(&'a PyContext);
+        impl<'a> Drop for ExitGuard<'a> {
+            fn drop(&mut self) {
+                // Only fails if the context is not the current one,
+                // which `run()` guarantees; but don't leave a stray exception set.
+                let py = unsafe { Python::assume_gil_acquired() };
+                if unsafe { ffi::PyContext_Exit((self.0).0.as_ptr()) } == -1 {
+                    PyErr::fetch(py).print(py);
+                }
+            }
+        }
+
+        err::error_on_minusone(py, unsafe { ffi::PyContext_Enter(self.0.as_ptr()) })?;
+        let _guard = ExitGuard(self);
+        f(py)
+    }
+}
+
+impl PyContextVar {
+    /// Creates a new context variable.
+    /// This is equivalent to the Python expression `contextvars.ContextVar(name, default=default)`.
+    ///
+    /// Fails with `ValueError` if `name` contains a NUL byte.
+    pub fn new(py: Python, name: &str, default: Option<&PyObject>) -> PyResult<PyContextVar> {
+        let name = CString::new(name)
+            .map_err(|_| PyErr::new::<exc::ValueError, _>(py, "embedded null character"))?;
+        unsafe {
+            err::result_cast_from_owned_ptr(
+                py,
+                ffi::PyContextVar_New(name.as_ptr(), default.as_ptr()),
+            )
+        }
+    }
+
+    /// Gets the variable's value in the current context.
+    ///
+    /// Returns the variable's default value if it has no value in the current context,
+    /// and `None` if there is no default either.
+    pub fn get(&self, py: Python) -> PyResult<Option<PyObject>> {
+        let mut value = ptr::null_mut();
+        unsafe {
+            err::error_on_minusone(
+                py,
+                ffi::PyContextVar_Get(self.0.as_ptr(), ptr::null_mut(), &mut value),
+            )?;
+            Ok(PyObject::from_owned_ptr_opt(py, value))
+        }
+    }
+
+    /// Sets the variable's value in the current context.
+    ///
+    /// Returns a token that can be passed to `reset()` to restore the previous value.
+    pub fn set<V>(&self, py: Python, value: V) -> PyResult<PyContextToken>
+    where
+        V: ToPyObject,
+    {
+        value.with_borrowed_ptr(py, |value| unsafe {
+            err::result_cast_from_owned_ptr(py, ffi::PyContextVar_Set(self.0.as_ptr(), value))
+        })
+    }
+
+    /// Restores the value the variable had before the `set()` call that returned `token`.
+    ///
+    /// Fails with `ValueError` if the token was created by another variable or in another
+    /// context, and with `RuntimeError` if it was already used.
+    pub fn reset(&self, py: Python, token: &PyContextToken) -> PyResult<()> {
+        unsafe {
+            err::error_on_minusone(
+                py,
+                ffi::PyContextVar_Reset(self.0.as_ptr(), token.0.as_ptr()),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PyContext, PyContextVar};
+    use crate::objects::exc;
+    use crate::python::{Python, PythonObject};
+    use crate::ToPyObject;
+
+    #[test]
+    fn test_get_set_reset() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let default = "default".to_py_object(py).into_object();
+        let var = PyContextVar::new(py, "var", Some(&default)).unwrap();
+        let ctx = PyContext::new(py).unwrap();
+        ctx.run(py, |py| {
+            assert_eq!(var.get(py)?.unwrap().extract::<String>(py)?, "default");
+            let token = var.set(py, 1)?;
+            let token2 = var.set(py, 2)?;
+            assert_eq!(var.get(py)?.unwrap().extract::<i32>(py)?, 2);
+            var.reset(py, &token2)?;
+            assert_eq!(var.get(py)?.unwrap().extract::<i32>(py)?, 1);
+            var.reset(py, &token)?;
+            assert_eq!(var.get(py)?.unwrap().extract::<String>(py)?, "default");
+            let err = var.reset(py, &token).unwrap_err();
+            assert!(err.matches(py, py.get_type::<exc::RuntimeError>()));
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_run_isolation() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let var = PyContextVar::new(py, "var", None).unwrap();
+        let ctx = PyContext::copy_current(py).unwrap();
+        ctx.run(py, |py| var.set(py, "inner").map(drop)).unwrap();
+        assert!(var.get(py).unwrap().is_none());
+
+        // Values set in a context are visible when running it again, and in copies.
+        let copy = ctx.copy(py).unwrap();
+        copy.run(py, |py| {
+            assert_eq!(var.get(py)?.unwrap().extract::<String>(py)?, "inner");
+            // A context can't be entered twice.
+            let err = copy.run(py, |_| Ok(())).unwrap_err();
+            assert!(err.matches(py, py.get_type::<exc::RuntimeError>()));
+            Ok(())
+        })
+        .unwrap();
+
+        // Python code sees the context, too.
+        let result = ctx
+            .run(py, |py| {
+                let locals = crate::PyDict::new(py);
+                locals.set_item(py, "var", &var)?;
+                py.eval("var.get()", None, Some(&locals))
+            })
+            .unwrap();
+        assert_eq!(result.extract::<String>(py).unwrap(), "inner");
+    }
+}
@@ -21,10 +21,16 @@ use std::{cmp, collections, hash, ptr};
 use crate::conversion::ToPyObject;
 use crate::err::{self, PyErr, PyResult};
 use crate::ffi;
-use crate::objects::{PyList, PyObject};
+use crate::objectprotocol::ObjectProtocol;
+use crate::objects::{NoArgs, PyList, PyObject};
 use crate::python::{Python, PythonObject};
 
 /// Represents a Python `dict`.
+///
+/// Since Python 3.7, dictionaries preserve insertion order, and so do `items()`,
+/// `items_list()` and the `HashMap`/`BTreeMap` conversions (which insert in the
+/// map's iteration order).
+/// On older versions, use `new_ordered()` and `ordered_items()` when the order matters.
 pub struct PyDict(PyObject);
 
 pyobject_newtype!(PyDict, PyDict_Check, PyDict_Type);
@@ -37,6 +43,41 @@ impl PyDict {
         unsafe { err::cast_from_owned_ptr_or_panic(py, ffi::PyDict_New()) }
     }
 
+    /// Creates a new empty dictionary that preserves insertion order.
+    ///
+    /// On Python 3.7 and later, this is a plain `dict`.
+    /// On older versions, it is a `collections.OrderedDict`; the methods of `PyDict`
+    /// that modify the dictionary then call the methods of `OrderedDict`, which maintain the order.
+    /// Use `ordered_items()` to retrieve the items in order.
+    pub fn new_ordered(py: Python) -> PyResult<PyDict> {
+        #[cfg(Py_3_7)]
+        {
+            Ok(PyDict::new(py))
+        }
+        #[cfg(not(Py_3_7))]
+        {
+            py.import("collections")?
+                .call(py, "OrderedDict", NoArgs, None)?
+                .cast_into::<PyDict>(py)
+                .map_err(PyErr::from)
+        }
+    }
+
+    /// Creates a new dictionary from an iterator of key-value pairs.
+    ///
+    /// If a key occurs more than once, the value of its last occurrence is kept.
+    /// See also `extend()`.
+    pub fn from_iter<I, K, V>(py: Python, iter: I) -> PyResult<PyDict>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: ToPyObject,
+        V: ToPyObject,
+    {
+        let dict = PyDict::new(py);
+        dict.extend(py, iter)?;
+        Ok(dict)
+    }
+
     /// Return a new dictionary that contains the same key-value pairs as self.
     /// Corresponds to `dict(self)` in Python.
     pub fn copy(&self, py: Python) -> PyResult<PyDict> {
@@ -46,6 +87,14 @@ impl PyDict {
     /// Empty an existing dictionary of all key-value pairs.
     #[inline]
     pub fn clear(&self, _py: Python) {
+        #[cfg(not(Py_3_7))]
+        {
+            if unsafe { ffi::PyDict_CheckExact(self.0.as_ptr()) } == 0 {
+                // `OrderedDict.clear()` cannot fail.
+                self.0.call_method(_py, "clear", NoArgs, None).unwrap();
+                return;
+            }
+        }
         unsafe { ffi::PyDict_Clear(self.0.as_ptr()) }
     }
 
@@ -73,6 +122,7 @@ impl PyDict {
 
     /// Gets an item from the dictionary.
     /// Returns None if the item is not present, or if an error occurs.
+    /// Use `get_item_checked()` to distinguish between the two cases.
     pub fn get_item<K>(&self, py: Python, key: K) -> Option<PyObject>
     where
         K: ToPyObject,
@@ -82,6 +132,44 @@ impl PyDict {
         })
     }
 
+    /// Gets an item from the dictionary.
+    ///
+    /// Returns `Ok(None)` if the item is not present, and `Err` if an error occurs
+    /// during the lookup (e.g. if the key is unhashable, or its `__hash__` or
+    /// `__eq__` method raises an exception).
+    /// Like `get_item()`, this does not call `__missing__` on dict subclasses.
+    pub fn get_item_checked<K>(&self, py: Python, key: K) -> PyResult<Option<PyObject>>
+    where
+        K: ToPyObject,
+    {
+        key.with_borrowed_ptr(py, |key| unsafe {
+            #[cfg(feature = "python3-sys")]
+            {
+                match PyObject::from_borrowed_ptr_opt(
+                    py,
+                    ffi::PyDict_GetItemWithError(self.0.as_ptr(), key),
+                ) {
+                    Some(value) => Ok(Some(value)),
+                    None if PyErr::occurred(py) => Err(PyErr::fetch(py)),
+                    None => Ok(None),
+                }
+            }
+            #[cfg(feature = "python27-sys")]
+            {
+                // Python 2.7 lacks PyDict_GetItemWithError; PyDict_Contains reports
+                // lookup errors, after which PyDict_GetItem can be used safely.
+                match ffi::PyDict_Contains(self.0.as_ptr(), key) {
+                    1 => Ok(PyObject::from_borrowed_ptr_opt(
+                        py,
+                        ffi::PyDict_GetItem(self.0.as_ptr(), key),
+                    )),
+                    0 => Ok(None),
+                    _ => Err(PyErr::fetch(py)),
+                }
+            }
+        })
+    }
+
     /// Sets an item value.
     /// This is equivalent to the Python expression `self[key] = value`.
     pub fn set_item<K, V>(&self, py: Python, key: K, value: V) -> PyResult<()>
@@ -91,11 +179,34 @@ impl PyDict {
     {
         key.with_borrowed_ptr(py, move |key| {
             value.with_borrowed_ptr(py, |value| unsafe {
-                err::error_on_minusone(py, ffi::PyDict_SetItem(self.0.as_ptr(), key, value))
+                err::error_on_minusone(py, dict_set_item(self.0.as_ptr(), key, value))
             })
         })
     }
 
+    /// Inserts all key-value pairs from an iterator.
+    /// This is equivalent to the Python expression `self.update(iter)`.
+    ///
+    /// Pairs are inserted in iteration order, so later duplicate keys overwrite earlier ones
+    /// (including keys that were already present in the dictionary).
+    /// On error, the pairs inserted before the failing one remain in the dictionary.
+    pub fn extend<I, K, V>(&self, py: Python, iter: I) -> PyResult<()>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: ToPyObject,
+        V: ToPyObject,
+    {
+        let dict = self.0.as_ptr();
+        for (key, value) in iter {
+            key.with_borrowed_ptr(py, |key| {
+                value.with_borrowed_ptr(py, |value| unsafe {
+                    err::error_on_minusone(py, dict_set_item(dict, key, value))
+                })
+            })?;
+        }
+        Ok(())
+    }
+
     /// Deletes an item.
     /// This is equivalent to the Python expression `del self[key]`.
     pub fn del_item<K>(&self, py: Python, key: K) -> PyResult<()>
@@ -103,7 +214,7 @@ impl PyDict {
         K: ToPyObject,
     {
         key.with_borrowed_ptr(py, |key| unsafe {
-            err::error_on_minusone(py, ffi::PyDict_DelItem(self.0.as_ptr(), key))
+            err::error_on_minusone(py, dict_del_item(self.0.as_ptr(), key))
         })
     }
 
@@ -114,6 +225,10 @@ impl PyDict {
     }
 
     /// Returns the list of (key,value) pairs in this dictionary.
+    ///
+    /// For a plain `dict`, the pairs are in insertion order on Python 3.7 and later.
+    /// For subclasses that keep their own ordering (such as an `OrderedDict`
+    /// after `move_to_end()`), use `ordered_items()`.
     pub fn items(&self, py: Python) -> Vec<(PyObject, PyObject)> {
         // Note that we don't provide an iterator because
         // PyDict_Next() is unsafe to use when the dictionary might be changed
@@ -132,6 +247,56 @@ impl PyDict {
         }
         vec
     }
+
+    /// Returns the list of (key,value) pairs in this dictionary, in iteration order.
+    ///
+    /// This is the order in which Python code iterating over `self.items()` sees the pairs:
+    /// insertion order for a plain `dict` on Python 3.7 and later,
+    /// and the order maintained by the mapping for an `OrderedDict`.
+    /// Like `items()`, this returns a snapshot rather than an iterator,
+    /// so that modifying the dictionary afterwards is safe.
+    pub fn ordered_items(&self, py: Python) -> PyResult<Vec<(PyObject, PyObject)>> {
+        #[cfg(Py_3_7)]
+        {
+            if unsafe { ffi::PyDict_CheckExact(self.0.as_ptr()) } != 0 {
+                return Ok(self.items(py));
+            }
+        }
+        let items = self.0.call_method(py, "items", NoArgs, None)?;
+        let mut vec = Vec::with_capacity(self.len(py));
+        for item in items.iter(py)? {
+            vec.push(item?.extract::<(PyObject, PyObject)>(py)?);
+        }
+        Ok(vec)
+    }
+}
+
+// Before Python 3.7, `new_ordered()` creates an `OrderedDict`, which only maintains
+// its order when modified through its own methods rather than the `PyDict_*` functions.
+#[inline]
+unsafe fn dict_set_item(
+    dict: *mut ffi::PyObject,
+    key: *mut ffi::PyObject,
+    value: *mut ffi::PyObject,
+) -> libc::c_int {
+    #[cfg(not(Py_3_7))]
+    {
+        if ffi::PyDict_CheckExact(dict) == 0 {
+            return ffi::PyObject_SetItem(dict, key, value);
+        }
+    }
+    ffi::PyDict_SetItem(dict, key, value)
+}
+
+#[inline]
+unsafe fn dict_del_item(dict: *mut ffi::PyObject, key: *mut ffi::PyObject) -> libc::c_int {
+    #[cfg(not(Py_3_7))]
+    {
+        if ffi::PyDict_CheckExact(dict) == 0 {
+            return ffi::PyObject_DelItem(dict, key);
+        }
+    }
+    ffi::PyDict_DelItem(dict, key)
 }
 
 /// Converts a Rust `HashMap` to a Python `dict`.
@@ -172,7 +337,8 @@ where
 #[cfg(test)]
 mod test {
     use crate::conversion::ToPyObject;
-    use crate::objects::{PyDict, PyTuple};
+    use crate::objectprotocol::ObjectProtocol;
+    use crate::objects::{exc, PyDict, PyList, PyObject, PyTuple};
     use crate::python::{Python, PythonObject};
     use std::collections::HashMap;
 
@@ -213,6 +379,29 @@ mod test {
         assert_eq!(None, dict.get_item(py, 8i32));
     }
 
+    #[test]
+    fn test_get_item_checked() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let mut v = HashMap::new();
+        v.insert(7, 32);
+        let dict = v.to_py_object(py);
+        assert_eq!(
+            32,
+            dict.get_item_checked(py, 7i32)
+                .unwrap()
+                .unwrap()
+                .extract::<i32>(py)
+                .unwrap()
+        );
+        assert!(dict.get_item_checked(py, 8i32).unwrap().is_none());
+
+        let unhashable = PyList::new(py, &[]);
+        assert!(dict.get_item(py, &unhashable).is_none());
+        let err = dict.get_item_checked(py, &unhashable).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+    }
+
     #[test]
     fn test_set_item() {
         let gil = Python::acquire_gil();
@@ -282,8 +471,8 @@ mod test {
         let mut value_sum = 0;
         for el in dict.items_list(py).iter(py) {
             let tuple = el.cast_into::<PyTuple>(py).unwrap();
-            key_sum += tuple.get_item(py, 0).extract::<i32>(py).unwrap();
-            value_sum += tuple.get_item(py, 1).extract::<i32>(py).unwrap();
+            key_sum += tuple.get_item(py, 0).unwrap().extract::<i32>(py).unwrap();
+            value_sum += tuple.get_item(py, 1).unwrap().extract::<i32>(py).unwrap();
         }
         assert_eq!(7 + 8 + 9, key_sum);
         assert_eq!(32 + 42 + 123, value_sum);
@@ -308,4 +497,114 @@ mod test {
         assert_eq!(7 + 8 + 9, key_sum);
         assert_eq!(32 + 42 + 123, value_sum);
     }
+
+    fn keys_of(py: Python, items: Vec<(PyObject, PyObject)>) -> Vec<String> {
+        items
+            .into_iter()
+            .map(|(key, _)| key.extract::<String>(py).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_new_ordered() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let dict = PyDict::new_ordered(py).unwrap();
+        for (i, key) in ["zeta", "alpha", "mu", "beta", "omega"].iter().enumerate() {
+            dict.set_item(py, key, i).unwrap();
+        }
+        dict.del_item(py, "mu").unwrap();
+        dict.set_item(py, "mu", 5).unwrap();
+        dict.set_item(py, "zeta", 6).unwrap(); // updating keeps the position
+        let items = dict.ordered_items(py).unwrap();
+        assert_eq!(items[0].1.extract::<i32>(py).unwrap(), 6);
+        assert_eq!(
+            keys_of(py, items),
+            vec!["zeta", "alpha", "beta", "omega", "mu"]
+        );
+        dict.clear(py);
+        dict.set_item(py, "nu", 7).unwrap();
+        assert_eq!(keys_of(py, dict.ordered_items(py).unwrap()), vec!["nu"]);
+    }
+
+    #[test]
+    fn test_ordered_items_ordered_dict() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let dict = py
+            .eval(
+                "__import__('collections').OrderedDict([('a', 1), ('b', 2), ('c', 3)])",
+                None,
+                None,
+            )
+            .unwrap()
+            .cast_into::<PyDict>(py)
+            .unwrap();
+        dict.as_object()
+            .call_method(py, "move_to_end", ("a",), None)
+            .unwrap();
+        assert_eq!(
+            keys_of(py, dict.ordered_items(py).unwrap()),
+            vec!["b", "c", "a"]
+        );
+    }
+
+    #[cfg(Py_3_7)]
+    #[test]
+    fn test_items_insertion_order() {
+        use std::collections::BTreeMap;
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let mut map = BTreeMap::new();
+        for key in &["d", "b", "a", "c"] {
+            map.insert(key.to_string(), 0);
+        }
+        let dict = map.to_py_object(py);
+        dict.set_item(py, "0", 0).unwrap();
+        let expected = vec!["a", "b", "c", "d", "0"];
+        assert_eq!(keys_of(py, dict.items(py)), expected);
+        assert_eq!(keys_of(py, dict.ordered_items(py).unwrap()), expected);
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let dict = PyDict::from_iter(py, vec![("a", 1), ("b", 2), ("a", 3)]).unwrap();
+        assert_eq!(2, dict.len(py));
+        assert_eq!(
+            3,
+            dict.get_item(py, "a").unwrap().extract::<i32>(py).unwrap()
+        );
+        assert_eq!(
+            2,
+            dict.get_item(py, "b").unwrap().extract::<i32>(py).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_extend() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let dict = PyDict::new(py);
+        dict.set_item(py, 7, 32).unwrap();
+        dict.extend(py, (0..3).map(|i| (i, i * 10))).unwrap();
+        dict.extend(py, vec![(7, 70)]).unwrap();
+        assert_eq!(4, dict.len(py));
+        assert_eq!(
+            20,
+            dict.get_item(py, 2).unwrap().extract::<i32>(py).unwrap()
+        );
+        assert_eq!(
+            70,
+            dict.get_item(py, 7).unwrap().extract::<i32>(py).unwrap()
+        );
+
+        let unhashable = PyList::new(py, &[]).into_object();
+        let err = dict
+            .extend(py, vec![(py.None(), 1), (unhashable, 2)])
+            .unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+        assert!(dict.contains(py, py.None()).unwrap());
+    }
 }
@@ -149,6 +149,19 @@ exc_type!(UnicodeDecodeError, PyExc_UnicodeDecodeError);
 exc_type!(UnicodeEncodeError, PyExc_UnicodeEncodeError);
 exc_type!(UnicodeTranslateError, PyExc_UnicodeTranslateError);
 
+exc_type!(Warning, PyExc_Warning);
+exc_type!(UserWarning, PyExc_UserWarning);
+exc_type!(DeprecationWarning, PyExc_DeprecationWarning);
+exc_type!(PendingDeprecationWarning, PyExc_PendingDeprecationWarning);
+exc_type!(SyntaxWarning, PyExc_SyntaxWarning);
+exc_type!(RuntimeWarning, PyExc_RuntimeWarning);
+exc_type!(FutureWarning, PyExc_FutureWarning);
+exc_type!(ImportWarning, PyExc_ImportWarning);
+exc_type!(UnicodeWarning, PyExc_UnicodeWarning);
+exc_type!(BytesWarning, PyExc_BytesWarning);
+#[cfg(feature = "python3-sys")]
+exc_type!(ResourceWarning, PyExc_ResourceWarning);
+
 impl UnicodeDecodeError {
     pub fn new(
         py: Python,
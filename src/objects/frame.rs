@@ -0,0 +1,91 @@
+// Copyright (c) 2015 Daniel Grunwald
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this
+// software and associated documentation files (the "Software"), to deal in the Software
+// without restriction, including without limitation the rights to use, copy, modify, merge,
+// publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons
+// to whom the Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED,
+// INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR
+// PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE
+// FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
+// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+use crate::err::PyResult;
+use crate::ffi;
+use crate::objectprotocol::ObjectProtocol;
+use crate::objects::{PyCode, PyObject};
+use crate::python::{Python, PythonObject};
+
+/// Represents a Python frame object: a function call (or module or class body)
+/// that is currently executing.
+///
+/// Use `Python::current_frame()` to get the innermost frame of the current thread.
+/// This is the same object as returned by `sys._getframe()` and `inspect.currentframe()`.
+pub struct PyFrame(PyObject);
+
+pyobject_newtype!(PyFrame, PyFrame_Check, PyFrame_Type);
+
+impl PyFrame {
+    /// Gets the code object being executed in this frame.
+    /// This is equivalent to the Python expression `self.f_code`.
+    pub fn code(&self, py: Python) -> PyResult<PyCode> {
+        Ok(self.0.getattr(py, "f_code")?.cast_into(py)?)
+    }
+
+    /// Gets the name of the source file of the code being executed.
+    /// This is equivalent to the Python expression `self.f_code.co_filename`.
+    pub fn filename(&self, py: Python) -> PyResult<String> {
+        self.code(py)?
+            .as_object()
+            .getattr(py, "co_filename")?
+            .extract(py)
+    }
+
+    /// Gets the name of the function (or `<module>` etc.) being executed.
+    /// This is equivalent to the Python expression `self.f_code.co_name`.
+    pub fn function_name(&self, py: Python) -> PyResult<String> {
+        self.code(py)?
+            .as_object()
+            .getattr(py, "co_name")?
+            .extract(py)
+    }
+
+    /// Gets the line number currently being executed.
+    /// This is equivalent to the Python expression `self.f_lineno`.
+    pub fn lineno(&self, _py: Python) -> u32 {
+        unsafe { ffi::PyFrame_GetLineNumber(self.0.as_ptr() as *mut ffi::PyFrameObject) as u32 }
+    }
+
+    /// Gets the local variables of the frame.
+    /// This is equivalent to the Python expression `self.f_locals`.
+    ///
+    /// For function frames, this is a snapshot of the local variables,
+    /// updated each time the locals are requested; assigning to it does not
+    /// change the variables of the running function.
+    pub fn locals(&self, py: Python) -> PyResult<PyObject> {
+        self.0.getattr(py, "f_locals")
+    }
+
+    /// Gets the global namespace of the frame.
+    /// This is equivalent to the Python expression `self.f_globals`.
+    pub fn globals(&self, py: Python) -> PyResult<PyObject> {
+        self.0.getattr(py, "f_globals")
+    }
+
+    /// Gets the calling frame, or `None` if this is the outermost frame.
+    /// This is equivalent to the Python expression `self.f_back`.
+    pub fn back(&self, py: Python) -> PyResult<Option<PyFrame>> {
+        let back = self.0.getattr(py, "f_back")?;
+        if back.is_none(py) {
+            Ok(None)
+        } else {
+            Ok(Some(back.cast_into(py)?))
+        }
+    }
+}
@@ -0,0 +1,232 @@
+// Copyright (c) 2017 Daniel Grunwald
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this
+// software and associated documentation files (the "Software"), to deal in the Software
+// without restriction, including without limitation the rights to use, copy, modify, merge,
+// publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons
+// to whom the Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED,
+// INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR
+// PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE
+// FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
+// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+use std::hash::{Hash, Hasher};
+
+use super::object::PyObject;
+use crate::err::{PyErr, PyResult};
+use crate::ffi;
+use crate::objectprotocol::ObjectProtocol;
+use crate::python::{PyClone, Python, ToPythonPointer};
+
+/// A Python object that can be used as key in Rust collections such as `HashMap`,
+/// using Python's notion of hashing and equality.
+///
+/// Unlike `PyObject`, whose `PartialEq` implementation compares object identity,
+/// `HashableObject` compares objects using Python's `==` operator, and hashes them
+/// using Python's `hash()`. `HashableObject` can thus be used to build Rust-side
+/// caches and lookup tables that behave like a Python `dict`.
+/// To key a collection by object identity instead, use [IdKey](struct.IdKey.html).
+///
+/// The hash value is computed once, by `HashableObject::new()`, which fails if the
+/// object is not hashable. Keys must not be mutated in a way that changes their hash
+/// while they are stored in a collection.
+///
+/// # Panics
+///
+/// `Hash` and `PartialEq` cannot report errors. Comparing two `HashableObject`s acquires
+/// the GIL (if it is not already held) and calls Python's `__eq__`; if that raises an
+/// exception, the comparison panics. Use `equals()` to handle such errors.
+///
+/// # Example
+/// ```
+/// use std::collections::HashMap;
+/// use cpython::{HashableObject, Python, PythonObject, ToPyObject};
+///
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let mut cache = HashMap::new();
+/// let key = HashableObject::new(py, "key".to_py_object(py).into_object()).unwrap();
+/// cache.insert(key, 1);
+///
+/// // A different but equal Python object finds the same entry.
+/// let lookup = py.eval("'k' + 'ey'", None, None).unwrap();
+/// let lookup = HashableObject::new(py, lookup).unwrap();
+/// assert_eq!(cache.get(&lookup), Some(&1));
+/// ```
+pub struct HashableObject {
+    obj: PyObject,
+    hash: crate::Py_hash_t,
+}
+
+impl HashableObject {
+    /// Wraps `obj`, computing its Python hash value.
+    ///
+    /// Fails with `TypeError` if the object is unhashable,
+    /// or with any exception raised by its `__hash__` method.
+    pub fn new(py: Python, obj: PyObject) -> PyResult<HashableObject> {
+        let hash = obj.hash(py)?;
+        Ok(HashableObject { obj, hash })
+    }
+
+    /// Gets the wrapped object.
+    #[inline]
+    pub fn as_object(&self) -> &PyObject {
+        &self.obj
+    }
+
+    /// Unwraps the object.
+    #[inline]
+    pub fn into_object(self) -> PyObject {
+        self.obj
+    }
+
+    /// Gets the Python hash value of the object.
+    #[inline]
+    pub fn hash_value(&self) -> crate::Py_hash_t {
+        self.hash
+    }
+
+    /// Compares the objects using Python's `==` operator.
+    pub fn equals(&self, py: Python, other: &HashableObject) -> PyResult<bool> {
+        match unsafe {
+            ffi::PyObject_RichCompareBool(self.obj.as_ptr(), other.obj.as_ptr(), ffi::Py_EQ)
+        } {
+            -1 => Err(PyErr::fetch(py)),
+            r => Ok(r != 0),
+        }
+    }
+}
+
+impl PyClone for HashableObject {
+    #[inline]
+    fn clone_ref(&self, py: Python) -> HashableObject {
+        HashableObject {
+            obj: self.obj.clone_ref(py),
+            hash: self.hash,
+        }
+    }
+}
+
+impl Hash for HashableObject {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state)
+    }
+}
+
+impl PartialEq for HashableObject {
+    fn eq(&self, other: &HashableObject) -> bool {
+        if self.obj.as_ptr() == other.obj.as_ptr() {
+            return true;
+        }
+        if self.hash != other.hash {
+            return false;
+        }
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        self.equals(py, other)
+            .unwrap_or_else(|e| panic!("exception while comparing Python objects: {:?}", e))
+    }
+}
+
+impl Eq for HashableObject {}
+
+/// A Python object that can be used as key in Rust collections such as `HashMap`,
+/// using object identity for hashing and equality.
+///
+/// `IdKey` hashes the object's identity (see `PyObject::id()`) and compares objects
+/// like the Python `is` operator. It never calls the Python `__hash__` or `__eq__`,
+/// and thus does not need the GIL and works for unhashable objects.
+/// Each key holds a reference to the object, which keeps it alive so that its address
+/// cannot be reused by another object.
+///
+/// # Example
+/// ```
+/// use std::collections::HashMap;
+/// use cpython::{IdKey, PyClone, Python};
+///
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let mut map = HashMap::new();
+/// let a = py.eval("[1]", None, None).unwrap();
+/// let b = py.eval("[1]", None, None).unwrap();
+/// map.insert(IdKey(a.clone_ref(py)), "a");
+/// map.insert(IdKey(b.clone_ref(py)), "b");
+/// // equal lists, but distinct objects
+/// assert_eq!(map.len(), 2);
+/// assert_eq!(map[&IdKey(a)], "a");
+/// ```
+#[derive(PartialEq, Eq)]
+pub struct IdKey(pub PyObject);
+
+impl PyClone for IdKey {
+    #[inline]
+    fn clone_ref(&self, py: Python) -> IdKey {
+        IdKey(self.0.clone_ref(py))
+    }
+}
+
+impl Hash for IdKey {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.id().hash(state)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{HashableObject, IdKey};
+    use crate::conversion::ToPyObject;
+    use crate::objects::{exc, PyList};
+    use crate::python::{PyClone, Python, PythonObject};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_hashmap_key() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let mut map = HashMap::new();
+        for i in 0..10i32 {
+            let key = HashableObject::new(py, i.to_py_object(py).into_object()).unwrap();
+            map.insert(key, i);
+        }
+        // 3.0 == 3 and hash(3.0) == hash(3) in Python
+        let key = HashableObject::new(py, 3.0f64.to_py_object(py).into_object()).unwrap();
+        assert_eq!(map.get(&key), Some(&3));
+        let key = HashableObject::new(py, "3".to_py_object(py).into_object()).unwrap();
+        assert_eq!(map.get(&key), None);
+    }
+
+    #[test]
+    fn test_unhashable() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = PyList::new(py, &[]).into_object();
+        let err = HashableObject::new(py, list).err().unwrap();
+        assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+    }
+
+    #[test]
+    fn test_id_key() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let a = PyList::new(py, &[]).into_object();
+        let b = PyList::new(py, &[]).into_object();
+        assert_eq!(a.id(), a.clone_ref(py).id());
+        assert_ne!(a.id(), b.id());
+
+        // unhashable, equal lists, but distinct objects
+        let mut map = HashMap::new();
+        map.insert(IdKey(a.clone_ref(py)), "a");
+        map.insert(IdKey(b.clone_ref(py)), "b");
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[&IdKey(a)], "a");
+        assert_eq!(map[&IdKey(b)], "b");
+    }
+}
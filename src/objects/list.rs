@@ -16,7 +16,9 @@
 // OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
 // DEALINGS IN THE SOFTWARE.
 
+use super::exc;
 use super::object::PyObject;
+use super::sequence::normalize_index;
 use crate::conversion::{FromPyObject, ToPyObject};
 use crate::err::{self, PyErr, PyResult};
 use crate::ffi::{self, Py_ssize_t};
@@ -42,6 +44,60 @@ impl PyList {
         }
     }
 
+    /// Construct a new list from the items of a Rust iterator.
+    ///
+    /// The list is pre-sized using the lower bound of the iterator's `size_hint()`;
+    /// any further items are appended.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{Python, PyList};
+    ///
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let list = PyList::from_iter(py, vec!["a", "b"]);
+    /// assert_eq!(list.len(py), 2);
+    /// ```
+    pub fn from_iter<I, T>(py: Python, elements: I) -> PyList
+    where
+        I: IntoIterator<Item = T>,
+        T: ToPyObject,
+    {
+        let elements = elements.into_iter();
+        let (lower, _) = elements.size_hint();
+        unsafe {
+            let ptr = ffi::PyList_New(lower as Py_ssize_t);
+            let t = err::result_from_owned_ptr(py, ptr)
+                .unwrap()
+                .unchecked_cast_into::<PyList>();
+            let mut len = 0;
+            for e in elements {
+                let obj = e.into_py_object(py).into_object();
+                if len < lower {
+                    ffi::PyList_SetItem(ptr, len as Py_ssize_t, obj.steal_ptr());
+                } else {
+                    err::error_on_minusone(py, ffi::PyList_Append(ptr, obj.as_ptr())).unwrap();
+                }
+                len += 1;
+            }
+            if len < lower {
+                // The iterator yielded fewer items than promised;
+                // remove the remaining (still empty) slots.
+                err::error_on_minusone(
+                    py,
+                    ffi::PyList_SetSlice(
+                        ptr,
+                        len as Py_ssize_t,
+                        lower as Py_ssize_t,
+                        std::ptr::null_mut(),
+                    ),
+                )
+                .unwrap();
+            }
+            t
+        }
+    }
+
     /// Gets the length of the list.
     #[inline]
     pub fn len(&self, _py: Python) -> usize {
@@ -51,15 +107,29 @@ impl PyList {
 
     /// Gets the item at the specified index.
     ///
-    /// Panics if the index is out of range.
-    pub fn get_item(&self, py: Python, index: usize) -> PyObject {
-        // TODO: do we really want to panic here?
-        assert!(index < self.len(py));
+    /// Negative indices count from the end of the list, as in Python.
+    /// Returns `IndexError` if the index is out of range.
+    pub fn get_item(&self, py: Python, index: isize) -> PyResult<PyObject> {
+        match self.try_get_item(py, index) {
+            Some(item) => Ok(item),
+            None => Err(PyErr::new::<exc::IndexError, _>(
+                py,
+                "list index out of range",
+            )),
+        }
+    }
+
+    /// Gets the item at the specified index,
+    /// or `None` if the index is out of range.
+    ///
+    /// Negative indices count from the end of the list, as in Python.
+    pub fn try_get_item(&self, py: Python, index: isize) -> Option<PyObject> {
+        let index = normalize_index(index, self.len(py))?;
         unsafe {
-            PyObject::from_borrowed_ptr(
+            Some(PyObject::from_borrowed_ptr(
                 py,
                 ffi::PyList_GetItem(self.0.as_ptr(), index as Py_ssize_t),
-            )
+            ))
         }
     }
 
@@ -72,24 +142,39 @@ impl PyList {
         assert!(r == 0);
     }
 
-    /// Inserts an item at the specified index.
+    /// Inserts an item before the specified index.
+    /// This is equivalent to the Python statement `self.insert(index, item)`.
     ///
-    /// Panics if the index is out of range.
-    pub fn insert(&self, _py: Python, index: usize, item: PyObject) {
-        let r = unsafe { ffi::PyList_Insert(self.0.as_ptr(), index as Py_ssize_t, item.as_ptr()) };
-        assert!(r == 0);
+    /// Negative indices are relative to the end of the list;
+    /// indices that are out of range insert at the start or end of the list.
+    pub fn insert<T>(&self, py: Python, index: isize, item: T) -> PyResult<()>
+    where
+        T: ToPyObject,
+    {
+        item.with_borrowed_ptr(py, |item| unsafe {
+            err::error_on_minusone(
+                py,
+                ffi::PyList_Insert(self.0.as_ptr(), index as Py_ssize_t, item),
+            )
+        })
     }
 
     // Old name for `insert`.
     #[deprecated(since = "0.3.1", note = "use list.insert() instead")]
     #[doc(hidden)]
     pub fn insert_item(&self, py: Python, index: usize, item: PyObject) {
-        self.insert(py, index, item);
+        self.insert(py, index as isize, item).unwrap();
     }
 
-    /// Appends an item to the end of the list
-    pub fn append(&self, _py: Python, item: PyObject) {
-        unsafe { ffi::PyList_Append(self.0.as_ptr(), item.as_ptr()) };
+    /// Appends an item to the end of the list.
+    /// This is equivalent to the Python statement `self.append(item)`.
+    pub fn append<T>(&self, py: Python, item: T) -> PyResult<()>
+    where
+        T: ToPyObject,
+    {
+        item.with_borrowed_ptr(py, |item| unsafe {
+            err::error_on_minusone(py, ffi::PyList_Append(self.0.as_ptr(), item))
+        })
     }
 
     #[inline]
@@ -114,13 +199,9 @@ impl<'a, 'p> Iterator for PyListIterator<'a, 'p> {
 
     #[inline]
     fn next(&mut self) -> Option<PyObject> {
-        if self.index < self.list.len(self.py) {
-            let item = self.list.get_item(self.py, self.index);
-            self.index += 1;
-            Some(item)
-        } else {
-            None
-        }
+        let item = self.list.try_get_item(self.py, self.index as isize)?;
+        self.index += 1;
+        Some(item)
     }
 
     // Note: we cannot implement size_hint because the length of the list
@@ -182,7 +263,7 @@ where
 #[cfg(test)]
 mod test {
     use crate::conversion::ToPyObject;
-    use crate::objects::PyList;
+    use crate::objects::{exc, PyList};
     use crate::python::{Python, PythonObject};
 
     #[test]
@@ -200,10 +281,37 @@ mod test {
         let py = gil.python();
         let v = vec![2, 3, 5, 7];
         let list = v.to_py_object(py);
-        assert_eq!(2, list.get_item(py, 0).extract::<i32>(py).unwrap());
-        assert_eq!(3, list.get_item(py, 1).extract::<i32>(py).unwrap());
-        assert_eq!(5, list.get_item(py, 2).extract::<i32>(py).unwrap());
-        assert_eq!(7, list.get_item(py, 3).extract::<i32>(py).unwrap());
+        assert_eq!(2, list.get_item(py, 0).unwrap().extract::<i32>(py).unwrap());
+        assert_eq!(3, list.get_item(py, 1).unwrap().extract::<i32>(py).unwrap());
+        assert_eq!(5, list.get_item(py, 2).unwrap().extract::<i32>(py).unwrap());
+        assert_eq!(7, list.get_item(py, 3).unwrap().extract::<i32>(py).unwrap());
+    }
+
+    #[test]
+    fn test_get_item_negative_and_out_of_range() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = vec![2, 3, 5, 7].to_py_object(py);
+        assert_eq!(
+            7,
+            list.get_item(py, -1).unwrap().extract::<i32>(py).unwrap()
+        );
+        assert_eq!(
+            2,
+            list.get_item(py, -4).unwrap().extract::<i32>(py).unwrap()
+        );
+        let err = list.get_item(py, 4).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::IndexError>()));
+        assert!(list.get_item(py, -5).is_err());
+        assert!(list.try_get_item(py, 4).is_none());
+        assert!(list.try_get_item(py, -5).is_none());
+        assert_eq!(
+            3,
+            list.try_get_item(py, -3)
+                .unwrap()
+                .extract::<i32>(py)
+                .unwrap()
+        );
     }
 
     #[test]
@@ -213,9 +321,12 @@ mod test {
         let v = vec![2, 3, 5, 7];
         let list = v.to_py_object(py);
         let val = 42i32.to_py_object(py).into_object();
-        assert_eq!(2, list.get_item(py, 0).extract::<i32>(py).unwrap());
+        assert_eq!(2, list.get_item(py, 0).unwrap().extract::<i32>(py).unwrap());
         list.set_item(py, 0, val);
-        assert_eq!(42, list.get_item(py, 0).extract::<i32>(py).unwrap());
+        assert_eq!(
+            42,
+            list.get_item(py, 0).unwrap().extract::<i32>(py).unwrap()
+        );
     }
 
     #[test]
@@ -226,11 +337,28 @@ mod test {
         let list = v.to_py_object(py);
         let val = 42i32.to_py_object(py).into_object();
         assert_eq!(4, list.len(py));
-        assert_eq!(2, list.get_item(py, 0).extract::<i32>(py).unwrap());
-        list.insert(py, 0, val);
+        assert_eq!(2, list.get_item(py, 0).unwrap().extract::<i32>(py).unwrap());
+        list.insert(py, 0, val).unwrap();
         assert_eq!(5, list.len(py));
-        assert_eq!(42, list.get_item(py, 0).extract::<i32>(py).unwrap());
-        assert_eq!(2, list.get_item(py, 1).extract::<i32>(py).unwrap());
+        assert_eq!(
+            42,
+            list.get_item(py, 0).unwrap().extract::<i32>(py).unwrap()
+        );
+        assert_eq!(2, list.get_item(py, 1).unwrap().extract::<i32>(py).unwrap());
+    }
+
+    #[test]
+    fn test_insert_out_of_range() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = vec![2, 3, 5, 7].to_py_object(py);
+        list.insert(py, -1, 6).unwrap();
+        list.insert(py, 100, 11).unwrap();
+        list.insert(py, -100, 1).unwrap();
+        assert_eq!(
+            vec![1, 2, 3, 5, 6, 7, 11],
+            list.as_object().extract::<Vec<i32>>(py).unwrap()
+        );
     }
 
     #[test]
@@ -241,9 +369,17 @@ mod test {
         let list = v.to_py_object(py);
         let val = 42i32.to_py_object(py).into_object();
         assert_eq!(4, list.len(py));
-        list.append(py, val);
+        list.append(py, val).unwrap();
         assert_eq!(5, list.len(py));
-        assert_eq!(42, list.get_item(py, 4).extract::<i32>(py).unwrap());
+        assert_eq!(
+            42,
+            list.get_item(py, 4).unwrap().extract::<i32>(py).unwrap()
+        );
+        list.append(py, "foo").unwrap();
+        assert_eq!(
+            "foo",
+            list.get_item(py, 5).unwrap().extract::<String>(py).unwrap()
+        );
     }
 
     #[test]
@@ -269,4 +405,43 @@ mod test {
         let v2 = list.into_object().extract::<Vec<i32>>(py).unwrap();
         assert_eq!(v, v2);
     }
+
+    #[test]
+    fn test_from_iter() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let list = PyList::from_iter(py, (0..6).filter(|i| i % 2 == 0));
+        assert_eq!(
+            list.as_object().extract::<Vec<i32>>(py).unwrap(),
+            vec![0, 2, 4]
+        );
+        let list = PyList::from_iter(py, vec![1.5, 2.5]);
+        assert_eq!(
+            list.as_object().extract::<Vec<f64>>(py).unwrap(),
+            vec![1.5, 2.5]
+        );
+
+        // An iterator whose size_hint overestimates the number of items.
+        struct Overestimate(u32);
+        impl Iterator for Overestimate {
+            type Item = u32;
+            fn next(&mut self) -> Option<u32> {
+                self.0 += 1;
+                if self.0 <= 2 {
+                    Some(self.0)
+                } else {
+                    None
+                }
+            }
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (5, Some(5))
+            }
+        }
+        let list = PyList::from_iter(py, Overestimate(0));
+        assert_eq!(list.len(py), 2);
+        assert_eq!(
+            list.as_object().extract::<Vec<u32>>(py).unwrap(),
+            vec![1, 2]
+        );
+    }
 }
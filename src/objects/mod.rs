@@ -26,11 +26,18 @@ pub use self::typeobject::PyType;
 pub use self::string::PyString as PyUnicode;
 #[cfg(feature = "python27-sys")]
 pub use self::string::PyUnicode;
-pub use self::string::{PyBytes, PyString, PyStringData};
+#[cfg(feature = "python3-sys")]
+pub use self::string::SurrogateHandling;
+pub use self::string::{AttrName, PyBytes, PyString, PyStringData};
 
 pub use self::boolobject::PyBool;
 pub use self::capsule::PyCapsule;
+pub use self::code::{CompileMode, PyCode};
+#[cfg(all(feature = "python3-sys", Py_3_7, not(Py_LIMITED_API)))]
+pub use self::contextvars::{PyContext, PyContextToken, PyContextVar};
 pub use self::dict::PyDict;
+pub use self::frame::PyFrame;
+pub use self::hashable::{HashableObject, IdKey};
 pub use self::iterator::PyIterator;
 pub use self::list::PyList;
 pub use self::none::PyNone;
@@ -38,7 +45,7 @@ pub use self::none::PyNone;
 pub use self::num::PyInt;
 #[cfg(feature = "python3-sys")]
 pub use self::num::PyLong as PyInt;
-pub use self::num::{PyFloat, PyLong};
+pub use self::num::{PyFloat, PyLong, StrictFloat, StrictInt};
 pub use self::sequence::PySequence;
 pub use self::set::PySet;
 pub use self::tuple::{NoArgs, PyTuple};
@@ -137,8 +144,13 @@ macro_rules! extract(
 
 mod boolobject;
 mod capsule;
+mod code;
+#[cfg(all(feature = "python3-sys", Py_3_7, not(Py_LIMITED_API)))]
+mod contextvars;
 mod dict;
 pub mod exc;
+mod frame;
+mod hashable;
 mod iterator;
 mod list;
 mod module;
@@ -18,6 +18,7 @@
 
 use libc::c_char;
 use std::ffi::{CStr, CString};
+use std::ptr;
 
 use crate::conversion::ToPyObject;
 use crate::err::{self, PyErr, PyResult};
@@ -45,6 +46,45 @@ impl PyModule {
         unsafe { err::result_cast_from_owned_ptr(py, ffi::PyImport_ImportModule(name.as_ptr())) }
     }
 
+    /// Import the Python module with the specified name, together with the names in `fromlist`.
+    /// This is equivalent to the Python expression `__import__(name, fromlist=fromlist)`,
+    /// which is what `from name import a, b` does before looking up `a` and `b`.
+    ///
+    /// Names in `fromlist` that are not attributes of the module are imported as submodules
+    /// (e.g. `import_from(py, "xml.dom", &["minidom"])` imports `xml.dom.minidom`),
+    /// and `"*"` imports the submodules listed in the package's `__all__`.
+    /// Missing submodules are ignored.
+    ///
+    /// If `fromlist` is non-empty, the module `name` is returned.
+    /// If it is empty, the top-level package is returned, like `import name` would bind
+    /// (e.g. `xml` for `"xml.dom"`); use `import()` to get the module `name` itself.
+    pub fn import_from(py: Python, name: &str, fromlist: &[&str]) -> PyResult<PyModule> {
+        let name = CString::new(name).unwrap();
+        let fromlist = fromlist.to_py_object(py);
+        unsafe {
+            err::result_cast_from_owned_ptr(
+                py,
+                ffi::PyImport_ImportModuleLevel(
+                    name.as_ptr() as *mut c_char,
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                    fromlist.as_object().as_ptr(),
+                    0,
+                ),
+            )
+        }
+    }
+
+    /// Reloads the module, re-executing its code in the existing module object.
+    /// This is equivalent to the Python expression `importlib.reload(self)`.
+    ///
+    /// Returns the module found in `sys.modules` afterwards (usually `self`).
+    /// Objects created from the old module code (e.g. instances of its classes,
+    /// or functions imported with `from module import f`) are not updated.
+    pub fn reload(&self, py: Python) -> PyResult<PyModule> {
+        unsafe { err::result_cast_from_owned_ptr(py, ffi::PyImport_ReloadModule(self.0.as_ptr())) }
+    }
+
     /// Return the dictionary object that implements module's namespace;
     /// this object is the same as the `__dict__` attribute of the module object.
     pub fn dict(&self, py: Python) -> PyDict {
@@ -302,7 +302,15 @@ impl ToPyObject for f64 {
 
 extract!(
     obj to f64;
-    /// Converts Python `float` to Rust `f64`.
+    /// Converts a Python number to Rust `f64`.
+    ///
+    /// Accepts `float`, `int` (and `bool`), and any object implementing `__float__`,
+    /// such as `decimal.Decimal`, `fractions.Fraction` or NumPy scalars.
+    /// On Python 3.8 and later, objects implementing only `__index__` are accepted as well.
+    /// Fails with `OverflowError` if an `int` is too large to be represented,
+    /// and with `TypeError` for other objects (e.g. `str`).
+    ///
+    /// Use [StrictFloat](struct.StrictFloat.html) to accept only `float` objects.
     py => {
         let v = unsafe { ffi::PyFloat_AsDouble(obj.as_ptr()) };
         if v == -1.0 && PyErr::occurred(py) {
@@ -328,7 +336,7 @@ impl ToPyObject for f32 {
 
 extract!(
     obj to f32;
-    /// Converts Python `float` to Rust `f32`.
+    /// Converts a Python number to Rust `f32`, accepting the same objects as `f64`.
     ///
     /// This conversion loses precision as the 64-bit float from Python gets
     /// converted to a 32-bit float. Out-of-range numbers may also overflow to infinity.
@@ -337,8 +345,188 @@ extract!(
     }
 );
 
+/// Converts `obj` into a Python `long` (`int` on Python 3.x) using `__index__`.
+#[cfg(feature = "num-bigint")]
+fn index_as_long(py: Python, obj: &PyObject) -> PyResult<PyObject> {
+    let num = unsafe { err::result_from_owned_ptr(py, ffi::PyNumber_Index(obj.as_ptr()))? };
+    if unsafe { ffi::PyLong_Check(num.as_ptr()) } != 0 {
+        Ok(num)
+    } else {
+        // Python 2.x `int`
+        unsafe { err::result_from_owned_ptr(py, ffi::PyNumber_Long(num.as_ptr())) }
+    }
+}
+
+/// Conversion of `num_bigint::BigInt` to Python `int` (`long` on Python 2.x).
+///
+/// Requires the `num-bigint` feature.
+#[cfg(feature = "num-bigint")]
+impl ToPyObject for num_bigint::BigInt {
+    type ObjectType = PyLong;
+
+    fn to_py_object(&self, py: Python) -> PyLong {
+        let bytes = self.to_signed_bytes_le();
+        unsafe {
+            err::cast_from_owned_ptr_or_panic(
+                py,
+                ffi::_PyLong_FromByteArray(bytes.as_ptr(), bytes.len(), 1, 1),
+            )
+        }
+    }
+}
+
+/// Conversion of `num_bigint::BigUint` to Python `int` (`long` on Python 2.x).
+///
+/// Requires the `num-bigint` feature.
+#[cfg(feature = "num-bigint")]
+impl ToPyObject for num_bigint::BigUint {
+    type ObjectType = PyLong;
+
+    fn to_py_object(&self, py: Python) -> PyLong {
+        let bytes = self.to_bytes_le();
+        unsafe {
+            err::cast_from_owned_ptr_or_panic(
+                py,
+                ffi::_PyLong_FromByteArray(bytes.as_ptr(), bytes.len(), 1, 0),
+            )
+        }
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+extract!(
+    obj to num_bigint::BigInt;
+    /// Converts Python integers to `num_bigint::BigInt` without loss of precision.
+    ///
+    /// Returns TypeError if the input is not an integer.
+    ///
+    /// Requires the `num-bigint` feature.
+    py => {
+        let num = index_as_long(py, obj)?;
+        unsafe {
+            let n_bits = ffi::_PyLong_NumBits(num.as_ptr());
+            err_if_invalid_value(py, !0, n_bits)?;
+            // one extra bit for the sign
+            let mut buf = vec![0u8; n_bits / 8 + 1];
+            err::error_on_minusone(
+                py,
+                ffi::_PyLong_AsByteArray(
+                    num.as_ptr() as *mut ffi::PyLongObject,
+                    buf.as_mut_ptr(),
+                    buf.len(),
+                    1,
+                    1,
+                ),
+            )?;
+            Ok(num_bigint::BigInt::from_signed_bytes_le(&buf))
+        }
+    }
+);
+
+#[cfg(feature = "num-bigint")]
+extract!(
+    obj to num_bigint::BigUint;
+    /// Converts Python integers to `num_bigint::BigUint` without loss of precision.
+    ///
+    /// Returns OverflowError if the input integer is negative;
+    /// or TypeError if the input is not an integer.
+    ///
+    /// Requires the `num-bigint` feature.
+    py => {
+        let num = index_as_long(py, obj)?;
+        unsafe {
+            let n_bits = ffi::_PyLong_NumBits(num.as_ptr());
+            err_if_invalid_value(py, !0, n_bits)?;
+            let mut buf = vec![0u8; std::cmp::max(1, (n_bits + 7) / 8)];
+            err::error_on_minusone(
+                py,
+                ffi::_PyLong_AsByteArray(
+                    num.as_ptr() as *mut ffi::PyLongObject,
+                    buf.as_mut_ptr(),
+                    buf.len(),
+                    1,
+                    0,
+                ),
+            )?;
+            Ok(num_bigint::BigUint::from_bytes_le(&buf))
+        }
+    }
+);
+
+/// Wrapper for extracting an integer that rejects Python `bool`s.
+///
+/// In Python, `bool` is a subclass of `int`, so `obj.extract::<i64>(py)`
+/// happily converts `True` to `1`.
+/// Extracting a `StrictInt<T>` instead fails with `TypeError` if `obj` is a `bool`,
+/// and otherwise behaves like extracting `T`.
+///
+/// `StrictInt` can also be used as parameter type in `py_fn!` and `py_class!` methods.
+///
+/// # Example
+/// ```
+/// use cpython::{Python, PythonObject, StrictInt};
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let StrictInt(v) = py.eval("42", None, None).unwrap().extract::<StrictInt<i64>>(py).unwrap();
+/// assert_eq!(v, 42);
+/// assert!(py.True().into_object().extract::<StrictInt<i64>>(py).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StrictInt<T>(pub T);
+
+impl<'s, T> FromPyObject<'s> for StrictInt<T>
+where
+    T: FromPyObject<'s>,
+{
+    fn extract(py: Python, obj: &'s PyObject) -> PyResult<Self> {
+        if unsafe { ffi::PyBool_Check(obj.as_ptr()) } != 0 {
+            return Err(PyErr::new::<exc::TypeError, _>(
+                py,
+                "expected an integer, got bool",
+            ));
+        }
+        T::extract(py, obj).map(StrictInt)
+    }
+}
+
+/// Wrapper for extracting a floating-point number that accepts only Python `float`s.
+///
+/// Extracting `f64` converts any number (e.g. `int` or `decimal.Decimal`) to a float.
+/// Extracting a `StrictFloat<T>` instead fails with `TypeError` unless `obj` is
+/// an instance of `float` (or a subclass), and otherwise behaves like extracting `T`.
+///
+/// `StrictFloat` can also be used as parameter type in `py_fn!` and `py_class!` methods.
+///
+/// # Example
+/// ```
+/// use cpython::{Python, StrictFloat};
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let StrictFloat(v) = py.eval("1.5", None, None).unwrap().extract::<StrictFloat<f64>>(py).unwrap();
+/// assert_eq!(v, 1.5);
+/// assert!(py.eval("1", None, None).unwrap().extract::<StrictFloat<f64>>(py).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct StrictFloat<T>(pub T);
+
+impl<'s, T> FromPyObject<'s> for StrictFloat<T>
+where
+    T: FromPyObject<'s>,
+{
+    fn extract(py: Python, obj: &'s PyObject) -> PyResult<Self> {
+        if unsafe { ffi::PyFloat_Check(obj.as_ptr()) } == 0 {
+            return Err(PyErr::new::<exc::TypeError, _>(
+                py,
+                format!("expected a float, got {}", obj.get_type(py).name(py)),
+            ));
+        }
+        T::extract(py, obj).map(StrictFloat)
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use super::{StrictFloat, StrictInt};
     use crate::conversion::ToPyObject;
     use crate::python::{Python, PythonObject};
 
@@ -416,4 +604,133 @@ mod test {
         assert_eq!(v, obj.extract::<u64>(py).unwrap());
         assert!(obj.extract::<i64>(py).is_err());
     }
+
+    #[test]
+    fn test_strict_int_rejects_bool() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let t = py.True().into_object();
+        assert_eq!(1i64, t.extract::<i64>(py).unwrap());
+        let err = t.extract::<StrictInt<i64>>(py).unwrap_err();
+        assert!(err.matches(py, py.get_type::<crate::objects::exc::TypeError>()));
+        let obj = 5i32.to_py_object(py).into_object();
+        assert_eq!(StrictInt(5u8), obj.extract(py).unwrap());
+        assert!(1.5f64
+            .to_py_object(py)
+            .into_object()
+            .extract::<StrictInt<i32>>(py)
+            .is_err());
+    }
+
+    #[test]
+    fn test_extract_f64_from_numbers() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let extract = |code: &str| py.eval(code, None, None).unwrap().extract::<f64>(py);
+        assert_eq!(extract("1.5").unwrap(), 1.5);
+        assert_eq!(extract("3").unwrap(), 3.0);
+        assert_eq!(extract("True").unwrap(), 1.0);
+        assert_eq!(extract("2 ** 70").unwrap(), 2f64.powi(70));
+        assert_eq!(
+            extract("__import__('decimal').Decimal('2.25')").unwrap(),
+            2.25
+        );
+        assert_eq!(
+            extract("__import__('fractions').Fraction(1, 4)").unwrap(),
+            0.25
+        );
+        // stands in for NumPy scalars, which implement __float__
+        assert_eq!(
+            extract("type('Scalar', (object,), {'__float__': lambda self: 0.5})()").unwrap(),
+            0.5
+        );
+
+        let err = extract("10 ** 400").unwrap_err();
+        assert!(err.matches(py, py.get_type::<crate::objects::exc::OverflowError>()));
+        let err = extract("'1.5'").unwrap_err();
+        assert!(err.matches(py, py.get_type::<crate::objects::exc::TypeError>()));
+        assert_eq!(
+            py.eval("7", None, None)
+                .unwrap()
+                .extract::<f32>(py)
+                .unwrap(),
+            7.0
+        );
+    }
+
+    #[test]
+    fn test_strict_float() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj = 1.5f64.to_py_object(py).into_object();
+        assert_eq!(StrictFloat(1.5f64), obj.extract(py).unwrap());
+        assert_eq!(StrictFloat(1.5f32), obj.extract(py).unwrap());
+        let subclass = py.eval("type('F', (float,), {})(2.5)", None, None).unwrap();
+        assert_eq!(StrictFloat(2.5f64), subclass.extract(py).unwrap());
+        for code in &[
+            "1",
+            "True",
+            "__import__('decimal').Decimal('2.25')",
+            "'1.5'",
+        ] {
+            let err = py
+                .eval(code, None, None)
+                .unwrap()
+                .extract::<StrictFloat<f64>>(py)
+                .unwrap_err();
+            assert!(err.matches(py, py.get_type::<crate::objects::exc::TypeError>()));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "num-bigint")]
+    fn test_bigint_roundtrip() {
+        use crate::objectprotocol::ObjectProtocol;
+        use num_bigint::{BigInt, BigUint};
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let big = BigInt::from(3).pow(3000) + 12345;
+        let neg = -big.clone();
+        for v in &[
+            big,
+            neg,
+            BigInt::from(0),
+            BigInt::from(-1),
+            BigInt::from(-128),
+            BigInt::from(255),
+        ] {
+            let obj = v.to_py_object(py).into_object();
+            assert_eq!(*v, obj.extract::<BigInt>(py).unwrap());
+        }
+        let obj = py.eval("-(3 ** 3000) - 12345", None, None).unwrap();
+        assert_eq!(
+            obj.extract::<BigInt>(py).unwrap(),
+            -(BigInt::from(3).pow(3000) + 12345)
+        );
+
+        let obj = py.eval("2 ** 4096 - 1", None, None).unwrap();
+        let v = obj.extract::<BigUint>(py).unwrap();
+        assert_eq!(v, BigUint::from(2u32).pow(4096) - 1u32);
+        assert_eq!(
+            v.to_py_object(py).into_object().compare(py, &obj).unwrap(),
+            std::cmp::Ordering::Equal
+        );
+        assert_eq!(
+            BigUint::from(0u32),
+            py.eval("0", None, None)
+                .unwrap()
+                .extract::<BigUint>(py)
+                .unwrap()
+        );
+        assert!(py
+            .eval("-1", None, None)
+            .unwrap()
+            .extract::<BigUint>(py)
+            .is_err());
+        assert!(py
+            .eval("1.5", None, None)
+            .unwrap()
+            .extract::<BigInt>(py)
+            .is_err());
+    }
 }
@@ -58,7 +58,27 @@ unsafe impl Send for PyObject {}
 unsafe impl Sync for PyObject {}
 
 /// Dropping a `PyObject` decrements the reference count on the object by 1.
+///
+/// If the current thread does not hold the GIL, the decrement is deferred:
+/// the object is pushed onto a queue that is drained the next time any thread
+/// acquires the GIL through `Python::acquire_gil()` or enters a Rust callback.
+/// Dropping never blocks waiting for the GIL.
+///
+/// With Python 2.7, which cannot cheaply check whether the GIL is held,
+/// dropping acquires the GIL instead.
 impl Drop for PyObject {
+    #[cfg(feature = "python3-sys")]
+    fn drop(&mut self) {
+        unsafe {
+            if ffi::PyGILState_Check() == 0 {
+                crate::pythonrun::register_decref(self.ptr.as_ptr());
+            } else {
+                ffi::Py_DECREF(self.ptr.as_ptr());
+            }
+        }
+    }
+
+    #[cfg(feature = "python27-sys")]
     fn drop(&mut self) {
         let _gil_guard = Python::acquire_gil();
         unsafe {
@@ -265,6 +285,18 @@ impl PyObject {
     pub fn is_none(&self, _py: Python) -> bool {
         self.as_ptr() == unsafe { ffi::Py_None() }
     }
+
+    /// Gets the identity of the object, as returned by the Python expression `id(self)`.
+    /// This is the address of the object and does not require the GIL.
+    ///
+    /// The identity is only unique while the object is alive: after the object
+    /// is deallocated, a new object may be allocated at the same address and get the same id.
+    /// To key a map by object identity, use an [IdKey](struct.IdKey.html) as the key instead;
+    /// the map then holds a reference that keeps the object, and thus its id, alive.
+    #[inline]
+    pub fn id(&self) -> usize {
+        self.as_ptr() as usize
+    }
 }
 
 /// PyObject implements the `==` operator using reference equality:
@@ -16,6 +16,7 @@
 // OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
 // DEALINGS IN THE SOFTWARE.
 
+use std::any::TypeId;
 use std::mem;
 
 use crate::buffer;
@@ -234,46 +235,68 @@ impl PySequence {
 /// Uses the sequence protocol and converts each individual element
 /// via `impl FromPyObject for T`.
 ///
-/// Note: when using `--features nightly`, a specialization of this impl
-/// may use the buffer protocol to perform a more efficient bulk copy.
-#[cfg(not(feature = "nightly"))]
+/// If `T` is a primitive numeric type (e.g. `u8`, `i64` or `f64`) and the Python object
+/// is a single-dimensional [buffer] with a compatible format (e.g. `array.array('d')`
+/// or a one-dimensional numpy array of `float64` for `Vec<f64>`), the data is instead
+/// copied directly from the buffer, which is much faster for large arrays.
+///
+/// [buffer]: https://docs.python.org/3/c-api/buffer.html
 impl<'s, T> FromPyObject<'s> for Vec<T>
 where
-    for<'a> T: FromPyObject<'a>,
+    for<'a> T: FromPyObject<'a> + 'static,
 {
     fn extract(py: Python, obj: &'s PyObject) -> PyResult<Self> {
+        if let Some(to_vec) = buffer_to_vec::<T>() {
+            if let Some(v) = extract_buffer(py, obj, to_vec) {
+                return Ok(v);
+            }
+        }
         extract_sequence(py, obj)
     }
 }
 
-/// Uses the sequence protocol and converts each individual element
-/// via `impl FromPyObject for T`.
-///
-/// Note: when using `--features nightly`, a specialization of this impl
-/// may use the buffer protocol to perform a more efficient bulk copy.
-#[cfg(feature = "nightly")]
-impl<'s, T> FromPyObject<'s> for Vec<T>
-where
-    for<'a> T: FromPyObject<'a>,
-{
-    default fn extract(py: Python, obj: &'s PyObject) -> PyResult<Self> {
-        extract_sequence(py, obj)
+type BufferToVec<T> = fn(&buffer::PyBuffer, Python) -> PyResult<Vec<T>>;
+
+/// Element types for which `Vec<T>` is copied from a buffer in bulk.
+/// The trait is private, so the set of types cannot be extended or changed elsewhere.
+trait VecBufferElement: buffer::Element + Copy + 'static {}
+
+macro_rules! vec_buffer_elements {
+    ($($t:ty),*) => {
+        $( impl VecBufferElement for $t {} )*
+
+        /// Returns `PyBuffer::to_vec::<T>` if `T` is a `VecBufferElement` type.
+        fn buffer_to_vec<T: 'static>() -> Option<BufferToVec<T>> {
+            None $( .or_else(buffer_to_vec_as::<$t, T>) )*
+        }
     }
 }
 
-/// If the Python object is a single-dimensional [buffer] of format `c` or `B` (C: `char` or `unsigned char`),
-/// returns an owned copy of the data in the buffer.
-/// Otherwise, uses the sequence protocol and converts each individual element
-/// via `impl FromPyObject for u8`.
-///
-/// [buffer]: https://docs.python.org/3/c-api/buffer.html
-#[cfg(feature = "nightly")]
-impl<'s, T> FromPyObject<'s> for Vec<T>
-where
-    for<'a> T: FromPyObject<'a> + buffer::Element + Copy,
-{
-    fn extract(py: Python, obj: &'s PyObject) -> PyResult<Self> {
-        extract_buffer_or_sequence(py, obj)
+vec_buffer_elements!(u8, i8, u16, i16, u32, i32, u64, i64, usize, isize, f32, f64);
+
+fn buffer_to_vec_as<E: VecBufferElement, T: 'static>() -> Option<BufferToVec<T>> {
+    if TypeId::of::<E>() == TypeId::of::<T>() {
+        let to_vec: BufferToVec<E> = buffer::PyBuffer::to_vec::<E>;
+        // `E` and `T` are the same type, so are the function pointer types.
+        Some(unsafe { mem::transmute::<BufferToVec<E>, BufferToVec<T>>(to_vec) })
+    } else {
+        None
+    }
+}
+
+/// Converts a Python-style index (negative values count from the end)
+/// into an offset into a sequence of length `len`.
+/// Returns `None` if the index is out of range.
+pub(crate) fn normalize_index(index: isize, len: usize) -> Option<usize> {
+    let index = if index < 0 {
+        index + len as isize
+    } else {
+        index
+    };
+    if 0 <= index && (index as usize) < len {
+        Some(index as usize)
+    } else {
+        None
     }
 }
 
@@ -282,19 +305,27 @@ where
     for<'a> T: FromPyObject<'a> + buffer::Element + Copy,
 {
     // first try buffer protocol
-    if let Ok(buf) = buffer::PyBuffer::get(py, obj) {
-        if buf.dimensions() == 1 {
-            if let Ok(v) = buf.to_vec::<T>(py) {
-                buf.release_ref(py);
-                return Ok(v);
-            }
-        }
-        buf.release_ref(py);
+    if let Some(v) = extract_buffer(py, obj, buffer::PyBuffer::to_vec::<T>) {
+        return Ok(v);
     }
     // fall back to sequence protocol
     extract_sequence(py, obj)
 }
 
+/// Copies `obj` using `to_vec` if it is a single-dimensional buffer.
+/// Returns `None` if the object does not support the buffer protocol,
+/// or if `to_vec` rejects the buffer (e.g. due to an incompatible format).
+fn extract_buffer<T>(py: Python, obj: &PyObject, to_vec: BufferToVec<T>) -> Option<Vec<T>> {
+    let buf = buffer::PyBuffer::get(py, obj).ok()?;
+    let result = if buf.dimensions() == 1 {
+        to_vec(&buf, py).ok()
+    } else {
+        None
+    };
+    buf.release_ref(py);
+    result
+}
+
 fn extract_sequence<T>(py: Python, obj: &PyObject) -> PyResult<Vec<T>>
 where
     for<'a> T: FromPyObject<'a>,
@@ -652,4 +683,44 @@ mod test {
             .unwrap();
         assert!(v == b"abc");
     }
+
+    #[test]
+    #[cfg(feature = "python3-sys")] // array.array doesn't implement the buffer protocol in python 2.7
+    fn test_extract_array_to_vec() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let v: Vec<f64> = py
+            .eval("__import__('array').array('d', [1.0, 2.5])", None, None)
+            .unwrap()
+            .extract(py)
+            .unwrap();
+        assert!(v == [1.0, 2.5]);
+        let v: Vec<i64> = py
+            .eval("__import__('array').array('q', [-1, 2])", None, None)
+            .unwrap()
+            .extract(py)
+            .unwrap();
+        assert!(v == [-1, 2]);
+        // incompatible item type: falls back to the sequence protocol
+        let v: Vec<f64> = py
+            .eval("__import__('array').array('i', [3, 4])", None, None)
+            .unwrap()
+            .extract(py)
+            .unwrap();
+        assert!(v == [3.0, 4.0]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "python3-sys", Py_3_8))]
+    fn test_extract_buffer_only_to_vec() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        // PickleBuffer supports the buffer protocol, but not the sequence protocol
+        let obj = py
+            .eval("__import__('pickle').PickleBuffer(b'abc')", None, None)
+            .unwrap();
+        let v: Vec<u8> = obj.extract(py).unwrap();
+        assert!(v == b"abc");
+        assert!(obj.extract::<Vec<i32>>(py).is_err());
+    }
 }
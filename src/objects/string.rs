@@ -18,12 +18,13 @@
 
 use libc::c_char;
 use std::borrow::Cow;
-use std::{char, mem, str};
+use std::{char, mem, ptr, str};
 
-use super::{exc, PyObject};
+use super::{exc, PyDict, PyObject, PyTuple};
 use crate::conversion::{FromPyObject, RefFromPyObject, ToPyObject};
 use crate::err::{self, PyErr, PyResult};
 use crate::ffi;
+use crate::objectprotocol::ObjectProtocol;
 use crate::python::{PyClone, Python, PythonObject, PythonObjectDowncastError, ToPythonPointer};
 
 /// Represents a Python string.
@@ -262,6 +263,67 @@ impl PyString {
         new_impl(py, s)
     }
 
+    /// Creates a new Python string object from an ASCII-only Rust string.
+    ///
+    /// On Python 3.x, this copies the bytes directly into a compact ASCII string object,
+    /// skipping the UTF-8 decoding done by `PyString::new()`. This is faster for short
+    /// identifier-like strings that are created often.
+    /// On Python 2.7, this is equivalent to `PyString::new()`, which creates a byte string.
+    ///
+    /// The string must be ASCII-only. This is checked with a debug assertion;
+    /// in release builds, non-ASCII input falls back to `PyString::new()`.
+    pub fn new_ascii(py: Python, s: &str) -> PyResult<PyString> {
+        debug_assert!(
+            s.is_ascii(),
+            "PyString::new_ascii() called with non-ASCII string"
+        );
+        #[cfg(feature = "python3-sys")]
+        {
+            if s.is_ascii() {
+                unsafe {
+                    let ptr = ffi::PyUnicode_New(s.len() as ffi::Py_ssize_t, 127);
+                    if ptr.is_null() {
+                        return Err(PyErr::fetch(py));
+                    }
+                    std::ptr::copy_nonoverlapping(
+                        s.as_ptr(),
+                        ffi::PyUnicode_DATA(ptr) as *mut u8,
+                        s.len(),
+                    );
+                    return Ok(err::cast_from_owned_ptr_or_panic(py, ptr));
+                }
+            }
+        }
+        Ok(PyString::new(py, s))
+    }
+
+    /// Creates a new interned Python string object.
+    ///
+    /// All interned strings with the same contents share a single object,
+    /// so creating the same string repeatedly does not allocate new storage,
+    /// and dictionary lookups keyed by interned strings can compare by identity.
+    /// This is useful for attribute names and other identifier-like strings.
+    ///
+    /// Unlike `PyUnicode_InternFromString`, this supports strings with embedded NUL characters.
+    /// On Python 2.7, only byte strings can be interned; if `PyString::new()` would
+    /// create a unicode string, the returned string is not interned.
+    ///
+    /// Panics if out of memory.
+    pub fn new_interned(py: Python, s: &str) -> PyString {
+        let mut ptr = PyString::new(py, s).into_object().steal_ptr();
+        unsafe {
+            #[cfg(feature = "python27-sys")]
+            {
+                if ffi::PyString_CheckExact(ptr) != 0 {
+                    ffi::PyString_InternInPlace(&mut ptr);
+                }
+            }
+            #[cfg(feature = "python3-sys")]
+            ffi::PyUnicode_InternInPlace(&mut ptr);
+            err::cast_from_owned_ptr_or_panic(py, ptr)
+        }
+    }
+
     /// Gets the python string data in its underlying representation.
     ///
     /// For Python 2 byte strings, this function always returns `PyStringData::Utf8`,
@@ -351,6 +413,217 @@ impl PyString {
     pub fn to_string_lossy(&self, py: Python) -> Cow<str> {
         self.data(py).to_string_lossy()
     }
+
+    /// Formats the string using `str.format()`, treating `self` as the format string.
+    ///
+    /// This is equivalent to the Python expression `self.format(*args, **kwargs)`.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{Python, PyString, PyDict, ToPyObject};
+    ///
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let kwargs = PyDict::new(py);
+    /// kwargs.set_item(py, "width", 5).unwrap();
+    /// let s = PyString::new(py, "{0:>{width}}|{1:.2f}")
+    ///     .format(py, &(42, 1.5).to_py_object(py), Some(&kwargs))
+    ///     .unwrap();
+    /// assert_eq!(s.to_string(py).unwrap(), "   42|1.50");
+    /// ```
+    pub fn format(
+        &self,
+        py: Python,
+        args: &PyTuple,
+        kwargs: Option<&PyDict>,
+    ) -> PyResult<PyString> {
+        Ok(self
+            .0
+            .call_method(py, "format", args, kwargs)?
+            .cast_into::<PyString>(py)?)
+    }
+
+    /// Formats the string using `str.format_map()`, treating `self` as the format string.
+    ///
+    /// This is equivalent to the Python expression `self.format_map(mapping)`.
+    /// Unlike `format()`, the mapping is used directly rather than copied,
+    /// so it can be any object implementing `__getitem__`.
+    ///
+    /// Not available on Python 2.7, which lacks `str.format_map`.
+    #[cfg(feature = "python3-sys")]
+    pub fn format_map(&self, py: Python, mapping: &PyObject) -> PyResult<PyString> {
+        Ok(self
+            .0
+            .call_method(py, "format_map", (mapping,), None)?
+            .cast_into::<PyString>(py)?)
+    }
+
+    /// Formats the string using printf-style `%` formatting.
+    ///
+    /// This is equivalent to the Python expression `self % args`;
+    /// as in Python, `args` may be a tuple, a mapping or a single value.
+    pub fn percent_format(&self, py: Python, args: &PyObject) -> PyResult<PyString> {
+        unsafe {
+            err::result_cast_from_owned_ptr(
+                py,
+                ffi::PyNumber_Remainder(self.as_ptr(), args.as_ptr()),
+            )
+        }
+    }
+
+    /// Encodes the string using the codec registered for `encoding`.
+    /// This is equivalent to the Python expression `self.encode(encoding, errors)`.
+    ///
+    /// `errors` selects the error handler, e.g. `"strict"`, `"ignore"`, `"replace"`
+    /// or `"surrogateescape"`.
+    /// Fails with `LookupError` if the codec or error handler is unknown, and with
+    /// `UnicodeEncodeError` if the string cannot be encoded using the `"strict"` handler.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{Python, PyString};
+    ///
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let s = PyString::new(py, "caf\u{e9}");
+    /// let bytes = s.encode(py, "latin-1", "strict").unwrap();
+    /// assert_eq!(bytes.data(py), b"caf\xe9");
+    /// ```
+    pub fn encode(&self, py: Python, encoding: &str, errors: &str) -> PyResult<PyBytes> {
+        #[cfg(feature = "python3-sys")]
+        unsafe {
+            let encoding = codec_arg(py, encoding)?;
+            let errors = codec_arg(py, errors)?;
+            err::result_cast_from_owned_ptr(
+                py,
+                ffi::PyUnicode_AsEncodedString(self.as_ptr(), encoding.as_ptr(), errors.as_ptr()),
+            )
+        }
+        #[cfg(feature = "python27-sys")]
+        {
+            Ok(self
+                .0
+                .call_method(py, "encode", (encoding, errors), None)?
+                .cast_into::<PyBytes>(py)?)
+        }
+    }
+
+    /// Creates a new Python string object from UTF-16 code units
+    /// (in native byte order, without byte order mark).
+    ///
+    /// `surrogates` controls how unpaired surrogates are handled.
+    /// Fails with `UnicodeDecodeError` if the input is invalid.
+    #[cfg(feature = "python3-sys")]
+    pub fn from_utf16(
+        py: Python,
+        data: &[u16],
+        surrogates: SurrogateHandling,
+    ) -> PyResult<PyString> {
+        let mut byteorder = NATIVE_BYTEORDER;
+        unsafe {
+            err::result_cast_from_owned_ptr(
+                py,
+                ffi::PyUnicode_DecodeUTF16(
+                    data.as_ptr() as *const c_char,
+                    (data.len() * 2) as ffi::Py_ssize_t,
+                    surrogates.error_handler().as_ptr(),
+                    &mut byteorder,
+                ),
+            )
+        }
+    }
+
+    /// Creates a new Python string object from UCS-4 code points.
+    ///
+    /// `surrogates` controls how code points in the surrogate range are handled.
+    /// Fails with `UnicodeDecodeError` if the input is invalid.
+    #[cfg(feature = "python3-sys")]
+    pub fn from_ucs4(
+        py: Python,
+        data: &[u32],
+        surrogates: SurrogateHandling,
+    ) -> PyResult<PyString> {
+        let mut byteorder = NATIVE_BYTEORDER;
+        unsafe {
+            err::result_cast_from_owned_ptr(
+                py,
+                ffi::PyUnicode_DecodeUTF32(
+                    data.as_ptr() as *const c_char,
+                    (data.len() * 4) as ffi::Py_ssize_t,
+                    surrogates.error_handler().as_ptr(),
+                    &mut byteorder,
+                ),
+            )
+        }
+    }
+
+    /// Encodes the string as UTF-16 code units
+    /// (in native byte order, without byte order mark).
+    ///
+    /// Unlike `to_string()`, this does not go through UTF-8, so unpaired
+    /// surrogates can be preserved using `SurrogateHandling::SurrogatePass`.
+    /// Fails with `UnicodeEncodeError` if the string cannot be encoded.
+    #[cfg(feature = "python3-sys")]
+    pub fn to_utf16(&self, py: Python, surrogates: SurrogateHandling) -> PyResult<Vec<u16>> {
+        let encoding = if cfg!(target_endian = "little") {
+            cstr!("utf-16-le")
+        } else {
+            cstr!("utf-16-be")
+        };
+        let bytes: PyBytes = unsafe {
+            err::result_cast_from_owned_ptr(
+                py,
+                ffi::PyUnicode_AsEncodedString(
+                    self.as_ptr(),
+                    encoding.as_ptr(),
+                    surrogates.error_handler().as_ptr(),
+                ),
+            )?
+        };
+        Ok(bytes
+            .data(py)
+            .chunks_exact(2)
+            .map(|c| u16::from_ne_bytes([c[0], c[1]]))
+            .collect())
+    }
+}
+
+/// Controls how unpaired surrogates are handled when converting between
+/// Python strings and UTF-16 or UCS-4 data.
+#[cfg(feature = "python3-sys")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SurrogateHandling {
+    /// Unpaired surrogates are an error.
+    /// Corresponds to Python's `'strict'` error handler.
+    Strict,
+    /// Unpaired surrogates are passed through unchanged.
+    /// Corresponds to Python's `'surrogatepass'` error handler.
+    SurrogatePass,
+}
+
+#[cfg(feature = "python3-sys")]
+impl SurrogateHandling {
+    fn error_handler(self) -> &'static std::ffi::CStr {
+        match self {
+            SurrogateHandling::Strict => cstr!("strict"),
+            SurrogateHandling::SurrogatePass => cstr!("surrogatepass"),
+        }
+    }
+}
+
+/// `byteorder` argument for the UTF-16/UTF-32 codecs that selects native byte order.
+#[cfg(feature = "python3-sys")]
+const NATIVE_BYTEORDER: libc::c_int = if cfg!(target_endian = "little") {
+    -1
+} else {
+    1
+};
+
+/// Converts a codec or error handler name into a C string.
+#[cfg(feature = "python3-sys")]
+fn codec_arg(py: Python, name: &str) -> PyResult<std::ffi::CString> {
+    std::ffi::CString::new(name)
+        .map_err(|_| PyErr::new::<exc::ValueError, _>(py, "embedded null character"))
 }
 
 impl PyBytes {
@@ -364,6 +637,41 @@ impl PyBytes {
         unsafe { err::cast_from_owned_ptr_or_panic(py, ffi::PyBytes_FromStringAndSize(ptr, len)) }
     }
 
+    /// Creates a new Python byte string object of length `len`,
+    /// and lets `fill` write its contents in place.
+    ///
+    /// Unlike `PyBytes::new()`, this avoids building the data in a separate Rust buffer
+    /// and copying it. The buffer passed to `fill` is zero-initialized.
+    /// Byte strings are immutable once they are visible to Python code;
+    /// this is the only opportunity to modify the contents.
+    ///
+    /// Panics if out of memory.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{Python, PyBytes};
+    ///
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let bytes = PyBytes::new_with(py, 4, |buf| buf.copy_from_slice(b"abcd"));
+    /// assert_eq!(bytes.data(py), b"abcd");
+    /// ```
+    pub fn new_with<F>(py: Python, len: usize, fill: F) -> PyBytes
+    where
+        F: FnOnce(&mut [u8]),
+    {
+        unsafe {
+            let bytes: PyBytes = err::cast_from_owned_ptr_or_panic(
+                py,
+                ffi::PyBytes_FromStringAndSize(ptr::null(), len as ffi::Py_ssize_t),
+            );
+            let buffer = ffi::PyBytes_AsString(bytes.as_object().as_ptr()) as *mut u8;
+            ptr::write_bytes(buffer, 0, len);
+            fill(std::slice::from_raw_parts_mut(buffer, len));
+            bytes
+        }
+    }
+
     /// Gets the Python string data as byte slice.
     pub fn data(&self, _py: Python) -> &[u8] {
         unsafe {
@@ -373,6 +681,49 @@ impl PyBytes {
         }
     }
 
+    /// Decodes the byte string using the codec registered for `encoding`.
+    /// This is equivalent to the Python expression `self.decode(encoding, errors)`.
+    ///
+    /// `errors` selects the error handler, e.g. `"strict"`, `"ignore"`, `"replace"`
+    /// or `"surrogateescape"`.
+    /// Fails with `LookupError` if the codec or error handler is unknown, and with
+    /// `UnicodeDecodeError` if the data cannot be decoded using the `"strict"` handler.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{Python, PyBytes};
+    ///
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let bytes = PyBytes::new(py, b"\x82\xa0");
+    /// let s = bytes.decode(py, "shift-jis", "strict").unwrap();
+    /// assert_eq!(s.to_string(py).unwrap(), "\u{3042}");
+    /// ```
+    pub fn decode(&self, py: Python, encoding: &str, errors: &str) -> PyResult<PyString> {
+        #[cfg(feature = "python3-sys")]
+        unsafe {
+            let encoding = codec_arg(py, encoding)?;
+            let errors = codec_arg(py, errors)?;
+            let data = self.data(py);
+            err::result_cast_from_owned_ptr(
+                py,
+                ffi::PyUnicode_Decode(
+                    data.as_ptr() as *const c_char,
+                    data.len() as ffi::Py_ssize_t,
+                    encoding.as_ptr(),
+                    errors.as_ptr(),
+                ),
+            )
+        }
+        #[cfg(feature = "python27-sys")]
+        {
+            Ok(self
+                .0
+                .call_method(py, "decode", (encoding, errors), None)?
+                .cast_into::<PyString>(py)?)
+        }
+    }
+
     /// Converts from `PyBytes` to `PyString`.
     /// This method is only available on Python 2.
     #[cfg(feature = "python27-sys")]
@@ -490,6 +841,63 @@ impl ToPyObject for String {
     }
 }
 
+/// An interned attribute name, for repeated attribute lookups.
+///
+/// Passing a `&str` to `ObjectProtocol::getattr()` creates a new Python string
+/// for every call. An `AttrName` is created once, and can then be used to look up
+/// the attribute with `ObjectProtocol::getattr_cached()`, or passed to `getattr()`,
+/// `setattr()`, `hasattr()` and `delattr()`, without allocating or touching reference counts.
+/// Because the name is interned, dictionary lookups of the attribute can usually compare
+/// the key by identity.
+///
+/// # Example
+/// ```
+/// use cpython::{AttrName, ObjectProtocol, Python};
+///
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let real = AttrName::new(py, "real");
+/// let mut sum = 0;
+/// for i in 0..10 {
+///     let obj = py.eval(&i.to_string(), None, None).unwrap();
+///     sum += obj.getattr_cached(py, &real).unwrap().extract::<i32>(py).unwrap();
+/// }
+/// assert_eq!(sum, 45);
+/// ```
+pub struct AttrName(PyString);
+
+impl AttrName {
+    /// Creates an interned attribute name.
+    ///
+    /// Panics if out of memory.
+    pub fn new(py: Python, name: &str) -> AttrName {
+        AttrName(PyString::new_interned(py, name))
+    }
+
+    /// Gets the underlying Python string object.
+    #[inline]
+    pub fn as_string(&self) -> &PyString {
+        &self.0
+    }
+}
+
+impl ToPyObject for AttrName {
+    type ObjectType = PyString;
+
+    #[inline]
+    fn to_py_object(&self, py: Python) -> PyString {
+        self.0.clone_ref(py)
+    }
+
+    #[inline]
+    fn with_borrowed_ptr<F, R>(&self, _py: Python, f: F) -> R
+    where
+        F: FnOnce(*mut ffi::PyObject) -> R,
+    {
+        f(self.0.as_object().as_ptr())
+    }
+}
+
 /// Allows extracting strings from Python objects.
 /// Accepts Python `str` and `unicode` objects.
 /// In Python 2.7, `str` is expected to be UTF-8 encoded.
@@ -533,6 +941,90 @@ impl<'s> FromPyObject<'s> for Cow<'s, [u8]> {
     }
 }
 
+/// Copies the bytes of `obj` into storage of the requested length created by `alloc`.
+///
+/// Python `bytes` and single-dimensional [buffer]s of format `c` or `B` are copied
+/// directly into the new storage; other objects are extracted via the sequence protocol.
+///
+/// [buffer]: https://docs.python.org/3/c-api/buffer.html
+#[cfg(any(feature = "smallvec", feature = "arrayvec"))]
+fn extract_bytes_into<V, F>(py: Python, obj: &PyObject, alloc: F) -> PyResult<V>
+where
+    V: std::ops::DerefMut<Target = [u8]>,
+    F: FnOnce(Python, usize) -> PyResult<V>,
+{
+    use crate::buffer::{Element, PyBuffer};
+    use crate::python::PyDrop;
+
+    if let Ok(bytes) = obj.cast_as::<PyBytes>(py) {
+        let data = bytes.data(py);
+        let mut v = alloc(py, data.len())?;
+        v.copy_from_slice(data);
+        return Ok(v);
+    }
+    if let Ok(buf) = PyBuffer::get(py, obj) {
+        if buf.dimensions() == 1 && u8::is_compatible_format(buf.format()) {
+            let result = alloc(py, buf.item_count()).and_then(|mut v| {
+                buf.copy_to_slice(py, &mut *v)?;
+                Ok(v)
+            });
+            buf.release_ref(py);
+            return result;
+        }
+        buf.release_ref(py);
+    }
+    let data: Vec<u8> = super::sequence::extract_buffer_or_sequence(py, obj)?;
+    let mut v = alloc(py, data.len())?;
+    v.copy_from_slice(&data);
+    Ok(v)
+}
+
+/// Extracts Python `bytes`, single-dimensional [buffer]s of format `c` or `B`,
+/// or sequences of integers into a `SmallVec`.
+///
+/// The data is copied into the inline storage, spilling to the heap
+/// only if it is longer than the inline capacity.
+///
+/// Requires the `smallvec` feature.
+///
+/// [buffer]: https://docs.python.org/3/c-api/buffer.html
+#[cfg(feature = "smallvec")]
+impl<'s, A> FromPyObject<'s> for smallvec::SmallVec<A>
+where
+    A: smallvec::Array<Item = u8>,
+{
+    fn extract(py: Python, obj: &'s PyObject) -> PyResult<Self> {
+        extract_bytes_into(py, obj, |_py, len| {
+            Ok(smallvec::SmallVec::from_elem(0, len))
+        })
+    }
+}
+
+/// Extracts Python `bytes`, single-dimensional [buffer]s of format `c` or `B`,
+/// or sequences of integers into an `ArrayVec`.
+///
+/// Raises `ValueError` if the data does not fit into the capacity of the `ArrayVec`.
+///
+/// Requires the `arrayvec` feature.
+///
+/// [buffer]: https://docs.python.org/3/c-api/buffer.html
+#[cfg(feature = "arrayvec")]
+impl<'s, const CAP: usize> FromPyObject<'s> for arrayvec::ArrayVec<u8, CAP> {
+    fn extract(py: Python, obj: &'s PyObject) -> PyResult<Self> {
+        extract_bytes_into(py, obj, |py, len| {
+            if len > CAP {
+                return Err(PyErr::new::<exc::ValueError, _>(
+                    py,
+                    format!("expected at most {} bytes, got {}", CAP, len),
+                ));
+            }
+            let mut v = arrayvec::ArrayVec::from([0; CAP]);
+            v.truncate(len);
+            Ok(v)
+        })
+    }
+}
+
 /// Allows extracting strings from Python objects.
 /// Accepts Python `str` and `unicode` objects.
 /// In Python 2.7, `str` is expected to be UTF-8 encoded.
@@ -565,9 +1057,192 @@ impl RefFromPyObject for [u8] {
 
 #[cfg(test)]
 mod test {
-    use super::{PyString, PyStringData};
+    use super::{AttrName, PyBytes, PyString, PyStringData};
     use crate::conversion::{RefFromPyObject, ToPyObject};
+    use crate::objectprotocol::ObjectProtocol;
+    use crate::objects::{exc, PyDict, PyTuple};
     use crate::python::{Python, PythonObject};
+    use std::cmp::Ordering;
+
+    #[test]
+    fn test_new_interned() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let a = PyString::new_interned(py, "value");
+        let b = PyString::new_interned(py, &String::from("value"));
+        assert_eq!(a.as_object().as_ptr(), b.as_object().as_ptr());
+        assert_eq!(b.to_string(py).unwrap(), "value");
+
+        let c = PyString::new_interned(py, "nul\0byte");
+        let d = PyString::new_interned(py, "nul\0byte");
+        assert_eq!(c.as_object().as_ptr(), d.as_object().as_ptr());
+        assert_eq!(d.to_string(py).unwrap(), "nul\0byte");
+        assert_ne!(a.as_object().as_ptr(), c.as_object().as_ptr());
+    }
+
+    #[test]
+    fn test_new_ascii() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        for s in &["", "a", "identifier_123", "with\0nul"] {
+            let ascii = PyString::new_ascii(py, s).unwrap();
+            assert_eq!(ascii.to_string(py).unwrap(), *s);
+            assert_eq!(
+                ascii.as_object().compare(py, PyString::new(py, s)).unwrap(),
+                Ordering::Equal
+            );
+        }
+        #[cfg(feature = "python3-sys")]
+        {
+            let s = PyString::new_ascii(py, "name").unwrap();
+            match s.data(py) {
+                PyStringData::Latin1(data) => assert_eq!(data, b"name"),
+                _ => panic!("expected a 1-byte string"),
+            }
+            let dict = PyDict::new(py);
+            dict.set_item(py, "s", s).unwrap();
+            py.run(
+                "assert s == 'name' and hash(s) == hash('name')",
+                None,
+                Some(&dict),
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn test_new_ascii_non_ascii_fallback() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let s = PyString::new_ascii(py, "caf\u{e9}").unwrap();
+        assert_eq!(s.to_string(py).unwrap(), "caf\u{e9}");
+    }
+
+    #[test]
+    fn test_attr_name() {
+        use crate::objectprotocol::ObjectProtocol;
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let name = AttrName::new(py, "value");
+        let obj = py.eval("type('A', (object,), {})()", None, None).unwrap();
+        assert!(!obj.hasattr(py, &name).unwrap());
+        obj.setattr(py, &name, 42).unwrap();
+        assert_eq!(
+            obj.getattr(py, "value")
+                .unwrap()
+                .extract::<i32>(py)
+                .unwrap(),
+            42
+        );
+        assert_eq!(
+            obj.getattr(py, &name).unwrap().extract::<i32>(py).unwrap(),
+            42
+        );
+        assert_eq!(
+            obj.getattr_cached(py, &name)
+                .unwrap()
+                .extract::<i32>(py)
+                .unwrap(),
+            42
+        );
+        obj.delattr(py, &name).unwrap();
+        assert!(obj.getattr_cached(py, &name).is_err());
+        assert_eq!(name.as_string().to_string(py).unwrap(), "value");
+        // interned names are shared
+        #[cfg(feature = "python3-sys")]
+        assert!(AttrName::new(py, "value").as_string().as_object() == name.as_string().as_object());
+    }
+
+    #[test]
+    fn test_bytes_new_with() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let bytes = PyBytes::new_with(py, 256, |buf| {
+            assert!(buf.iter().all(|&b| b == 0));
+            for (i, b) in buf.iter_mut().enumerate() {
+                *b = i as u8;
+            }
+        });
+        assert_eq!(bytes.data(py).len(), 256);
+        assert!(bytes
+            .data(py)
+            .iter()
+            .enumerate()
+            .all(|(i, &b)| b == i as u8));
+
+        let empty = PyBytes::new_with(py, 0, |buf| assert!(buf.is_empty()));
+        assert_eq!(empty.data(py), b"");
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let s = PyString::new(py, "caf\u{e9}");
+        let bytes = s.encode(py, "latin-1", "strict").unwrap();
+        assert_eq!(bytes.data(py), b"caf\xe9");
+        assert_eq!(s.encode(py, "ascii", "ignore").unwrap().data(py), b"caf");
+        assert_eq!(s.encode(py, "ascii", "replace").unwrap().data(py), b"caf?");
+        let err = s.encode(py, "ascii", "strict").err().unwrap();
+        assert!(err.matches(py, py.get_type::<exc::UnicodeEncodeError>()));
+
+        let decoded = bytes.decode(py, "latin-1", "strict").unwrap();
+        assert_eq!(decoded.to_string(py).unwrap(), "caf\u{e9}");
+        let decoded = bytes.decode(py, "utf-8", "replace").unwrap();
+        assert_eq!(decoded.to_string(py).unwrap(), "caf\u{fffd}");
+        let err = bytes.decode(py, "no-such-codec", "strict").err().unwrap();
+        assert!(err.matches(py, py.get_type::<exc::LookupError>()));
+    }
+
+    #[test]
+    fn test_format() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let kwargs = PyDict::new(py);
+        kwargs.set_item(py, "name", "world").unwrap();
+        let s = PyString::new(py, "{0}, {name}! {1:03d}")
+            .format(py, &("Hello", 7).to_py_object(py), Some(&kwargs))
+            .unwrap();
+        assert_eq!(s.to_string(py).unwrap(), "Hello, world! 007");
+
+        let err = PyString::new(py, "{missing}")
+            .format(py, &PyTuple::empty(py), None)
+            .err()
+            .unwrap();
+        assert!(err.matches(py, py.get_type::<crate::objects::exc::KeyError>()));
+    }
+
+    #[test]
+    #[cfg(feature = "python3-sys")]
+    fn test_format_map() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let mapping = PyDict::new(py);
+        mapping.set_item(py, "x", 1).unwrap();
+        let s = PyString::new(py, "x={x}")
+            .format_map(py, mapping.as_object())
+            .unwrap();
+        assert_eq!(s.to_string(py).unwrap(), "x=1");
+    }
+
+    #[test]
+    fn test_percent_format() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let fmt = PyString::new(py, "%s=%d");
+        let s = fmt
+            .percent_format(py, ("a", 3).to_py_object(py).as_object())
+            .unwrap();
+        assert_eq!(s.to_string(py).unwrap(), "a=3");
+
+        let single = PyString::new(py, "[%s]")
+            .percent_format(py, &"x".to_py_object(py).into_object())
+            .unwrap();
+        assert_eq!(single.to_string(py).unwrap(), "[x]");
+
+        assert!(fmt.percent_format(py, &py.None()).is_err());
+    }
 
     #[test]
     fn test_non_bmp() {
@@ -608,7 +1283,6 @@ mod test {
     }
 
     #[test]
-    #[cfg(feature = "nightly")] // only works with specialization
     fn test_extract_byte_str_to_vec() {
         let gil = Python::acquire_gil();
         let py = gil.python();
@@ -617,6 +1291,36 @@ mod test {
         assert_eq!(b"Hello", &v[..]);
     }
 
+    #[test]
+    #[cfg(feature = "smallvec")]
+    fn test_extract_bytes_to_smallvec() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let py_bytes = py.eval("b'Hello'", None, None).unwrap();
+        let v = py_bytes.extract::<smallvec::SmallVec<[u8; 8]>>(py).unwrap();
+        assert_eq!(b"Hello", &v[..]);
+        assert!(!v.spilled());
+        let py_array = py.eval("bytearray(b'Hello, world')", None, None).unwrap();
+        let v = py_array.extract::<smallvec::SmallVec<[u8; 8]>>(py).unwrap();
+        assert_eq!(b"Hello, world", &v[..]);
+        assert!(v.spilled());
+    }
+
+    #[test]
+    #[cfg(feature = "arrayvec")]
+    fn test_extract_bytes_to_arrayvec() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let py_bytes = py.eval("b'Hello'", None, None).unwrap();
+        let v = py_bytes.extract::<arrayvec::ArrayVec<u8, 8>>(py).unwrap();
+        assert_eq!(b"Hello", &v[..]);
+        let py_list = py.eval("[1, 2, 3]", None, None).unwrap();
+        let v = py_list.extract::<arrayvec::ArrayVec<u8, 8>>(py).unwrap();
+        assert_eq!([1, 2, 3], &v[..]);
+        let py_long = py.eval("b'Hello, world'", None, None).unwrap();
+        assert!(py_long.extract::<arrayvec::ArrayVec<u8, 8>>(py).is_err());
+    }
+
     #[allow(unused_variables)] // when compiling for py2.7
     #[test]
     fn test_extract_umlaut() {
@@ -664,4 +1368,52 @@ mod test {
             .to_string_lossy(py);
         assert_eq!("x=\u{fffd}", result);
     }
+
+    #[test]
+    #[cfg(feature = "python3-sys")]
+    fn test_utf16_roundtrip() {
+        use super::SurrogateHandling;
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let data: Vec<u16> = "x=\u{1F30F}".encode_utf16().collect();
+        let py_string = PyString::from_utf16(py, &data, SurrogateHandling::Strict).unwrap();
+        assert_eq!("x=\u{1F30F}", py_string.to_string(py).unwrap());
+        assert_eq!(
+            data,
+            py_string.to_utf16(py, SurrogateHandling::Strict).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "python3-sys")]
+    fn test_utf16_lone_surrogate() {
+        use super::SurrogateHandling;
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let data = ['x' as u16, 0xd800];
+        assert!(PyString::from_utf16(py, &data, SurrogateHandling::Strict).is_err());
+        let py_string = PyString::from_utf16(py, &data, SurrogateHandling::SurrogatePass).unwrap();
+        assert!(py_string.to_utf16(py, SurrogateHandling::Strict).is_err());
+        assert_eq!(
+            data[..],
+            py_string
+                .to_utf16(py, SurrogateHandling::SurrogatePass)
+                .unwrap()[..]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "python3-sys")]
+    fn test_from_ucs4() {
+        use super::SurrogateHandling;
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let data = ['x' as u32, 0x1F30F];
+        let py_string = PyString::from_ucs4(py, &data, SurrogateHandling::Strict).unwrap();
+        assert_eq!("x\u{1F30F}", py_string.to_string(py).unwrap());
+        assert!(PyString::from_ucs4(py, &[0xd800], SurrogateHandling::Strict).is_err());
+        let py_string =
+            PyString::from_ucs4(py, &[0xd800], SurrogateHandling::SurrogatePass).unwrap();
+        assert!(py_string.to_string(py).is_err());
+    }
 }
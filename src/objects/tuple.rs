@@ -20,6 +20,7 @@ use std::slice;
 
 use super::exc;
 use super::object::PyObject;
+use super::sequence::normalize_index;
 use crate::conversion::{FromPyObject, ToPyObject};
 use crate::err::{self, PyErr, PyResult};
 use crate::ffi::{self, Py_ssize_t};
@@ -44,6 +45,39 @@ impl PyTuple {
         }
     }
 
+    /// Construct a new tuple from the items of a Rust iterator.
+    ///
+    /// As the length of a tuple is fixed on creation, the items are converted
+    /// and collected first, then moved into the tuple.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{Python, PyTuple};
+    ///
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let t = PyTuple::from_iter(py, (1..4).map(|i| i * 10));
+    /// assert_eq!(t.len(py), 3);
+    /// ```
+    pub fn from_iter<I, T>(py: Python, elements: I) -> PyTuple
+    where
+        I: IntoIterator<Item = T>,
+        T: ToPyObject,
+    {
+        let elements: Vec<PyObject> = elements
+            .into_iter()
+            .map(|e| e.into_py_object(py).into_object())
+            .collect();
+        unsafe {
+            let ptr = ffi::PyTuple_New(elements.len() as Py_ssize_t);
+            let t = err::result_cast_from_owned_ptr::<PyTuple>(py, ptr).unwrap();
+            for (i, e) in elements.into_iter().enumerate() {
+                ffi::PyTuple_SetItem(ptr, i as Py_ssize_t, e.steal_ptr());
+            }
+            t
+        }
+    }
+
     /// Retrieves the empty tuple.
     pub fn empty(py: Python) -> PyTuple {
         unsafe { err::result_cast_from_owned_ptr::<PyTuple>(py, ffi::PyTuple_New(0)).unwrap() }
@@ -60,16 +94,29 @@ impl PyTuple {
 
     /// Gets the item at the specified index.
     ///
-    /// Panics if the index is out of range.
-    pub fn get_item(&self, py: Python, index: usize) -> PyObject {
-        // TODO: reconsider whether we should panic
-        // It's quite inconsistent that this method takes `Python` when `len()` does not.
-        assert!(index < self.len(py));
+    /// Negative indices count from the end of the tuple, as in Python.
+    /// Returns `IndexError` if the index is out of range.
+    pub fn get_item(&self, py: Python, index: isize) -> PyResult<PyObject> {
+        match self.try_get_item(py, index) {
+            Some(item) => Ok(item),
+            None => Err(PyErr::new::<exc::IndexError, _>(
+                py,
+                "tuple index out of range",
+            )),
+        }
+    }
+
+    /// Gets the item at the specified index,
+    /// or `None` if the index is out of range.
+    ///
+    /// Negative indices count from the end of the tuple, as in Python.
+    pub fn try_get_item(&self, py: Python, index: isize) -> Option<PyObject> {
+        let index = normalize_index(index, self.len(py))?;
         unsafe {
-            PyObject::from_borrowed_ptr(
+            Some(PyObject::from_borrowed_ptr(
                 py,
                 ffi::PyTuple_GET_ITEM(self.0.as_ptr(), index as Py_ssize_t),
-            )
+            ))
         }
     }
 
@@ -237,6 +284,7 @@ extract!(obj to NoArgs;
 #[cfg(test)]
 mod test {
     use crate::conversion::ToPyObject;
+    use crate::objects::exc;
     use crate::python::{Python, PythonObject};
 
     #[test]
@@ -247,4 +295,44 @@ mod test {
         assert_eq!(3, tuple.len(py));
         assert_eq!((1, 2, 3), tuple.into_object().extract(py).unwrap());
     }
+
+    #[test]
+    fn test_from_iter() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let tuple = super::PyTuple::from_iter(py, (1..4).map(|i| i.to_string()));
+        assert_eq!(tuple.len(py), 3);
+        assert_eq!(
+            tuple
+                .as_object()
+                .extract::<(String, String, String)>(py)
+                .unwrap(),
+            ("1".to_owned(), "2".to_owned(), "3".to_owned())
+        );
+        let empty = super::PyTuple::from_iter(py, Vec::<i32>::new());
+        assert_eq!(empty.len(py), 0);
+    }
+
+    #[test]
+    fn test_get_item() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let tuple = (1, 2, 3).to_py_object(py);
+        assert_eq!(
+            1,
+            tuple.get_item(py, 0).unwrap().extract::<i32>(py).unwrap()
+        );
+        assert_eq!(
+            3,
+            tuple.get_item(py, -1).unwrap().extract::<i32>(py).unwrap()
+        );
+        assert_eq!(
+            1,
+            tuple.get_item(py, -3).unwrap().extract::<i32>(py).unwrap()
+        );
+        let err = tuple.get_item(py, 3).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::IndexError>()));
+        assert!(tuple.try_get_item(py, -4).is_none());
+        assert!(tuple.try_get_item(py, 2).is_some());
+    }
 }
@@ -18,10 +18,12 @@
 
 use std::borrow::Cow;
 use std::ffi::CStr;
+use std::ptr;
 
 use crate::conversion::ToPyObject;
 use crate::err::{result_from_owned_ptr, PyResult};
 use crate::ffi;
+use crate::objectprotocol::ObjectProtocol;
 use crate::objects::{PyDict, PyObject, PyTuple};
 use crate::python::{Python, PythonObject, ToPythonPointer};
 
@@ -62,6 +64,78 @@ impl PyType {
         unsafe { ffi::PyObject_TypeCheck(obj.as_ptr(), self.as_type_ptr()) != 0 }
     }
 
+    /// Creates a new class at runtime.
+    /// This is equivalent to the Python expression: `type(name, bases, namespace)`
+    ///
+    /// As with the Python expression, the metaclass of the new class is derived from `bases`:
+    /// if one of the bases uses a custom metaclass (e.g. `abc.ABCMeta`), that metaclass
+    /// is used to create the class.
+    /// `namespace` is copied into the new class; an empty `bases` tuple
+    /// creates a subclass of `object`.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{NoArgs, PyDict, PyTuple, PyType, Python};
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let namespace = PyDict::new(py);
+    /// namespace.set_item(py, "answer", 42).unwrap();
+    /// let cls = PyType::create(py, "Example", &PyTuple::empty(py), &namespace).unwrap();
+    /// let obj = cls.call(py, NoArgs, None).unwrap();
+    /// assert!(cls.is_instance(py, &obj));
+    /// ```
+    pub fn create(py: Python, name: &str, bases: &PyTuple, namespace: &PyDict) -> PyResult<PyType> {
+        let metaclass = py.get_type::<PyType>();
+        Ok(metaclass
+            .call(py, (name, bases, namespace), None)?
+            .cast_into(py)?)
+    }
+
+    /// Creates a new class whose metaclass is `metaclass`, the way `type` itself does.
+    /// This is equivalent to the Python expression: `type.__new__(metaclass, name, bases, namespace)`
+    ///
+    /// This is how the `__new__` method of a metaclass defined with `py_class!` creates
+    /// the class object. (CPython refuses to call `type.__new__` from Python code with
+    /// such a metaclass, because its `__new__` is not inherited from `type`.)
+    /// As with `type.__new__`, `metaclass` must be a subclass of `type`, and the class
+    /// is initialized by the metaclass's `__init__` only when created by calling `metaclass`.
+    pub fn create_with_metaclass(
+        py: Python,
+        metaclass: &PyType,
+        name: &str,
+        bases: &PyTuple,
+        namespace: &PyDict,
+    ) -> PyResult<PyType> {
+        let args = (name, bases, namespace).to_py_object(py);
+        unsafe {
+            let type_new = ffi::PyType_Type.tp_new.expect("type has no tp_new");
+            Ok(result_from_owned_ptr(
+                py,
+                type_new(
+                    metaclass.as_type_ptr(),
+                    args.as_object().as_ptr(),
+                    ptr::null_mut(),
+                ),
+            )?
+            .cast_into(py)?)
+        }
+    }
+
+    /// Registers `subclass` as a virtual subclass of the abstract base class `self`.
+    /// This is equivalent to the Python expression `self.register(subclass)`.
+    ///
+    /// Afterwards, the Python expressions `isinstance(x, self)` and `issubclass(subclass, self)`
+    /// are true for instances of `subclass`, e.g. a `py_class!` type that implements the
+    /// protocol described by the ABC. Note that `is_instance()` and `is_subtype_of()`
+    /// only consider real base classes, not virtual subclasses.
+    ///
+    /// Fails with `AttributeError` if `self` is not an abstract base class
+    /// (i.e. its metaclass is not `abc.ABCMeta`).
+    pub fn register_virtual_subclass(&self, py: Python, subclass: &PyType) -> PyResult<()> {
+        self.0.call_method(py, "register", (subclass,), None)?;
+        Ok(())
+    }
+
     /// Calls the type object, thus creating a new instance.
     /// This is equivalent to the Python expression: `self(*args, **kwargs)`
     #[inline]
@@ -85,3 +159,32 @@ impl PartialEq for PyType {
     }
 }
 impl Eq for PyType {}
+
+#[cfg(test)]
+mod test {
+    use crate::conversion::ToPyObject;
+    use crate::objectprotocol::ObjectProtocol;
+    use crate::objects::{PyDict, PyTuple, PyType};
+    use crate::python::{PyClone, Python, PythonObject};
+
+    #[test]
+    fn test_create() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let base = py
+            .eval("type('Base', (object,), {'x': 1})", None, None)
+            .unwrap();
+        let bases = PyTuple::new(py, &[base.clone_ref(py)]);
+        let namespace = PyDict::new(py);
+        namespace.set_item(py, "y", 2).unwrap();
+        let cls = PyType::create(py, "Derived", &bases, &namespace).unwrap();
+        assert_eq!(cls.name(py), "Derived");
+        assert!(cls.is_subtype_of(py, &base.cast_into::<PyType>(py).unwrap()));
+
+        let obj = cls.call(py, crate::NoArgs, None).unwrap();
+        assert!(cls.is_instance(py, &obj));
+        assert_eq!(obj.getattr(py, "x").unwrap().extract::<i32>(py).unwrap(), 1);
+        assert_eq!(obj.getattr(py, "y").unwrap().extract::<i32>(py).unwrap(), 2);
+        assert!(PyType::create(py, "Bad", &(1,).to_py_object(py), &namespace).is_err());
+    }
+}
@@ -91,6 +91,9 @@ macro_rules! py_class_instance_method {
             $crate::_detail::handle_callback(
                 LOCATION, $crate::_detail::PyObjectCallbackConverter,
                 |py| {
+                    // Default value expressions may refer to the `py` token of the class.
+                    #[allow(unused_variables)]
+                    let $py = py;
                     $crate::py_argparse_raw!(py, Some(LOCATION), args, kwargs,
                         [ $( { $pname : $ptype = $detail } )* ]
                         {
@@ -145,6 +148,9 @@ macro_rules! py_class_class_method {
             $crate::_detail::handle_callback(
                 LOCATION, $crate::_detail::PyObjectCallbackConverter,
                 |py| {
+                    // Default value expressions may refer to the `py` token of the class.
+                    #[allow(unused_variables)]
+                    let $py = py;
                     $crate::py_argparse_raw!(py, Some(LOCATION), args, kwargs,
                         [ $( { $pname : $ptype = $detail } )* ]
                         {
@@ -202,6 +208,9 @@ macro_rules! py_class_static_method {
             $crate::_detail::handle_callback(
                 LOCATION, $crate::_detail::PyObjectCallbackConverter,
                 |py| {
+                    // Default value expressions may refer to the `py` token of the class.
+                    #[allow(unused_variables)]
+                    let $py = py;
                     $crate::py_argparse_raw!(py, Some(LOCATION), args, kwargs,
                         [ $( { $pname : $ptype = $detail } )* ]
                         {
@@ -35,12 +35,14 @@ mod py_class_impl3;
 #[doc(hidden)]
 pub mod slots;
 
-use std::{cell, mem, ptr};
+use std::cell::{Cell, RefCell};
+use std::{cmp, mem, ptr, slice};
 
 use crate::err::{self, PyResult};
 use crate::ffi;
 use crate::objects::{PyModule, PyObject, PyType};
 use crate::python::{self, Python, PythonObject};
+use crate::pythonrun::GILProtected;
 
 // TODO: consider moving CompareOp to a different module, so that it isn't exported via two paths
 #[derive(Debug)]
@@ -104,6 +106,178 @@ pub unsafe fn data_drop<T>(_py: Python<'_>, obj: *mut ffi::PyObject, offset: usi
     ptr::drop_in_place(ptr)
 }
 
+type Cleanup = Box<dyn FnOnce(Python) + Send>;
+
+struct DeallocState {
+    /// Number of `py_class!` instances currently being deallocated.
+    depth: Cell<usize>,
+    cleanups: RefCell<Vec<Cleanup>>,
+}
+
+static DEALLOC_STATE: GILProtected<DeallocState> = GILProtected::new(DeallocState {
+    depth: Cell::new(0),
+    cleanups: RefCell::new(Vec::new()),
+});
+
+/// Runs `f` once no `py_class!` instance is being deallocated anymore.
+///
+/// The `Drop` implementations of `py_class!` data members run in the middle of `tp_dealloc`,
+/// while the instance is half-destroyed. Running Python code at that point is hazardous:
+/// it may re-enter the dying object (e.g. through a reference cycle that is being torn down),
+/// or run user-defined Python code that observes partially dropped data.
+/// `defer_cleanup` lets a `Drop` implementation schedule such work instead: `f` is queued,
+/// and runs right after the outermost deallocation in progress completes.
+/// If no deallocation is in progress, `f` runs immediately.
+///
+/// Queued cleanups run in the order they were deferred, with the GIL held.
+/// Any Python exception that was set when the deallocation started is preserved.
+/// A cleanup that is deferred by another cleanup runs after it.
+/// Because cleanups run as part of `tp_dealloc`, a panic in `f` aborts the process.
+///
+/// `Drop` does not receive a `Python` token; since `tp_dealloc` always runs with the GIL held,
+/// `Python::acquire_gil()` can be used to obtain one cheaply.
+///
+/// # Example
+/// ```
+/// use cpython::{py_class, NoArgs, ObjectProtocol, PyObject, Python};
+/// use cpython::py_class::defer_cleanup;
+///
+/// struct Connection {
+///     on_close: Option<PyObject>,
+/// }
+///
+/// impl Drop for Connection {
+///     fn drop(&mut self) {
+///         if let Some(callback) = self.on_close.take() {
+///             let gil = Python::acquire_gil();
+///             defer_cleanup(gil.python(), move |py| {
+///                 // Errors can't be propagated from here; report them instead.
+///                 if let Err(e) = callback.call(py, NoArgs, None) {
+///                     e.print(py);
+///                 }
+///             });
+///         }
+///     }
+/// }
+///
+/// py_class!(class Client |py| {
+///     data connection: Connection;
+/// });
+/// # fn main() {}
+/// ```
+pub fn defer_cleanup<F>(py: Python, f: F)
+where
+    F: FnOnce(Python) + Send + 'static,
+{
+    let state = DEALLOC_STATE.get(py);
+    if state.depth.get() == 0 {
+        f(py);
+    } else {
+        state.cleanups.borrow_mut().push(Box::new(f));
+    }
+}
+
+pub(crate) fn enter_dealloc(py: Python) {
+    let depth = &DEALLOC_STATE.get(py).depth;
+    depth.set(depth.get() + 1);
+}
+
+pub(crate) fn leave_dealloc(py: Python) {
+    let state = DEALLOC_STATE.get(py);
+    state.depth.set(state.depth.get() - 1);
+    if state.depth.get() != 0 {
+        return;
+    }
+    loop {
+        let cleanups = mem::take(&mut *state.cleanups.borrow_mut());
+        if cleanups.is_empty() {
+            return;
+        }
+        // Deallocation may happen while an exception is being propagated;
+        // the cleanups must not clobber it.
+        let mut ptype = ptr::null_mut();
+        let mut pvalue = ptr::null_mut();
+        let mut ptraceback = ptr::null_mut();
+        unsafe { ffi::PyErr_Fetch(&mut ptype, &mut pvalue, &mut ptraceback) };
+        for f in cleanups {
+            f(py);
+        }
+        unsafe { ffi::PyErr_Restore(ptype, pvalue, ptraceback) };
+    }
+}
+
+/// Trait implemented by the types produced by the `py_class!()` macro,
+/// describing their variable-size data.
+///
+/// This is an unstable implementation detail; do not implement manually!
+#[doc(hidden)]
+pub trait VarSizedObject {
+    /// The size of each item (`tp_itemsize`), or 0 if the class has no variable-size data.
+    fn item_size() -> usize;
+}
+
+/// Size of the object header: variable-size objects store their item count in `ob_size`.
+#[inline]
+#[doc(hidden)]
+pub fn header_size<T: VarSizedObject>() -> usize {
+    if T::item_size() == 0 {
+        mem::size_of::<ffi::PyObject>()
+    } else {
+        mem::size_of::<ffi::PyVarObject>()
+    }
+}
+
+#[inline]
+#[doc(hidden)]
+pub fn var_item_size<T>() -> usize {
+    // tp_itemsize must be non-zero even for zero-sized items,
+    // otherwise Python doesn't record the item count.
+    cmp::max(mem::size_of::<T>(), 1)
+}
+
+#[doc(hidden)]
+pub unsafe fn var_data_alloc(py: Python, ty: &PyType, len: usize) -> PyResult<PyObject> {
+    let ptr = type_alloc(ty.as_type_ptr(), len as ffi::Py_ssize_t);
+    err::result_from_owned_ptr(py, ptr)
+}
+
+/// Allocates an instance of `ty` through its `tp_alloc` slot.
+/// `py_class!` types inherit `PyType_GenericAlloc` from `object`,
+/// and subclasses defined in Python inherit it in turn.
+unsafe fn type_alloc(ty: *mut ffi::PyTypeObject, nitems: ffi::Py_ssize_t) -> *mut ffi::PyObject {
+    match (*ty).tp_alloc {
+        Some(alloc) => alloc(ty, nitems),
+        None => ffi::PyType_GenericAlloc(ty, nitems),
+    }
+}
+
+#[doc(hidden)]
+pub unsafe fn var_data_init<T>(_py: Python, obj: &PyObject, offset: usize, items: Vec<T>)
+where
+    T: Send + 'static,
+{
+    let ptr = (obj.as_ptr() as *mut u8).add(offset) as *mut T;
+    debug_assert_eq!(ffi::Py_SIZE(obj.as_ptr()) as usize, items.len());
+    for (i, item) in items.into_iter().enumerate() {
+        ptr::write(ptr.add(i), item);
+    }
+}
+
+#[inline]
+#[doc(hidden)]
+pub unsafe fn var_data_get<'a, T>(_py: Python<'a>, obj: &'a PyObject, offset: usize) -> &'a [T] {
+    let ptr = (obj.as_ptr() as *const u8).add(offset) as *const T;
+    slice::from_raw_parts(ptr, ffi::Py_SIZE(obj.as_ptr()) as usize)
+}
+
+#[inline]
+#[doc(hidden)]
+pub unsafe fn var_data_drop<T>(_py: Python<'_>, obj: *mut ffi::PyObject, offset: usize) {
+    let ptr = (obj as *mut u8).add(offset) as *mut T;
+    let items = slice::from_raw_parts_mut(ptr, ffi::Py_SIZE(obj) as usize);
+    ptr::drop_in_place(items as *mut [T])
+}
+
 #[inline]
 #[doc(hidden)]
 pub fn is_ready(_py: Python, ty: &ffi::PyTypeObject) -> bool {
@@ -138,7 +312,14 @@ impl BaseObject for PyObject {
     type InitType = ();
 
     unsafe fn alloc(py: Python, ty: &PyType, _init_val: ()) -> PyResult<PyObject> {
-        let ptr = ffi::PyType_GenericAlloc(ty.as_type_ptr(), 0);
+        // Instances of a metaclass are type objects; only `type.__new__` can set those up.
+        if ffi::PyType_HasFeature(ty.as_type_ptr(), ffi::Py_TPFLAGS_TYPE_SUBCLASS) != 0 {
+            return Err(err::PyErr::new::<crate::exc::TypeError, _>(
+                py,
+                "instances of a metaclass must be created with type.__new__",
+            ));
+        }
+        let ptr = type_alloc(ty.as_type_ptr(), 0);
         //println!("BaseObject::alloc({:?}) = {:?}", ty.as_type_ptr(), ptr);
         err::result_from_owned_ptr(py, ptr)
     }
@@ -153,10 +334,8 @@ impl BaseObject for PyObject {
         } else {
             ffi::PyObject_Free(obj as *mut libc::c_void);
         }
-        // For heap types, PyType_GenericAlloc calls INCREF on the type objects,
-        // so we need to call DECREF here:
-        if ffi::PyType_HasFeature(ty, ffi::Py_TPFLAGS_HEAPTYPE) != 0 {
-            ffi::Py_DECREF(ty as *mut ffi::PyObject);
-        }
+        // For heap types, PyType_GenericAlloc calls INCREF on the type object.
+        // `py_class!` types are static, so a heap type here is a subclass defined in Python,
+        // whose `subtype_dealloc` calls DECREF after this function returns.
     }
 }
@@ -33,6 +33,28 @@ would also be valid.
 in all function bodies.
 * `{ ... }` is the class body, described in more detail below.
 
+Doc comments (`///`) before `class` become the Python class docstring (`__doc__`, used by `help()`).
+Doc comments on instance methods, class methods, static methods and properties
+become the docstrings of those members. Special methods (e.g. `__len__`) cannot have doc comments.
+The docstrings are stored as static strings; no allocation happens at runtime.
+
+```
+use cpython::{PyResult, py_class};
+
+py_class!(
+    /// A counter that starts at zero.
+    pub class Counter |py| {
+        data value: i32;
+
+        /// Returns the current count.
+        def get(&self) -> PyResult<i32> {
+            Ok(*self.value(py))
+        }
+    }
+);
+# fn main() {}
+```
+
 # Example
 ```
 use cpython::{Python, PyResult, PyDict, py_class};
@@ -72,6 +94,7 @@ impl PythonObjectFromPyClassMacro for MyType { ... }
 
 impl MyType {
     fn create_instance(py: Python, number: i32) -> PyResult<MyType> { ... }
+    fn create_instance_of(py: Python, cls: &PyType, number: i32) -> PyResult<MyType> { ... }
 
     // data accessors
     fn number<'a>(&'a self, py: Python<'a>) -> &'a i32 { ... }
@@ -92,10 +115,17 @@ impl MyType {
 * The inherent `create_instance` method can create new Python objects
   given the values for the data fields.
     - Note: Any visibility keyword on the class will also be used for this method.
+* The inherent `create_instance_of` method does the same, but takes the type object to
+  instantiate as an additional first argument (after `py`). This must be `MyType` or a subclass
+  of it defined in Python; otherwise `TypeError` is raised. See `__new__` below.
 * Private accessors functions are created for the data fields.
 * All functions callable from Python are also exposed as public Rust functions.
 * To convert from `MyType` to `PyObject`, use `as_object()` or `into_object()` (from the `PythonObject` trait).
 * To convert `PyObject` to `MyType`, use `obj.cast_as::<MyType>(py)` or `obj.cast_into::<MyType>(py)`.
+  The check compares the object's type (`ob_type`, not the `__class__` attribute) against
+  the type object of `MyType`, like `PyObject_TypeCheck`, so it also succeeds for instances
+  of subclasses defined in Python.
+  On failure, a `PythonObjectDowncastError` is returned, which converts to a Python `TypeError`.
 
 # py_class body
 The body of a `py_class!` supports the following definitions:
@@ -140,6 +170,208 @@ impl MyType {
 
 [PySharedRefCell]: struct.PySharedRefCell.html
 
+## Attribute data declarations
+`@property data data_name: data_type;`
+
+`@property data data_name: data_type, setter set_method_name;`
+
+Declares a data field that is exposed to Python as an instance attribute,
+similar to an entry in a Python class's `__slots__`.
+The value is stored directly in the Python object instance; like all `py_class!` instances,
+the object has no `__dict__`.
+
+`data_type` must implement `Clone`, `ToPyObject` and `FromPyObject`.
+Reading the attribute returns a clone of the stored value.
+With `setter`, the attribute can also be assigned from Python; assigning a value that
+cannot be converted to `data_type` raises the `FromPyObject` error (usually `TypeError`).
+Deleting the attribute raises `AttributeError`.
+Without `setter`, the attribute is read-only.
+
+On the Rust side, the getter and setter are available as methods:
+```ignore
+impl MyType {
+    pub fn data_name(&self, py: Python) -> PyResult<data_type> { ... }
+    pub fn set_method_name(&self, py: Python, value: Option<data_type>) -> PyResult<()> { ... }
+}
+```
+
+## Variable-size data declarations
+`data data_name: [item_type];`
+
+Declares a variable-size array of items that is stored directly in the Python object instance,
+after all other data fields (using `tp_itemsize`, like the items of a Python `tuple`).
+This avoids a separate heap allocation for the payload.
+A class can have at most one variable-size data declaration.
+
+The number of items is chosen when the instance is created: `create_instance()` takes
+an additional last parameter of type `Vec<item_type>`, whose items are moved into the new instance.
+The number of items cannot change afterwards.
+Like other data fields, `item_type` must be `Send + 'static`, and mutating the items
+requires interior mutability (e.g. `[Cell<u8>]`).
+
+On the Rust side, the items are accessed through the automatically generated accessor function:
+```ignore
+impl MyType {
+    fn data_name<'a>(&'a self, py: Python<'a>) -> &'a [item_type] { ... }
+}
+```
+
+Example:
+```
+use std::cell::Cell;
+use cpython::{py_class, PyResult};
+
+py_class!(class FixedBuffer |py| {
+    data len: Cell<usize>;
+    data storage: [Cell<u8>];
+
+    def __new__(_cls, capacity: usize) -> PyResult<FixedBuffer> {
+        FixedBuffer::create_instance(py, Cell::new(0), vec![Cell::new(0); capacity])
+    }
+
+    def push(&self, byte: u8) -> PyResult<bool> {
+        let len = self.len(py).get();
+        match self.storage(py).get(len) {
+            Some(slot) => {
+                slot.set(byte);
+                self.len(py).set(len + 1);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+});
+# fn main() {}
+```
+
+## Base type
+`@base base_expr;`
+
+By default, classes created by `py_class!` derive from `object`.
+A `@base` declaration specifies a different base class, for example another
+`py_class!` type whose methods should be shared by several classes.
+
+`base_expr` must evaluate to a `PyType`. It is evaluated once, when the class is initialized;
+the `py` token is in scope, and errors can be propagated using `?`
+(causing the class initialization to fail).
+
+The following constraints apply to the base type; otherwise class initialization fails with `TypeError`:
+
+  * The base type must allow subclassing; a `py_class!` base type must be declared `@subclassable`.
+  * The base type must be a static type, i.e. implemented in Rust or C.
+    Classes defined in Python (including the abstract base classes in `collections.abc`)
+    cannot be used; to make a class pass `isinstance()` checks against an abstract base class,
+    register it as a virtual subclass instead.
+  * Instances of the base type must not store any data beyond the `PyObject` header.
+    In particular, the base must not be a variable-size type such as `tuple` or `int`,
+    and a `py_class!` base type must not declare `data` members.
+
+The `__new__` and `__init__` methods of the base type are not called by `create_instance()`.
+Unless the class declares its own `__new__`, it cannot be instantiated from Python code.
+Other methods of the base type are inherited as usual.
+
+Example:
+```
+use cpython::{py_class, NoArgs, ObjectProtocol, PyResult, PythonObject};
+
+py_class!(class Shape |py| {
+    @subclassable;
+    def describe(&self) -> PyResult<String> {
+        let area = self.as_object().call_method(py, "area", NoArgs, None)?;
+        Ok(format!("a shape with area {}", area))
+    }
+});
+
+py_class!(class Square |py| {
+    @base py.get_type::<Shape>();
+    data side: f64;
+
+    def area(&self) -> PyResult<f64> {
+        Ok(self.side(py) * self.side(py))
+    }
+});
+# fn main() {}
+```
+
+## Metaclasses
+`@metaclass;`
+
+A `@metaclass` declaration makes the class a subclass of `type`, so that it can be used
+as the metaclass of other classes (`class Foo(metaclass=Registry): ...` in Python 3,
+or by calling it like `type(name, bases, namespace)`).
+Instances of the class are type objects, which are laid out, initialized and deallocated
+by `type` itself. `@metaclass` cannot be combined with `@base`, and class initialization
+fails with `TypeError` if the class declares `data` members, `__traverse__` or `__clear__`.
+
+Unless the class declares `__new__` or `__init__`, those of `type` are inherited and
+receive the usual class creation arguments. A custom `__new__` must create the class
+object with `PyType::create_with_metaclass()`, which does the work of `type.__new__`.
+`create_instance()` and `create_instance_of()` always fail with `TypeError`.
+
+Methods declared in the class become methods of the classes it creates:
+`self.as_object()` is the class object, which can be cast to `PyType`.
+
+Example:
+```
+use cpython::{py_class, PyDict, PyResult, PyTuple, PyType};
+
+py_class!(class Registry |py| {
+    @metaclass;
+
+    def __new__(mcs, name: String, bases: PyTuple, namespace: PyDict) -> PyResult<PyType> {
+        let class = PyType::create_with_metaclass(py, mcs, &name, &bases, &namespace)?;
+        // ... record `class` somewhere ...
+        Ok(class)
+    }
+});
+# fn main() {}
+```
+
+## Subclassing
+`@subclassable;`
+
+By default, classes created by `py_class!` cannot be subclassed: a Python class statement
+deriving from them, or a `@base` declaration naming them, fails with `TypeError`.
+A `@subclassable` declaration allows subclassing (it sets `Py_TPFLAGS_BASETYPE`).
+
+Instances of a subclass pass `cast_as::<MyType>()`, so methods written for `MyType`
+must not assume that `self` is an instance of exactly `MyType`.
+A `__new__` method that should construct instances of subclasses
+must create the object with `create_instance_of(py, cls, ...)` (see `__new__` below).
+
+## Type name
+`@module "module_name";`
+`@qualname "QualifiedName";`
+
+By default, the type's `__name__` and `__qualname__` are the name of the Rust struct,
+and its `__module__` is the name of the module the class was added to
+(or `builtins` if the class was not added to any module).
+For types defined in Rust, Python derives `__module__` and `__qualname__` from the type's
+`tp_name` field, which is set to `module_name.QualifiedName`.
+
+An `@module` declaration overrides the module name, for example to name the module
+a class is re-exported from, or to give a correct `__module__` to a class that is
+only created through `create_instance()` and never added to a module.
+An `@qualname` declaration overrides the name; `add_class()` then registers the class
+in the module under that name. The qualified name must not contain a dot.
+Both expressions must have type `&'static str`.
+
+Correct names are needed for pickling instances (`pickle` looks up the class
+as `getattr(sys.modules[cls.__module__], cls.__qualname__)`) and make `repr()`
+and tracebacks easier to read.
+
+Example:
+```
+use cpython::py_class;
+
+py_class!(class RustPoint |py| {
+    @module "geometry.shapes";
+    @qualname "Point";
+    data x: i32;
+});
+# fn main() {}
+```
+
 ## Instance methods
 `def method_name(&self, parameter-list) -> PyResult<...> { ... }`
 `pub(crate) def method_name(&self, parameter-list) -> PyResult<...> { ... }`
@@ -216,9 +448,102 @@ Declares a constructor method callable from Python.
   This may be the type object of a derived class declared in Python.
 * The first parameter implicitly has type `&PyType`. This type must not be explicitly specified.
 * For details on `parameter-list`, see the documentation of `py_argparse!()`.
+* Default values are evaluated on every call that omits the argument, not once when the
+  class is created. They may use the `py` token, e.g. `def __new__(cls, items: PyList = PyList::new(py, &[]))`
+  creates a fresh list for each instance. This applies to the parameters of all methods.
 * The return type must be `PyResult<T>` for some `T` that implements `ToPyObject`.
   Usually, `T` will be `MyType`.
 
+Classes declared `@subclassable` can be subclassed in Python.
+When such a subclass is instantiated, `cls` is the subclass, and `__new__` must pass it on to
+`create_instance_of()`, which allocates the object through `cls`, so that the new object
+is an instance of the subclass (with its `__dict__` and its methods);
+`create_instance()` always creates an instance of `MyType`.
+As usual in Python, `__init__` is only called if `__new__` returns an instance of `cls`.
+
+```
+use cpython::{Python, PyResult, PyDict, py_class};
+
+py_class!(class Point |py| {
+    @subclassable;
+    data x: i32;
+    def __new__(cls, x: i32) -> PyResult<Point> {
+        Point::create_instance_of(py, cls, x)
+    }
+    def get_x(&self) -> PyResult<i32> {
+        Ok(*self.x(py))
+    }
+});
+
+fn main() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let dict = PyDict::new(py);
+    dict.set_item(py, "Point", py.get_type::<Point>()).unwrap();
+    py.run("
+class Labeled(Point):
+    def __init__(self, x):
+        self.label = 'p%d' % x
+
+p = Labeled(3)
+assert type(p) is Labeled and p.get_x() == 3 and p.label == 'p3'
+", None, Some(&dict)).unwrap();
+}
+```
+
+## __init__
+`def __init__(&self, parameter-list) -> PyResult<()> { ... }`
+
+Declares an initializer, which is stored in the `tp_init` slot.
+
+When the class (or a subclass) is called from Python, `__new__` is called first to create the
+instance, then `__init__` is called on it with the same arguments.
+`__new__` therefore has to accept all arguments of `__init__` (or use `*args, **kwargs`),
+and `__init__` is only called if `__new__` returns an instance of `cls`.
+Subclasses defined in Python that override `__init__` can call `super().__init__(...)`.
+
+* All data fields are initialized by `create_instance()` or `create_instance_of()` in `__new__`.
+  `__init__` can only modify them through interior mutability (e.g. `Cell` or `RefCell`),
+  and may be called again on an existing instance.
+* `__init__` is not called by `create_instance()`; it is only called when the class is called from Python.
+* Without `__new__`, the class cannot be instantiated from Python, so `__init__` is not useful on its own.
+* For details on `parameter-list`, see the documentation of `py_argparse!()`.
+
+```
+use std::cell::Cell;
+use cpython::{Python, PyResult, PyDict, py_class};
+
+py_class!(class Counter |py| {
+    @subclassable;
+    data count: Cell<i32>;
+    def __new__(cls, *args, **kwargs) -> PyResult<Counter> {
+        Counter::create_instance_of(py, cls, Cell::new(0))
+    }
+    def __init__(&self, start: i32) -> PyResult<()> {
+        self.count(py).set(start);
+        Ok(())
+    }
+    def get(&self) -> PyResult<i32> {
+        Ok(self.count(py).get())
+    }
+});
+
+fn main() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let dict = PyDict::new(py);
+    dict.set_item(py, "Counter", py.get_type::<Counter>()).unwrap();
+    py.run("
+class FromTen(Counter):
+    def __init__(self):
+        super(FromTen, self).__init__(10)
+
+assert Counter(3).get() == 3
+assert FromTen().get() == 10
+", Some(&dict), None).unwrap();
+}
+```
+
 ## Garbage Collector Integration
 
 If your type owns references to other python objects, you will need to
@@ -276,6 +601,35 @@ statement, after the mutable borrow on the `RefCell` has expired.
 Note that this restriction applies not only to `__clear__`, but to all methods
 that use `RefCell::borrow_mut`.
 
+## Finalization
+
+  * `def __del__(&self) -> PyResult<()>`
+
+    Called when the instance is about to be destroyed, using the `tp_finalize` slot (PEP 442).
+    Only supported in Python 3.
+
+The `Drop` implementations of the data members run when the memory of the instance
+is released (in `tp_dealloc`). At that point the instance is no longer a valid Python object,
+so `Drop` should only release resources and must not run arbitrary Python code.
+Python-level cleanup that is triggered by `Drop` can be scheduled using
+[defer_cleanup](py_class/fn.defer_cleanup.html), which runs it after the deallocation completes.
+`__del__` instead runs while the instance is still intact, and may use `self` and
+call into Python like any other method:
+
+  * When the reference count drops to zero, `__del__` runs first,
+    followed by the `Drop` implementations.
+  * When the garbage collector finds an unreachable cycle (which requires `__traverse__`),
+    it calls `__del__` on all instances in the cycle before calling any `__clear__`.
+
+If `__del__` stores a new reference to `self` somewhere, the instance is resurrected:
+it is not deallocated, and the `Drop` implementations run later, when the
+instance becomes unreachable again.
+For types with `__traverse__`, `__del__` is called at most once per instance;
+otherwise it runs again each time a resurrected instance is about to be destroyed.
+
+Exceptions raised by `__del__` cannot be propagated to the caller;
+they are reported using `sys.unraisablehook` (printed to `stderr` before Python 3.8).
+
 ## Iterator Types
 
 Iterators can be defined using the Python special methods `__iter__` and `__next__`:
@@ -305,6 +659,11 @@ py_class!(class MyIterator |py| {
 # fn main() {}
 ```
 
+To return a lazy iterator from a regular method instead of defining an iterator class,
+use [py_generator()](fn.py_generator.html) for a Rust iterator that owns its data,
+or [py_generator_with_state()](fn.py_generator_with_state.html) with `self.clone_ref(py)`
+as state to iterate over the instance's own data.
+
 ## String Conversions
 
   * `def __repr__(&self) -> PyResult<impl ToPyObject<ObjectType=PyString>>`
@@ -315,6 +674,14 @@ py_class!(class MyIterator |py| {
     In Python 2.7, Unicode strings returned by `__str__` and `__repr__` will be converted to byte strings
     by the Python runtime, which results in an exception if the string contains non-ASCII characters.
 
+    Like all methods, `__str__` and `__repr__` receive `self` by shared reference.
+    `py_class!` does not wrap the instance data in a `RefCell`, so any number of
+    data accessors can be used together, and calling back into Python
+    (e.g. `repr()` of a contained object, which may end up in `__repr__` of the same instance)
+    cannot cause a borrow panic by itself.
+    If a data field uses a `RefCell`, prefer `borrow()` over `borrow_mut()` here,
+    or clone the contained `PyObject` and release the borrow before calling back into Python.
+
   * `def __bytes__(&self) -> PyResult<PyBytes>`
 
     On Python 3.x, provides the conversion to `bytes`.
@@ -346,18 +713,37 @@ py_class!(class MyIterator |py| {
     Objects that compare equal must have the same hash value.
     The return type must be `PyResult<T>` where `T` is one of Rust's primitive integer types.
 
+  * `@unhashable;`
+
+    Marks the type as unhashable, like `__hash__ = None` in a Python class body:
+    `hash(obj)` raises `TypeError`, the type's `__hash__` attribute is `None`,
+    and `isinstance(obj, collections.abc.Hashable)` is false.
+    Use this for mutable types, whose value (and thus hash) can change.
+    It cannot be combined with `def __hash__`.
+
+    A type that defines `__richcmp__` but not `__hash__` is already unhashable,
+    as Python does not inherit `object.__hash__` when comparisons are overridden.
+    Without `__richcmp__`, instances are hashable by identity unless `@unhashable` is used.
+
 ## Emulating Container Types
 
   * `def __len__(&self) -> PyResult<usize>`
 
     Called by the built-in Python function `len()`.
 
-  * `def __length_hint__(&self) -> PyResult<usize>`
+    If the class does not define `__bool__`, `__len__` is also used to determine
+    the truth value of the object: the object is false if the length is zero.
+
+  * `def __length_hint__(&self) -> PyResult<impl ToPyObject>`
 
-    Should return an estimated length for the object.
+    Should return an estimated length for the object, usually `usize`.
     This method is purely an optimization and is never required for correctness.
+    It is defined as a regular method, which Python looks up to presize collections
+    (e.g. in `list(obj)`), as do `operator.length_hint()` (Python 3.4 and later)
+    and `ObjectProtocol::length_hint()`.
 
-    `__length_hint__` is new in Python 3.4; older versions will ignore the method.
+    If no estimate is available, return `Ok(py.NotImplemented())`;
+    the caller then uses its default value.
 
   * `def __getitem__(&self, key: impl FromPyObject) -> PyResult<impl ToPyObject>`
 
@@ -376,6 +762,30 @@ py_class!(class MyIterator |py| {
     Called by the `reversed()` built-in.
     It should return a new iterator object that iterates over all the objects in the container in reverse order.
 
+    `__reversed__` is a regular method, not a type slot. If it is not defined, `reversed()`
+    falls back to using `__len__` and `__getitem__` with integer indices.
+
+    ```
+    use cpython::{py_class, py_generator_with_state, PyClone, PyObject, PyResult};
+
+    py_class!(class Stack |py| {
+        data items: Vec<i32>;
+
+        def __len__(&self) -> PyResult<usize> {
+            Ok(self.items(py).len())
+        }
+
+        def __reversed__(&self) -> PyResult<PyObject> {
+            let mut remaining = self.items(py).len();
+            py_generator_with_state(py, self.clone_ref(py), move |py, this: &Stack| {
+                remaining = remaining.checked_sub(1)?;
+                Some(Ok(this.items(py)[remaining]))
+            })
+        }
+    });
+    # fn main() {}
+    ```
+
   * `def __contains__(&self, item: impl FromPyObject) -> PyResult<bool>`
 
     Called by Python `item in self`.
@@ -383,13 +793,25 @@ py_class!(class MyIterator |py| {
     or the key-item pairs.
 
     If extraction of the `item` parameter fails with `TypeError`,
-    `__contains__` will return `Ok(false)`.
+    `__contains__` will return `Ok(false)`. Use `item: PyObject` to accept any object.
+    Errors returned by the method are raised from the `in` expression.
+
+    `__contains__` is stored in the `sq_contains` slot; without it, Python falls back to
+    iterating over the object (using `__iter__` or `__getitem__`) to test membership.
+
+  * `@classmethod def __class_getitem__(cls, item: impl FromPyObject) -> PyResult<impl ToPyObject>`
+
+    Called by subscripting the class itself, e.g. `MyType[int]` (PEP 560, Python 3.7 and later).
+    Python classes get an implicit `@classmethod` for this method, but in `py_class!` it must be
+    declared explicitly. It may return any object; to behave like `list[int]`, return
+    `types.GenericAlias(cls, item)` (Python 3.9 and later).
 
 ## Arithmetic methods
 
   * `def __add__(lhs, rhs) -> PyResult<impl ToPyObject>`
   * `def __sub__(lhs, rhs) -> PyResult<impl ToPyObject>`
   * `def __mul__(lhs, rhs) -> PyResult<impl ToPyObject>`
+  * `def __matmul__(lhs, rhs) -> PyResult<impl ToPyObject>`
   * `def __lshift__(lhs, rhs) -> PyResult<impl ToPyObject>`
   * `def __rshift__(lhs, rhs) -> PyResult<impl ToPyObject>`
   * `def __and__(lhs, rhs) -> PyResult<impl ToPyObject>`
@@ -406,7 +828,13 @@ py_class!(class MyIterator |py| {
     This means that you can't rely on the first parameter of these methods being `self`
     or being the correct type, and you should test the types of both operands before deciding what to do.
     If you can't handle the combination of types you've been given,
-    you should return `Ok(py.NotImplemented())`.
+    you should return `Ok(py.NotImplemented())`. Python then tries the other operand
+    (including a `__radd__()` etc. defined on a Python class), and raises `TypeError`
+    if neither operand supports the operation. Raising `TypeError` yourself instead
+    would prevent this fallback.
+
+    `__matmul__` (the `@` operator) and `__imatmul__` (`@=`) require Python 3.5 or later,
+    and are not supported on Python 2.7.
 
   * `def __iadd__(&self, other: impl FromPyObject) -> PyResult<impl ToPyObject>`
   * `def __isub__(&self, other: impl FromPyObject) -> PyResult<impl ToPyObject>`
@@ -428,17 +856,121 @@ py_class!(class MyIterator |py| {
     If you can't handle the combination of types you've been given,
     you should return `Ok(py.NotImplemented())`.
 
+  * `def __neg__(&self) -> PyResult<impl ToPyObject>`
+  * `def __pos__(&self) -> PyResult<impl ToPyObject>`
+  * `def __abs__(&self) -> PyResult<impl ToPyObject>`
+  * `def __invert__(&self) -> PyResult<impl ToPyObject>`
+
+    Called by the unary operators `-self`, `+self`, `abs(self)` and `~self`.
+
+  * `def __index__(&self) -> PyResult<impl ToPyObject>`
+
+    Called by `operator.index()` and wherever Python needs an exact integer,
+    e.g. for list indexing, slicing, `range()`, `bin()` and `hex()`.
+    The returned object must be an `int` (or `long` on Python 2), otherwise Python raises `TypeError`.
+    From Rust, use `ObjectProtocol::to_index()` to apply this conversion.
+
+  * `def __round__(&self, ndigits: Option<impl FromPyObject> = None) -> PyResult<impl ToPyObject>`
+  * `def __trunc__(&self) -> PyResult<impl ToPyObject>`
+  * `def __floor__(&self) -> PyResult<impl ToPyObject>`
+  * `def __ceil__(&self) -> PyResult<impl ToPyObject>`
+
+    Called by `round(self)` / `round(self, ndigits)`, `math.trunc(self)`, `math.floor(self)`
+    and `math.ceil(self)`. These are regular instance methods, not type slots;
+    Python looks them up on the type, so they work like any other `def`.
+    `round(self)` calls `__round__` without arguments, so `ndigits` should have a default.
+
+    On Python 2.7, `round()`, `math.floor()` and `math.ceil()` always convert the argument
+    to `float` and ignore these methods; only `math.trunc()` calls `__trunc__`.
+
 ## Context Manager
 
   * `def __enter__(&self) -> PyResult<impl ToPyObject>`
   * `def __exit__(&self, ty: Option<PyType>, value: PyObject, traceback: PyObject) -> PyResult<bool>`
 
+## Pickling
+
+  * `def __reduce__(&self) -> PyResult<impl ToPyObject>`
+  * `def __getnewargs__(&self) -> PyResult<impl ToPyObject<ObjectType=PyTuple>>`
+  * `def __getstate__(&self) -> PyResult<impl ToPyObject>`
+  * `def __setstate__(&self, state: impl FromPyObject) -> PyResult<PyNone>`
+
+These are regular instance methods, looked up by the `pickle` and `copy` modules.
+
+`__reduce__` typically returns a tuple `(callable, args)`; unpickling calls `callable(*args)`.
+Using the class itself as `callable` requires the class to declare `__new__`.
+
+Without `__reduce__`, pickle protocol 2 and later create the new instance by calling
+`cls.__new__(cls, *self.__getnewargs__())` and then restore the state with
+`__setstate__(self.__getstate__())`. This also requires `__new__`, and because `__setstate__`
+takes `&self`, the restored state must be stored using interior mutability.
+
+In either case, pickle serializes a reference to the class by its `__module__`
+and `__qualname__`, so the class must be accessible under that name from an importable module
+(see "Type name" above).
+
+## Class Creation Hooks
+
+  * `def __set_name__(&self, owner: PyType, name: PyString) -> PyResult<PyNone>`
+
+    Called when an instance of the class is assigned to an attribute in the body of
+    a class statement, with the class being created and the attribute name.
+    This lets objects such as registries or field declarations know the name they
+    are bound to. Only Python 3.6 and later call this method.
+
+  * `def __init_subclass__(cls, parameter-list) -> PyResult<PyNone>`
+
+    Called on the base class when a subclass of it is defined in Python,
+    with `cls` being the new subclass. The parameter list receives the keyword arguments
+    given in the class statement, e.g. `class Sub(Base, tag="x")`.
+    As in Python, this is implicitly a class method; `@classmethod` may be omitted.
+    Only Python 3.6 and later call this method.
+
+## Abstract Base Classes
+
+  * `@classmethod def __subclasshook__(cls, subclass: PyType) -> PyResult<impl ToPyObject>`
+
+    Customizes `issubclass()` and `isinstance()` checks against an abstract base class:
+    return `true` or `false` to override the normal check, or `py.NotImplemented()` to
+    fall back to it. As in Python, this must be declared as a class method.
+    The hook is only consulted by `abc.ABCMeta`, so it has an effect on Python subclasses
+    that use that metaclass, e.g. `class MyABC(MyType, metaclass=abc.ABCMeta)`.
+
+To make a `py_class!` type pass `isinstance()` checks against an existing abstract base class,
+register it as a virtual subclass using
+[PyType::register_virtual_subclass](struct.PyType.html#method.register_virtual_subclass).
+
+## Attribute Access
+
+  * `def __getattribute__(&self, name: impl FromPyObject) -> PyResult<impl ToPyObject>`
+
+    Called for every attribute lookup on instances of the class, including lookups of
+    methods and properties. Use `ObjectProtocol::generic_getattr()` on `self.as_object()`
+    to perform the default lookup, for example to fall back to a computed value
+    only if it fails with `AttributeError` (like a Python `__getattr__` method):
+
+    ```ignore
+    def __getattribute__(&self, name: PyString) -> PyResult<PyObject> {
+        match self.as_object().generic_getattr(py, &name) {
+            Err(ref e) if e.matches(py, py.get_type::<exc::AttributeError>()) => {
+                self.lookup_fallback(py, name)
+            }
+            result => result,
+        }
+    }
+    ```
+
+`__getattr__`, `__setattr__` and `__delattr__` cannot be defined.
+
 ## Other Special Methods
 
   * `def __bool__(&self) -> PyResult<bool>`
 
     Determines the "truthyness" of the object.
 
+    If both `__bool__` and `__len__` are defined, `__bool__` takes precedence
+    for `bool(obj)` and `if obj:`, while `len(obj)` still calls `__len__`.
+
     Note that `py_class!` always expects this member to be called `__bool__`,
     even on Python 2.7 where the Python spelling was `__nonzero__`.
 
@@ -447,6 +979,12 @@ py_class!(class MyIterator |py| {
     For details on `parameter-list`, see the documentation of `py_argparse!()`.
     The return type must be `PyResult<T>` for some `T` that implements `ToPyObject`.
 
+    On Python 3.9 and later, instances also implement the PEP 590 vectorcall protocol:
+    the arguments are parsed directly from the caller's argument array, without building
+    an argument tuple and keyword dict for each call.
+    (If the parameter list is `(*args, **kwargs)`, the tuple and dict are still created.)
+    Older Python versions call `__call__` through the `tp_call` slot.
+
 
 # Errors
 
@@ -455,28 +993,33 @@ py_class!(class MyIterator |py| {
   when the Python value does not match the expected type;
   the implementation of `FromPyObject` for your type may document additional
   errors).
-* If a method panics in Rust, a Python `SystemError` will be raised.
+* If a method panics in Rust, the panic is caught before it can unwind into Python,
+  and a Python `RuntimeError` will be raised
+  (this can be customized using [set_panic_handler](fn.set_panic_handler.html)).
 
 */
 #[macro_export]
 macro_rules! py_class {
-    (class $class:ident |$py: ident| { $( $body:tt )* }) => (
+    ($(#[doc=$doc:expr])* class $class:ident |$py: ident| { $( $body:tt )* }) => (
         $crate::py_class_impl! {
             { $( $body )* }
             $class $py
             /* info: */ {
                 /* base_type: */ $crate::PyObject,
-                /* size: */ <$crate::PyObject as $crate::py_class::BaseObject>::size(),
+                /* size: */ $crate::py_class::header_size::<$class>(),
                 /* class_visibility: */ {},
                 /* gc: */ {
                     /* traverse_proc: */ None,
                     /* traverse_data: */ [ /*name*/ ]
                 },
-                /* data: */ [ /* { offset, name, type, init_expr, init_type } */ ]
-                // TODO: base type, documentation, ...
+                /* base_type_object: */ {},
+                /* type_name: */ { module: {}, qualname: {} },
+                /* subclassable: */ false,
+                /* data: */ [ /* { offset, name, type, init_expr, init_type } */ ],
+                /* var_data: */ { /* name, item_type */ }
             }
             /* slots: */ {
-                /* type_slots */  [ /* slot: expr, */ ]
+                /* type_slots */  [ tp_doc: $crate::py_class_tp_doc!($($doc),*), ]
                 /* as_number */   [ /* slot: expr, */ ]
                 /* as_sequence */ [ /* slot: expr, */ ]
                 /* as_mapping */  [ /* slot: expr, */ ]
@@ -484,29 +1027,33 @@ macro_rules! py_class {
                     sdi_setitem: {},
                     sdi_delitem: {},
                 ]
+                /* vectorcall */ [ vc_vectorcall: {}, ]
             }
             /* impls: */ { /* impl body */ }
             /* members: */ { /* ident = expr; */ }
             /* props: */ { [ /* getters */ ] [ /* setters */ ] }
         }
     );
-    ($visibility:vis class $class:ident |$py: ident| { $( $body:tt )* }) => (
+    ($(#[doc=$doc:expr])* $visibility:vis class $class:ident |$py: ident| { $( $body:tt )* }) => (
         $crate::py_class_impl! {
             { $( $body )* }
             $class $py
             /* info: */ {
                 /* base_type: */ $crate::PyObject,
-                /* size: */ <$crate::PyObject as $crate::py_class::BaseObject>::size(),
+                /* size: */ $crate::py_class::header_size::<$class>(),
                 /* class_visibility: */ {$visibility},
                 /* gc: */ {
                     /* traverse_proc: */ None,
                     /* traverse_data: */ [ /*name*/ ]
                 },
-                /* data: */ [ /* { offset, name, type, init_expr, init_type } */ ]
-                // TODO: base type, documentation, ...
+                /* base_type_object: */ {},
+                /* type_name: */ { module: {}, qualname: {} },
+                /* subclassable: */ false,
+                /* data: */ [ /* { offset, name, type, init_expr, init_type } */ ],
+                /* var_data: */ { /* name, item_type */ }
             }
             /* slots: */ {
-                /* type_slots */  [ /* slot: expr, */ ]
+                /* type_slots */  [ tp_doc: $crate::py_class_tp_doc!($($doc),*), ]
                 /* as_number */   [ /* slot: expr, */ ]
                 /* as_sequence */ [ /* slot: expr, */ ]
                 /* as_mapping */  [ /* slot: expr, */ ]
@@ -514,6 +1061,7 @@ macro_rules! py_class {
                     sdi_setitem: {},
                     sdi_delitem: {},
                 ]
+                /* vectorcall */ [ vc_vectorcall: {}, ]
             }
             /* impls: */ { /* impl body */ }
             /* members: */ { /* ident = expr; */ }
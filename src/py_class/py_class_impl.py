@@ -37,7 +37,7 @@ macro_rules! py_class_impl {
 '''
 
 base_case = '''
-    // Base case: we're done munching and can start producing code:
+    // Base case: we're done munching and can start producing code%(comment)s:
     {   {}
         $class:ident $py:ident
         /* info: */ {
@@ -45,7 +45,11 @@ base_case = '''
             $size:expr,
             { $( $class_visibility:tt )* },
             $gc:tt,
-            /* data: */ [ $( { $data_offset:expr, $data_name:ident, $data_ty:ty, $init_expr:expr, $init_ty:ty } )* ]
+            /* base_type_object: */ $base_type_object:tt,
+            /* type_name: */ $type_name:tt,
+            /* subclassable: */ $subclassable:tt,
+            /* data: */ [ $( { $data_offset:expr, $data_name:ident, $data_ty:ty, $init_expr:expr, $init_ty:ty } )* ],
+            /* var_data: */ %(var_pattern)s
         }
         $slots:tt { $( $imp:item )* } $members:tt $props:tt
     } => {
@@ -112,44 +116,62 @@ base_case = '''
 
         $crate::py_coerce_item! {
             impl $crate::py_class::BaseObject for $class {
-                type InitType = ( $( $init_ty, )* );
+                type InitType = ( $( $init_ty, )* %(var_init_ty)s);
 
                 #[inline]
                 fn size() -> usize {
-                    $size
+                    %(size)s
                 }
 
                 unsafe fn alloc(
                     py: $crate::Python,
                     ty: &$crate::PyType,
-                    ( $( $data_name, )* ): Self::InitType
+                    ( $( $data_name, )* %(var_init_name)s): Self::InitType
                 ) -> $crate::PyResult<$crate::PyObject>
                 {
-                    let obj = <$base_type as $crate::py_class::BaseObject>::alloc(py, ty, ())?;
+                    %(alloc)s
                     $( $crate::py_class::data_init::<$data_ty>(py, &obj, $data_offset, $init_expr); )*
+                    $crate::py_class_vectorcall_init!(py, &obj, $size, $slots);
                     Ok(obj)
                 }
 
                 unsafe fn dealloc(py: $crate::Python, obj: *mut $crate::_detail::ffi::PyObject) {
-                    $( $crate::py_class::data_drop::<$data_ty>(py, obj, $data_offset); )*
+                    $( $crate::py_class::data_drop::<$data_ty>(py, obj, $data_offset); )*%(drop)s
                     <$base_type as $crate::py_class::BaseObject>::dealloc(py, obj)
                 }
             }
         }
+
+        impl $crate::py_class::VarSizedObject for $class {
+            #[inline]
+            fn item_size() -> usize {
+                %(item_size)s
+            }
+        }
         $($imp)*
         $crate::py_coerce_item! {
             impl $class {
-                $($class_visibility)* fn create_instance(py: $crate::Python $( , $data_name : $init_ty )* ) -> $crate::PyResult<$class> {
+                $($class_visibility)* fn create_instance_of(py: $crate::Python, cls: &$crate::PyType $( , $data_name : $init_ty )* %(var_param)s) -> $crate::PyResult<$class> {
+                    $crate::py_class::slots::check_subtype(py, cls, &py.get_type::<$class>())?;
+                    let obj = unsafe {
+                        <$class as $crate::py_class::BaseObject>::alloc(
+                            py, cls, ( $($data_name,)* %(var_init_name)s)
+                        )
+                    }?;
+                    Ok($class { _unsafe_inner: obj })
+                }
+
+                $($class_visibility)* fn create_instance(py: $crate::Python $( , $data_name : $init_ty )* %(var_param)s) -> $crate::PyResult<$class> {
                     let obj = unsafe {
                         <$class as $crate::py_class::BaseObject>::alloc(
-                            py, &py.get_type::<$class>(), ( $($data_name,)* )
+                            py, &py.get_type::<$class>(), ( $($data_name,)* %(var_init_name)s)
                         )
                     }?;
                     return Ok($class { _unsafe_inner: obj });
 
                     // hide statics in create_instance to avoid name conflicts
                     static mut TYPE_OBJECT : $crate::_detail::ffi::PyTypeObject
-                        = $crate::py_class_type_object_static_init!($class, $gc, $slots);
+                        = $crate::py_class_type_object_static_init!($class, $gc, $subclassable, $slots);
                     static mut INIT_ACTIVE: bool = false;
 
                     // trait implementations that need direct access to TYPE_OBJECT
@@ -185,12 +207,13 @@ base_case = '''
 
                         fn add_to_module(py: $crate::Python, module: &$crate::PyModule) -> $crate::PyResult<()> {
                             let ty = <$class as $crate::py_class::PythonObjectFromPyClassMacro>::initialize(py, module.name(py).ok())?;
-                            module.add(py, stringify!($class), ty)
+                            module.add(py, $crate::py_class_type_name!(qualname, $class, $type_name), ty)
                         }
                     }
 
                     fn init($py: $crate::Python, module_name: Option<&str>) -> $crate::PyResult<$crate::PyType> {
-                        $crate::py_class_type_object_dynamic_init!($class, $py, TYPE_OBJECT, module_name, $slots $props);
+                        $crate::py_class_type_object_dynamic_init!($class, $py, TYPE_OBJECT, module_name, $slots $props $base_type_object $type_name);
+                        $crate::py_class_type_object_vectorcall!(TYPE_OBJECT, $size, $slots);
                         $crate::py_class_init_members!($class, $py, TYPE_OBJECT, $members);
                         unsafe {
                             if $crate::_detail::ffi::PyType_Ready(&mut TYPE_OBJECT) == 0 {
@@ -247,7 +270,8 @@ slot_groups = (
     ('nb', 'as_number', None),
     ('sq', 'as_sequence', None),
     ('mp', 'as_mapping', None),
-    ('sdi', 'setdelitem', ['sdi_setitem', 'sdi_delitem'])
+    ('sdi', 'setdelitem', ['sdi_setitem', 'sdi_delitem']),
+    ('vc', 'vectorcall', ['vc_vectorcall']),
 )
 
 def generate_case(pattern, old_info=None, new_info=None, new_impl=None, new_slots=None, new_members=None, new_props=None):
@@ -261,7 +285,11 @@ def generate_case(pattern, old_info=None, new_info=None, new_impl=None, new_slot
         write('$size: expr,\n')
         write('$class_visibility: tt,\n')
         write('$gc: tt,\n')
-        write('[ $( $data:tt )* ]\n')
+        write('$base_type_object: tt,\n')
+        write('$type_name: tt,\n')
+        write('$subclassable: tt,\n')
+        write('[ $( $data:tt )* ],\n')
+        write('$var_data: tt\n')
         write('}\n')
     else:
         write('$info:tt')
@@ -362,6 +390,9 @@ def data_decl():
             /* size: */ $crate::py_class::data_new_size::<$data_type>($size),
             $class_visibility,
             $gc,
+            $base_type_object,
+            $type_name,
+            $subclassable,
             /* data: */ [
                 $($data)*
                 {
@@ -371,7 +402,8 @@ def data_decl():
                     /* init_expr: */ $data_name,
                     /* init_ty: */ $data_type
                 }
-            ]
+            ],
+            $var_data
         }
         ''',
         new_impl='''
@@ -388,6 +420,54 @@ def data_decl():
             }
         ''')
 
+def var_data_decl():
+    # the items are stored after all other data members (at offset `size()`),
+    # and their count in the `ob_size` field of the object header.
+    generate_case('data $data_name:ident : [$item_type:ty];',
+        old_info = '''
+        /* info: */ {
+            $base_type: ty,
+            $size: expr,
+            $class_visibility: tt,
+            $gc: tt,
+            $base_type_object: tt,
+            $type_name: tt,
+            $subclassable: tt,
+            $datas: tt,
+            /* var_data: */ {}
+        }
+        ''',
+        new_info = '''
+        /* info: */ {
+            $base_type,
+            $size,
+            $class_visibility,
+            $gc,
+            $base_type_object,
+            $type_name,
+            $subclassable,
+            $datas,
+            /* var_data: */ { $data_name, $item_type }
+        }
+        ''',
+        new_impl='''
+            impl $class {
+                fn $data_name<'a>(&'a self, py: $crate::Python<'a>) -> &'a [$item_type] {
+                    unsafe {
+                        $crate::py_class::var_data_get::<$item_type>(
+                            py,
+                            &self._unsafe_inner,
+                            <$class as $crate::py_class::BaseObject>::size()
+                        )
+                    }
+                }
+            }
+        ''')
+    print('''
+    { { data $data_name:ident : [$item_type:ty]; $($tail:tt)* } $( $stuff:tt )* } => {
+        $crate::py_error! { "A py_class! can only have one variable-size data member." }
+    };''')
+
 def shared_data_decl():
     # the storage type is PySharedRefCell<$data_type>, but unlike plain "data",
     # its reference type is PySharedRef<'a, $data_type>.
@@ -398,6 +478,9 @@ def shared_data_decl():
             /* size: */ $crate::py_class::data_new_size::<$crate::PySharedRefCell<$data_type>>($size),
             $class_visibility,
             $gc,
+            $base_type_object,
+            $type_name,
+            $subclassable,
             /* data: */ [
                 $($data)*
                 {
@@ -407,7 +490,8 @@ def shared_data_decl():
                     /* init_expr: */ $crate::PySharedRefCell::<$data_type>::new($data_name),
                     /* init_ty: */ $data_type
                 }
-            ]
+            ],
+            $var_data
         }
         ''',
         new_impl='''
@@ -425,6 +509,67 @@ def shared_data_decl():
             }
         ''')
 
+def data_property_decl():
+    # the storage type is RefCell<$data_type>; the generated getter returns a clone
+    # of the value and the generated setter replaces it.
+    data_info = '''
+        /* info: */ {
+            $base_type,
+            /* size: */ $crate::py_class::data_new_size::<std::cell::RefCell<$data_type>>($size),
+            $class_visibility,
+            $gc,
+            $base_type_object,
+            $type_name,
+            $subclassable,
+            /* data: */ [
+                $($data)*
+                {
+                    $crate::py_class::data_offset::<std::cell::RefCell<$data_type>>($size),
+                    $data_name,
+                    /* data_ty: */ std::cell::RefCell<$data_type>,
+                    /* init_expr: */ std::cell::RefCell::<$data_type>::new($data_name),
+                    /* init_ty: */ $data_type
+                }
+            ],
+            $var_data
+        }
+        '''
+    cell = '''
+                    let cell = unsafe {
+                        $crate::py_class::data_get::<std::cell::RefCell<$data_type>>(
+                            py,
+                            &self._unsafe_inner,
+                            $crate::py_class::data_offset::<std::cell::RefCell<$data_type>>($size)
+                        )
+                    };'''
+    getter = '''
+                pub fn $data_name(&self, py: $crate::Python) -> $crate::PyResult<$data_type> {%s
+                    Ok(std::clone::Clone::clone(&*cell.borrow()))
+                }''' % cell
+    setter = '''
+                pub fn $setter_name(&self, py: $crate::Python, value: Option<$data_type>) -> $crate::PyResult<()> {
+                    match value {
+                        Some(value) => {%s
+                            let old = cell.replace(value);
+                            // the old value is dropped outside of the borrow,
+                            // as its destructor may run Python code accessing this attribute
+                            drop(old);
+                            Ok(())
+                        }
+                        None => Err($crate::PyErr::new::<$crate::exc::AttributeError, _>(
+                            py, concat!("cannot delete attribute '", stringify!($data_name), "'"))),
+                    }
+                }''' % cell.replace('\n                    ', '\n                            ')
+    generate_case('$(#[doc=$doc:expr])* @property data $data_name:ident : $data_type:ty;',
+        new_info=data_info,
+        new_impl='impl $class {%s\n            }' % getter,
+        new_props=([('concat!($($doc, "\\n"),*)', '$data_name', '$data_type')], []))
+    generate_case('$(#[doc=$doc:expr])* @property data $data_name:ident : $data_type:ty, setter $setter_name:ident;',
+        new_info=data_info,
+        new_impl='impl $class {%s%s\n            }' % (getter, setter),
+        new_props=([('concat!($($doc, "\\n"),*)', '$data_name', '$data_type')],
+                   [('$data_name', '$data_type', '$setter_name')]))
+
 def generate_class_method(special_name=None, decoration='',
         slot=None, add_member=False, value_macro=None, value_args=None):
     name_pattern = special_name or '$name:ident'
@@ -481,7 +626,11 @@ def traverse_and_clear():
                 /* traverse_proc: */ None,
                 $traverse_data: tt
             },
-            $datas: tt
+            $base_type_object: tt,
+            $type_name: tt,
+            $subclassable: tt,
+            $datas: tt,
+            $var_data: tt
         }
         ''',
         new_info='''
@@ -493,7 +642,11 @@ def traverse_and_clear():
                 /* traverse_proc: */ $class::__traverse__,
                 $traverse_data
             },
-            $datas
+            $base_type_object,
+            $type_name,
+            $subclassable,
+            $datas,
+            $var_data
         }
         ''',
         new_impl='''
@@ -517,8 +670,133 @@ def traverse_and_clear():
             }
         ''')
 
+def base_type_object():
+    generate_case('@base $base_expr:expr;',
+        old_info = '''
+        /* info: */ {
+            $base_type: ty,
+            $size: expr,
+            $class_visibility: tt,
+            $gc: tt,
+            /* base_type_object: */ {},
+            $type_name: tt,
+            $subclassable: tt,
+            $datas: tt,
+            $var_data: tt
+        }
+        ''',
+        new_info = '''
+        /* info: */ {
+            $base_type,
+            $size,
+            $class_visibility,
+            $gc,
+            /* base_type_object: */ { $base_expr },
+            $type_name,
+            $subclassable,
+            $datas,
+            $var_data
+        }
+        ''')
+
+def metaclass():
+    generate_case('@metaclass;',
+        old_info = '''
+        /* info: */ {
+            $base_type: ty,
+            $size: expr,
+            $class_visibility: tt,
+            $gc: tt,
+            /* base_type_object: */ {},
+            $type_name: tt,
+            $subclassable: tt,
+            $datas: tt,
+            $var_data: tt
+        }
+        ''',
+        new_info = '''
+        /* info: */ {
+            $base_type,
+            $size,
+            $class_visibility,
+            $gc,
+            /* base_type_object: */ { @metaclass },
+            $type_name,
+            $subclassable,
+            $datas,
+            $var_data
+        }
+        ''')
+
+def subclassable():
+    generate_case('@subclassable;',
+        old_info = '''
+        /* info: */ {
+            $base_type: ty,
+            $size: expr,
+            $class_visibility: tt,
+            $gc: tt,
+            $base_type_object: tt,
+            $type_name: tt,
+            /* subclassable: */ false,
+            $datas: tt,
+            $var_data: tt
+        }
+        ''',
+        new_info = '''
+        /* info: */ {
+            $base_type,
+            $size,
+            $class_visibility,
+            $gc,
+            $base_type_object,
+            $type_name,
+            /* subclassable: */ true,
+            $datas,
+            $var_data
+        }
+        ''')
+
+def type_name():
+    for part, before, after in (
+            ('module', 'module: {}, qualname: $qualname:tt',
+                       'module: { $module_expr }, qualname: $qualname'),
+            ('qualname', 'module: $module:tt, qualname: {}',
+                         'module: $module, qualname: { $qualname_expr }')):
+        generate_case('@%s $%s_expr:expr;' % (part, part),
+            old_info = '''
+            /* info: */ {
+                $base_type: ty,
+                $size: expr,
+                $class_visibility: tt,
+                $gc: tt,
+                $base_type_object: tt,
+                /* type_name: */ { %s },
+                $subclassable: tt,
+                $datas: tt,
+                $var_data: tt
+            }
+            ''' % before,
+            new_info = '''
+            /* info: */ {
+                $base_type,
+                $size,
+                $class_visibility,
+                $gc,
+                $base_type_object,
+                /* type_name: */ { %s },
+                $subclassable,
+                $datas,
+                $var_data
+            }
+            ''' % after)
+
+def unhashable():
+    generate_case('@unhashable;',
+        new_slots=[('tp_hash', 'Some($crate::_detail::ffi::PyObject_HashNotImplemented)')])
+
 def generate_instance_method(special_name=None, decoration='',
-        slot=None, add_member=False, value_macro=None, value_args=None):
+        slot=None, add_member=False, value_macro=None, value_args=None, extra_slots=()):
     name_pattern = special_name or '$name:ident'
     name_use = special_name or '$name'
     def impl(with_params, with_docs, with_visibility):
@@ -539,18 +817,20 @@ def generate_instance_method(special_name=None, decoration='',
                 py_class_impl_item { $class, $py, %s, %s(&$slf,) $res_type; { $($body)* } }
                 [] ($($p)+,)
             }''' % (visibility_expansion, name_use)
-            value = '$crate::py_argparse_parse_plist_impl!{%s {%s} [] ($($p)+,)}' \
-                    % (value_macro, value_args + value_suffix)
+            value_pattern = '$crate::py_argparse_parse_plist_impl!{%s {%s} [] ($($p)+,)}'
         else:
             param_pattern = ''
             impl = '$crate::py_class_impl_item! { $class, $py, %s, %s(&$slf,) $res_type; { $($body)* } [] }' \
                 % (visibility_expansion, name_use)
-            value = '$crate::%s!{%s []}' % (value_macro, value_args + value_suffix)
+            value_pattern = '$crate::%s!{%s []}'
+        value = value_pattern % (value_macro, value_args + value_suffix)
         pattern = '%s %s def %s (&$slf:ident%s) -> $res_type:ty { $( $body:tt )* }' \
             % (doc_prefix + decoration, visibility_capture, name_pattern, param_pattern)
         slots = []
         if slot is not None:
             slots.append((slot, value))
+        for extra_slot, extra_macro in extra_slots:
+            slots.append((extra_slot, value_pattern % (extra_macro, value_args + value_suffix)))
         members = []
         if add_member:
             members.append((name_use, value))
@@ -736,7 +1016,26 @@ def call_operator(special_name, slot):
         special_name=special_name,
         slot=slot,
         value_macro='py_class_call_slot',
-        value_args='$class::%s' % special_name)
+        value_args='$py, $class::%s' % special_name,
+        extra_slots=[('vc_vectorcall', 'py_class_vectorcall_slot')])
+
+@special_method
+def init_operator(special_name, slot):
+    generate_instance_method(
+        special_name=special_name,
+        slot=slot,
+        value_macro='py_class_init_slot',
+        value_args='$py, $class::%s' % special_name)
+
+@special_method
+def finalizer(special_name, slot):
+    generate_case(
+        pattern='def %s(&$slf:ident) -> $res_type:ty { $($body:tt)* }' % special_name,
+        new_impl='$crate::py_class_impl_item! { $class, $py, pub, %s(&$slf,) $res_type; { $($body)* } [] }'
+                 % special_name,
+        new_slots=[(slot, '$crate::py_class_finalize_slot!($class::%s)' % special_name)]
+    )
+    error('Invalid signature for %s' % special_name)(special_name)
 
 @special_method
 def binary_numeric_operator(special_name, slot):
@@ -760,12 +1059,13 @@ def inplace_numeric_operator(special_name, slot):
              args=[Argument('other')])(special_name)
 
 special_names = {
-    '__init__': error('__init__ is not supported by py_class!; use __new__ instead.'),
+    '__init__': init_operator('tp_init'),
     '__new__': special_class_method(
         slot='tp_new',
         value_macro='py_class_wrap_newfunc',
-        value_args='$class::__new__'),
-    '__del__': error('__del__ is not supported by py_class!; Use a data member with a Drop impl instead.'),
+        value_args='$py, $class::__new__'),
+    '__del__': error('__del__ is not supported by py_class! in Python 2.7; Use a data member with a Drop impl instead.')
+               if PY2 else finalizer('tp_finalize'),
     '__repr__': operator('tp_repr', res_type="PyString"),
     '__str__': operator('tp_str', res_type="PyString"),
     '__unicode__': normal_method(),
@@ -790,7 +1090,7 @@ special_names = {
         res_type='bool'),
     # Customizing attribute access
     '__getattr__': unimplemented(),
-    '__getattribute__': unimplemented(),
+    '__getattribute__': operator('tp_getattro', args=[Argument('name')]),
     '__setattr__': unimplemented(),
     '__delattr__': unimplemented(),
     '__dir__': unimplemented(),
@@ -799,6 +1099,16 @@ special_names = {
     '__get__': unimplemented(),
     '__set__': unimplemented(),
     '__delete__': unimplemented(),
+    '__set_name__': normal_method(),
+
+    # Customizing class creation
+    # Like in Python, __init_subclass__ is implicitly a class method.
+    '__init_subclass__': special_class_method(
+        add_member=True,
+        value_macro='py_class_class_method',
+        value_args='$py, $class::__init_subclass__'),
+    '__subclasshook__': error('__subclasshook__ must be declared as a @classmethod.'),
+    '__class_getitem__': error('__class_getitem__ must be declared as a @classmethod.'),
 
     # Customizing instance and subclass checks
     '__instancecheck__': unimplemented(),
@@ -838,7 +1148,8 @@ special_names = {
     '__add__': binary_numeric_operator('nb_add'),
     '__sub__': binary_numeric_operator('nb_subtract'),
     '__mul__': binary_numeric_operator('nb_multiply'),
-    '__matmul__': unimplemented(),
+    '__matmul__': error('__matmul__ is not supported by py_class! in Python 2.7.')
+                  if PY2 else binary_numeric_operator('nb_matrix_multiply'),
     '__div__': unimplemented(),
     '__truediv__': unimplemented(),
     '__floordiv__': unimplemented(),
@@ -872,7 +1183,8 @@ special_names = {
     '__iadd__': inplace_numeric_operator('nb_inplace_add'),
     '__isub__': inplace_numeric_operator('nb_inplace_subtract'),
     '__imul__': inplace_numeric_operator('nb_inplace_multiply'),
-    '__imatmul__': inplace_numeric_operator('nb_inplace_matrix_multiply'),
+    '__imatmul__': error('__imatmul__ is not supported by py_class! in Python 2.7.')
+                   if PY2 else inplace_numeric_operator('nb_inplace_matrix_multiply'),
     '__idiv__': unimplemented(),
     '__itruediv__': inplace_numeric_operator('nb_inplace_true_divide'),
     '__ifloordiv__': inplace_numeric_operator('nb_inplace_floor_divide'),
@@ -893,14 +1205,24 @@ special_names = {
     '__int__': unimplemented(),
     '__long__': unimplemented(),
     '__float__': unimplemented(),
-    '__round__': unimplemented(),
-    '__index__': unimplemented(),
+    '__round__': normal_method(),
+    '__trunc__': normal_method(),
+    '__floor__': normal_method(),
+    '__ceil__': normal_method(),
+    '__index__': operator('nb_index'),
     '__coerce__': unimplemented(),
 
     # With statement context managers
     '__enter__': normal_method(),
     '__exit__': normal_method(),
 
+    # Pickling
+    '__reduce__': normal_method(),
+    '__reduce_ex__': normal_method(),
+    '__getnewargs__': normal_method(),
+    '__getstate__': normal_method(),
+    '__setstate__': normal_method(),
+
     # Coroutines
     '__await__': unimplemented(),
     '__aiter__': unimplemented(),
@@ -924,10 +1246,40 @@ def main():
     print('//')
     print('// REGENERATE USING THE MAKEFILE IN ROOT OF REPOSITORY: make build')
     print(macro_start)
-    print(base_case)
+    print(base_case % {
+        'comment': '',
+        'var_pattern': '{}',
+        'var_init_ty': '',
+        'var_init_name': '',
+        'var_param': '',
+        'size': '$crate::py_class_vectorcall_size!($size, $slots)',
+        'alloc': 'let obj = <$base_type as $crate::py_class::BaseObject>::alloc(py, ty, ())?;',
+        'drop': '',
+        'item_size': '0',
+    })
+    print(base_case % {
+        'comment': ' (with variable-size data)',
+        'var_pattern': '{ $var_name:ident, $var_ty:ty }',
+        'var_init_ty': 'std::vec::Vec<$var_ty>, ',
+        'var_init_name': '$var_name, ',
+        'var_param': ', $var_name: std::vec::Vec<$var_ty> ',
+        'size': '$crate::py_class::data_offset::<$var_ty>($crate::py_class_vectorcall_size!($size, $slots))',
+        'alloc': '''let obj = $crate::py_class::var_data_alloc(py, ty, $var_name.len())?;
+                    $crate::py_class::var_data_init::<$var_ty>(py, &obj, Self::size(), $var_name);''',
+        'drop': '''
+                    $crate::py_class::var_data_drop::<$var_ty>(py, obj, Self::size());''',
+        'item_size': '$crate::py_class::var_item_size::<$var_ty>()',
+    })
+    var_data_decl()
     data_decl()
     shared_data_decl()
+    data_property_decl()
     traverse_and_clear()
+    base_type_object()
+    metaclass()
+    type_name()
+    subclassable()
+    unhashable()
     for name, f in sorted(special_names.items()):
         f(name)
     generate_instance_method(
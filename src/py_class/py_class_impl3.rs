@@ -39,7 +39,11 @@ macro_rules! py_class_impl {
             $size:expr,
             { $( $class_visibility:tt )* },
             $gc:tt,
-            /* data: */ [ $( { $data_offset:expr, $data_name:ident, $data_ty:ty, $init_expr:expr, $init_ty:ty } )* ]
+            /* base_type_object: */ $base_type_object:tt,
+            /* type_name: */ $type_name:tt,
+            /* subclassable: */ $subclassable:tt,
+            /* data: */ [ $( { $data_offset:expr, $data_name:ident, $data_ty:ty, $init_expr:expr, $init_ty:ty } )* ],
+            /* var_data: */ {}
         }
         $slots:tt { $( $imp:item )* } $members:tt $props:tt
     } => {
@@ -110,7 +114,7 @@ macro_rules! py_class_impl {
 
                 #[inline]
                 fn size() -> usize {
-                    $size
+                    $crate::py_class_vectorcall_size!($size, $slots)
                 }
 
                 unsafe fn alloc(
@@ -121,6 +125,7 @@ macro_rules! py_class_impl {
                 {
                     let obj = <$base_type as $crate::py_class::BaseObject>::alloc(py, ty, ())?;
                     $( $crate::py_class::data_init::<$data_ty>(py, &obj, $data_offset, $init_expr); )*
+                    $crate::py_class_vectorcall_init!(py, &obj, $size, $slots);
                     Ok(obj)
                 }
 
@@ -130,9 +135,26 @@ macro_rules! py_class_impl {
                 }
             }
         }
+
+        impl $crate::py_class::VarSizedObject for $class {
+            #[inline]
+            fn item_size() -> usize {
+                0
+            }
+        }
         $($imp)*
         $crate::py_coerce_item! {
             impl $class {
+                $($class_visibility)* fn create_instance_of(py: $crate::Python, cls: &$crate::PyType $( , $data_name : $init_ty )* ) -> $crate::PyResult<$class> {
+                    $crate::py_class::slots::check_subtype(py, cls, &py.get_type::<$class>())?;
+                    let obj = unsafe {
+                        <$class as $crate::py_class::BaseObject>::alloc(
+                            py, cls, ( $($data_name,)* )
+                        )
+                    }?;
+                    Ok($class { _unsafe_inner: obj })
+                }
+
                 $($class_visibility)* fn create_instance(py: $crate::Python $( , $data_name : $init_ty )* ) -> $crate::PyResult<$class> {
                     let obj = unsafe {
                         <$class as $crate::py_class::BaseObject>::alloc(
@@ -143,7 +165,201 @@ macro_rules! py_class_impl {
 
                     // hide statics in create_instance to avoid name conflicts
                     static mut TYPE_OBJECT : $crate::_detail::ffi::PyTypeObject
-                        = $crate::py_class_type_object_static_init!($class, $gc, $slots);
+                        = $crate::py_class_type_object_static_init!($class, $gc, $subclassable, $slots);
+                    static mut INIT_ACTIVE: bool = false;
+
+                    // trait implementations that need direct access to TYPE_OBJECT
+                    impl $crate::PythonObjectWithTypeObject for $class {
+                        fn type_object(py: $crate::Python) -> $crate::PyType {
+                            unsafe {
+                                if $crate::py_class::is_ready(py, &TYPE_OBJECT) {
+                                    $crate::PyType::from_type_ptr(py, &mut TYPE_OBJECT)
+                                } else {
+                                    // automatically initialize the class on-demand
+                                    <$class as $crate::py_class::PythonObjectFromPyClassMacro>::initialize(py, None)
+                                        .expect(concat!("An error occurred while initializing class ", stringify!($class)))
+                                }
+                            }
+                        }
+                    }
+
+                    impl $crate::py_class::PythonObjectFromPyClassMacro for $class {
+                        fn initialize(py: $crate::Python, module_name: Option<&str>) -> $crate::PyResult<$crate::PyType> {
+                            unsafe {
+                                if $crate::py_class::is_ready(py, &TYPE_OBJECT) {
+                                    return Ok($crate::PyType::from_type_ptr(py, &mut TYPE_OBJECT));
+                                }
+                                assert!(!INIT_ACTIVE,
+                                    concat!("Reentrancy detected: already initializing class ",
+                                    stringify!($class)));
+                                INIT_ACTIVE = true;
+                                let res = init(py, module_name);
+                                INIT_ACTIVE = false;
+                                res
+                            }
+                        }
+
+                        fn add_to_module(py: $crate::Python, module: &$crate::PyModule) -> $crate::PyResult<()> {
+                            let ty = <$class as $crate::py_class::PythonObjectFromPyClassMacro>::initialize(py, module.name(py).ok())?;
+                            module.add(py, $crate::py_class_type_name!(qualname, $class, $type_name), ty)
+                        }
+                    }
+
+                    fn init($py: $crate::Python, module_name: Option<&str>) -> $crate::PyResult<$crate::PyType> {
+                        $crate::py_class_type_object_dynamic_init!($class, $py, TYPE_OBJECT, module_name, $slots $props $base_type_object $type_name);
+                        $crate::py_class_type_object_vectorcall!(TYPE_OBJECT, $size, $slots);
+                        $crate::py_class_init_members!($class, $py, TYPE_OBJECT, $members);
+                        unsafe {
+                            if $crate::_detail::ffi::PyType_Ready(&mut TYPE_OBJECT) == 0 {
+                                Ok($crate::PyType::from_type_ptr($py, &mut TYPE_OBJECT))
+                            } else {
+                                Err($crate::PyErr::fetch($py))
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+
+    // Base case: we're done munching and can start producing code (with variable-size data):
+    {   {}
+        $class:ident $py:ident
+        /* info: */ {
+            $base_type:ty,
+            $size:expr,
+            { $( $class_visibility:tt )* },
+            $gc:tt,
+            /* base_type_object: */ $base_type_object:tt,
+            /* type_name: */ $type_name:tt,
+            /* subclassable: */ $subclassable:tt,
+            /* data: */ [ $( { $data_offset:expr, $data_name:ident, $data_ty:ty, $init_expr:expr, $init_ty:ty } )* ],
+            /* var_data: */ { $var_name:ident, $var_ty:ty }
+        }
+        $slots:tt { $( $imp:item )* } $members:tt $props:tt
+    } => {
+        $crate::py_coerce_item! {
+            $($class_visibility)* struct $class { _unsafe_inner: $crate::PyObject }
+        }
+
+        $crate::py_impl_to_py_object_for_python_object!($class);
+        $crate::py_impl_from_py_object_for_python_object!($class);
+
+        impl $crate::PythonObject for $class {
+            #[inline]
+            fn as_object(&self) -> &$crate::PyObject {
+                &self._unsafe_inner
+            }
+
+            #[inline]
+            fn into_object(self) -> $crate::PyObject {
+                self._unsafe_inner
+            }
+
+            /// Unchecked downcast from PyObject to Self.
+            /// Undefined behavior if the input object does not have the expected type.
+            #[inline]
+            unsafe fn unchecked_downcast_from(obj: $crate::PyObject) -> Self {
+                $class { _unsafe_inner: obj }
+            }
+
+            /// Unchecked downcast from PyObject to Self.
+            /// Undefined behavior if the input object does not have the expected type.
+            #[inline]
+            unsafe fn unchecked_downcast_borrow_from<'a>(obj: &'a $crate::PyObject) -> &'a Self {
+                std::mem::transmute(obj)
+            }
+        }
+
+        impl $crate::PythonObjectWithCheckedDowncast for $class {
+            #[inline]
+            fn downcast_from<'p>(py: $crate::Python<'p>, obj: $crate::PyObject) -> Result<$class, $crate::PythonObjectDowncastError<'p>> {
+                if py.get_type::<$class>().is_instance(py, &obj) {
+                    Ok($class { _unsafe_inner: obj })
+                } else {
+                    Err($crate::PythonObjectDowncastError::new(
+                        py,
+                        stringify!($class),
+                        obj.get_type(py),
+                    ))
+                }
+            }
+
+            #[inline]
+            fn downcast_borrow_from<'a, 'p>(py: $crate::Python<'p>, obj: &'a $crate::PyObject) -> Result<&'a $class, $crate::PythonObjectDowncastError<'p>> {
+                if py.get_type::<$class>().is_instance(py, obj) {
+                    unsafe { Ok(std::mem::transmute(obj)) }
+                } else {
+                    Err($crate::PythonObjectDowncastError::new(
+                        py,
+                        stringify!($class),
+                        obj.get_type(py),
+                    ))
+                }
+            }
+        }
+
+        $crate::py_coerce_item! {
+            impl $crate::py_class::BaseObject for $class {
+                type InitType = ( $( $init_ty, )* std::vec::Vec<$var_ty>, );
+
+                #[inline]
+                fn size() -> usize {
+                    $crate::py_class::data_offset::<$var_ty>($crate::py_class_vectorcall_size!($size, $slots))
+                }
+
+                unsafe fn alloc(
+                    py: $crate::Python,
+                    ty: &$crate::PyType,
+                    ( $( $data_name, )* $var_name, ): Self::InitType
+                ) -> $crate::PyResult<$crate::PyObject>
+                {
+                    let obj = $crate::py_class::var_data_alloc(py, ty, $var_name.len())?;
+                    $crate::py_class::var_data_init::<$var_ty>(py, &obj, Self::size(), $var_name);
+                    $( $crate::py_class::data_init::<$data_ty>(py, &obj, $data_offset, $init_expr); )*
+                    $crate::py_class_vectorcall_init!(py, &obj, $size, $slots);
+                    Ok(obj)
+                }
+
+                unsafe fn dealloc(py: $crate::Python, obj: *mut $crate::_detail::ffi::PyObject) {
+                    $( $crate::py_class::data_drop::<$data_ty>(py, obj, $data_offset); )*
+                    $crate::py_class::var_data_drop::<$var_ty>(py, obj, Self::size());
+                    <$base_type as $crate::py_class::BaseObject>::dealloc(py, obj)
+                }
+            }
+        }
+
+        impl $crate::py_class::VarSizedObject for $class {
+            #[inline]
+            fn item_size() -> usize {
+                $crate::py_class::var_item_size::<$var_ty>()
+            }
+        }
+        $($imp)*
+        $crate::py_coerce_item! {
+            impl $class {
+                $($class_visibility)* fn create_instance_of(py: $crate::Python, cls: &$crate::PyType $( , $data_name : $init_ty )* , $var_name: std::vec::Vec<$var_ty> ) -> $crate::PyResult<$class> {
+                    $crate::py_class::slots::check_subtype(py, cls, &py.get_type::<$class>())?;
+                    let obj = unsafe {
+                        <$class as $crate::py_class::BaseObject>::alloc(
+                            py, cls, ( $($data_name,)* $var_name, )
+                        )
+                    }?;
+                    Ok($class { _unsafe_inner: obj })
+                }
+
+                $($class_visibility)* fn create_instance(py: $crate::Python $( , $data_name : $init_ty )* , $var_name: std::vec::Vec<$var_ty> ) -> $crate::PyResult<$class> {
+                    let obj = unsafe {
+                        <$class as $crate::py_class::BaseObject>::alloc(
+                            py, &py.get_type::<$class>(), ( $($data_name,)* $var_name, )
+                        )
+                    }?;
+                    return Ok($class { _unsafe_inner: obj });
+
+                    // hide statics in create_instance to avoid name conflicts
+                    static mut TYPE_OBJECT : $crate::_detail::ffi::PyTypeObject
+                        = $crate::py_class_type_object_static_init!($class, $gc, $subclassable, $slots);
                     static mut INIT_ACTIVE: bool = false;
 
                     // trait implementations that need direct access to TYPE_OBJECT
@@ -179,12 +395,13 @@ macro_rules! py_class_impl {
 
                         fn add_to_module(py: $crate::Python, module: &$crate::PyModule) -> $crate::PyResult<()> {
                             let ty = <$class as $crate::py_class::PythonObjectFromPyClassMacro>::initialize(py, module.name(py).ok())?;
-                            module.add(py, stringify!($class), ty)
+                            module.add(py, $crate::py_class_type_name!(qualname, $class, $type_name), ty)
                         }
                     }
 
                     fn init($py: $crate::Python, module_name: Option<&str>) -> $crate::PyResult<$crate::PyType> {
-                        $crate::py_class_type_object_dynamic_init!($class, $py, TYPE_OBJECT, module_name, $slots $props);
+                        $crate::py_class_type_object_dynamic_init!($class, $py, TYPE_OBJECT, module_name, $slots $props $base_type_object $type_name);
+                        $crate::py_class_type_object_vectorcall!(TYPE_OBJECT, $size, $slots);
                         $crate::py_class_init_members!($class, $py, TYPE_OBJECT, $members);
                         unsafe {
                             if $crate::_detail::ffi::PyType_Ready(&mut TYPE_OBJECT) == 0 {
@@ -199,6 +416,57 @@ macro_rules! py_class_impl {
         }
     };
 
+    { { data $data_name:ident : [$item_type:ty]; $($tail:tt)* }
+        $class:ident $py:ident
+        /* info: */ {
+            $base_type: ty,
+            $size: expr,
+            $class_visibility: tt,
+            $gc: tt,
+            $base_type_object: tt,
+            $type_name: tt,
+            $subclassable: tt,
+            $datas: tt,
+            /* var_data: */ {}
+        }
+        $slots:tt
+        { $( $imp:item )* }
+        $members:tt $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py
+        /* info: */ {
+            $base_type,
+            $size,
+            $class_visibility,
+            $gc,
+            $base_type_object,
+            $type_name,
+            $subclassable,
+            $datas,
+            /* var_data: */ { $data_name, $item_type }
+        }
+        $slots
+        /* impl: */ {
+            $($imp)*
+            impl $class {
+                fn $data_name<'a>(&'a self, py: $crate::Python<'a>) -> &'a [$item_type] {
+                    unsafe {
+                        $crate::py_class::var_data_get::<$item_type>(
+                        py,
+                        &self._unsafe_inner,
+                        <$class as $crate::py_class::BaseObject>::size()
+                        )
+                    }
+                }
+            }
+        }
+        $members $props
+    }};
+
+    { { data $data_name:ident : [$item_type:ty]; $($tail:tt)* } $( $stuff:tt )* } => {
+        $crate::py_error! { "A py_class! can only have one variable-size data member." }
+    };
     { { data $data_name:ident : $data_type:ty; $($tail:tt)* }
         $class:ident $py:ident
         /* info: */ {
@@ -206,7 +474,11 @@ macro_rules! py_class_impl {
             $size: expr,
             $class_visibility: tt,
             $gc: tt,
-            [ $( $data:tt )* ]
+            $base_type_object: tt,
+            $type_name: tt,
+            $subclassable: tt,
+            [ $( $data:tt )* ],
+            $var_data: tt
         }
         $slots:tt
         { $( $imp:item )* }
@@ -219,6 +491,9 @@ macro_rules! py_class_impl {
             /* size: */ $crate::py_class::data_new_size::<$data_type>($size),
             $class_visibility,
             $gc,
+            $base_type_object,
+            $type_name,
+            $subclassable,
             /* data: */ [
                 $($data)*
                 {
@@ -228,7 +503,8 @@ macro_rules! py_class_impl {
                     /* init_expr: */ $data_name,
                     /* init_ty: */ $data_type
                 }
-            ]
+            ],
+            $var_data
         }
         $slots
         /* impl: */ {
@@ -245,56 +521,217 @@ macro_rules! py_class_impl {
                 }
             }
         }
-        $members $props
+        $members $props
+    }};
+    { { @shared data $data_name:ident : $data_type:ty; $($tail:tt)* }
+        $class:ident $py:ident
+        /* info: */ {
+            $base_type: ty,
+            $size: expr,
+            $class_visibility: tt,
+            $gc: tt,
+            $base_type_object: tt,
+            $type_name: tt,
+            $subclassable: tt,
+            [ $( $data:tt )* ],
+            $var_data: tt
+        }
+        $slots:tt
+        { $( $imp:item )* }
+        $members:tt $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py
+        /* info: */ {
+            $base_type,
+            /* size: */ $crate::py_class::data_new_size::<$crate::PySharedRefCell<$data_type>>($size),
+            $class_visibility,
+            $gc,
+            $base_type_object,
+            $type_name,
+            $subclassable,
+            /* data: */ [
+                $($data)*
+                {
+                    $crate::py_class::data_offset::<$crate::PySharedRefCell<$data_type>>($size),
+                    $data_name,
+                    /* data_ty: */ $crate::PySharedRefCell<$data_type>,
+                    /* init_expr: */ $crate::PySharedRefCell::<$data_type>::new($data_name),
+                    /* init_ty: */ $data_type
+                }
+            ],
+            $var_data
+        }
+        $slots
+        /* impl: */ {
+            $($imp)*
+            impl $class {
+                fn $data_name<'a>(&'a self, py: $crate::Python<'a>) -> $crate::PySharedRef<'a, $data_type> {
+                    unsafe {
+                        let data = $crate::py_class::data_get::<$crate::PySharedRefCell<$data_type>>(
+                        py,
+                        &self._unsafe_inner,
+                        $crate::py_class::data_offset::<$crate::PySharedRefCell<$data_type>>($size)
+                        );
+                        $crate::PySharedRef::new(py, &self._unsafe_inner, data)
+                    }
+                }
+            }
+        }
+        $members $props
+    }};
+    { { $(#[doc=$doc:expr])* @property data $data_name:ident : $data_type:ty; $($tail:tt)* }
+        $class:ident $py:ident
+        /* info: */ {
+            $base_type: ty,
+            $size: expr,
+            $class_visibility: tt,
+            $gc: tt,
+            $base_type_object: tt,
+            $type_name: tt,
+            $subclassable: tt,
+            [ $( $data:tt )* ],
+            $var_data: tt
+        }
+        $slots:tt
+        { $( $imp:item )* }
+        $members:tt
+        { [ $( $prop_doc:tt $prop_getter_name:ident: $prop_type:ty, )* ]
+            [ $( $prop_setter_name:ident : $prop_setter_value_type:tt => $prop_setter_setter:ident, )* ] }
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py
+        /* info: */ {
+            $base_type,
+            /* size: */ $crate::py_class::data_new_size::<std::cell::RefCell<$data_type>>($size),
+            $class_visibility,
+            $gc,
+            $base_type_object,
+            $type_name,
+            $subclassable,
+            /* data: */ [
+                $($data)*
+                {
+                    $crate::py_class::data_offset::<std::cell::RefCell<$data_type>>($size),
+                    $data_name,
+                    /* data_ty: */ std::cell::RefCell<$data_type>,
+                    /* init_expr: */ std::cell::RefCell::<$data_type>::new($data_name),
+                    /* init_ty: */ $data_type
+                }
+            ],
+            $var_data
+        }
+        $slots
+        /* impl: */ {
+            $($imp)*
+            impl $class {
+                pub fn $data_name(&self, py: $crate::Python) -> $crate::PyResult<$data_type> {
+                    let cell = unsafe {
+                        $crate::py_class::data_get::<std::cell::RefCell<$data_type>>(
+                        py,
+                        &self._unsafe_inner,
+                        $crate::py_class::data_offset::<std::cell::RefCell<$data_type>>($size)
+                        )
+                    };
+                    Ok(std::clone::Clone::clone(&*cell.borrow()))
+                }
+            }
+        }
+        $members
+        /* props: */ {
+            [ $( $prop_doc $prop_getter_name: $prop_type, )*
+                { concat!($($doc, "\n"),*) } $data_name: $data_type,
+            ]
+            [ $( $prop_setter_name : $prop_setter_value_type => $prop_setter_setter, )*
+            ]
+        }
     }};
-    { { @shared data $data_name:ident : $data_type:ty; $($tail:tt)* }
+    { { $(#[doc=$doc:expr])* @property data $data_name:ident : $data_type:ty, setter $setter_name:ident; $($tail:tt)* }
         $class:ident $py:ident
         /* info: */ {
             $base_type: ty,
             $size: expr,
             $class_visibility: tt,
             $gc: tt,
-            [ $( $data:tt )* ]
+            $base_type_object: tt,
+            $type_name: tt,
+            $subclassable: tt,
+            [ $( $data:tt )* ],
+            $var_data: tt
         }
         $slots:tt
         { $( $imp:item )* }
-        $members:tt $props:tt
+        $members:tt
+        { [ $( $prop_doc:tt $prop_getter_name:ident: $prop_type:ty, )* ]
+            [ $( $prop_setter_name:ident : $prop_setter_value_type:tt => $prop_setter_setter:ident, )* ] }
     } => { $crate::py_class_impl! {
         { $($tail)* }
         $class $py
         /* info: */ {
             $base_type,
-            /* size: */ $crate::py_class::data_new_size::<$crate::PySharedRefCell<$data_type>>($size),
+            /* size: */ $crate::py_class::data_new_size::<std::cell::RefCell<$data_type>>($size),
             $class_visibility,
             $gc,
+            $base_type_object,
+            $type_name,
+            $subclassable,
             /* data: */ [
                 $($data)*
                 {
-                    $crate::py_class::data_offset::<$crate::PySharedRefCell<$data_type>>($size),
+                    $crate::py_class::data_offset::<std::cell::RefCell<$data_type>>($size),
                     $data_name,
-                    /* data_ty: */ $crate::PySharedRefCell<$data_type>,
-                    /* init_expr: */ $crate::PySharedRefCell::<$data_type>::new($data_name),
+                    /* data_ty: */ std::cell::RefCell<$data_type>,
+                    /* init_expr: */ std::cell::RefCell::<$data_type>::new($data_name),
                     /* init_ty: */ $data_type
                 }
-            ]
+            ],
+            $var_data
         }
         $slots
         /* impl: */ {
             $($imp)*
             impl $class {
-                fn $data_name<'a>(&'a self, py: $crate::Python<'a>) -> $crate::PySharedRef<'a, $data_type> {
-                    unsafe {
-                        let data = $crate::py_class::data_get::<$crate::PySharedRefCell<$data_type>>(
+                pub fn $data_name(&self, py: $crate::Python) -> $crate::PyResult<$data_type> {
+                    let cell = unsafe {
+                        $crate::py_class::data_get::<std::cell::RefCell<$data_type>>(
                         py,
                         &self._unsafe_inner,
-                        $crate::py_class::data_offset::<$crate::PySharedRefCell<$data_type>>($size)
-                        );
-                        $crate::PySharedRef::new(py, &self._unsafe_inner, data)
+                        $crate::py_class::data_offset::<std::cell::RefCell<$data_type>>($size)
+                        )
+                    };
+                    Ok(std::clone::Clone::clone(&*cell.borrow()))
+                }
+                pub fn $setter_name(&self, py: $crate::Python, value: Option<$data_type>) -> $crate::PyResult<()> {
+                    match value {
+                        Some(value) => {
+                            let cell = unsafe {
+                                $crate::py_class::data_get::<std::cell::RefCell<$data_type>>(
+                                py,
+                                &self._unsafe_inner,
+                                $crate::py_class::data_offset::<std::cell::RefCell<$data_type>>($size)
+                                )
+                            };
+                            let old = cell.replace(value);
+                            // the old value is dropped outside of the borrow,
+                            // as its destructor may run Python code accessing this attribute
+                            drop(old);
+                            Ok(())
+                        }
+                        None => Err($crate::PyErr::new::<$crate::exc::AttributeError, _>(
+                        py, concat!("cannot delete attribute '", stringify!($data_name), "'"))),
                     }
                 }
             }
         }
-        $members $props
+        $members
+        /* props: */ {
+            [ $( $prop_doc $prop_getter_name: $prop_type, )*
+                { concat!($($doc, "\n"),*) } $data_name: $data_type,
+            ]
+            [ $( $prop_setter_name : $prop_setter_value_type => $prop_setter_setter, )*
+                $data_name : [ $data_type ] => $setter_name,
+            ]
+        }
     }};
     { { def __traverse__(&$slf:tt, $visit:ident) $body:block $($tail:tt)* }
         $class:ident $py:ident
@@ -306,7 +743,11 @@ macro_rules! py_class_impl {
                 /* traverse_proc: */ None,
                 $traverse_data: tt
             },
-            $datas: tt
+            $base_type_object: tt,
+            $type_name: tt,
+            $subclassable: tt,
+            $datas: tt,
+            $var_data: tt
         }
         $slots:tt
         { $( $imp:item )* }
@@ -322,7 +763,11 @@ macro_rules! py_class_impl {
                 /* traverse_proc: */ $class::__traverse__,
                 $traverse_data
             },
-            $datas
+            $base_type_object,
+            $type_name,
+            $subclassable,
+            $datas,
+            $var_data
         }
         $slots
         /* impl: */ {
@@ -343,7 +788,7 @@ macro_rules! py_class_impl {
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -355,7 +800,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_clear: $crate::py_class_tp_clear!($class),
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -367,12 +812,181 @@ macro_rules! py_class_impl {
         }
         $members $props
     }};
+    { { @base $base_expr:expr; $($tail:tt)* }
+        $class:ident $py:ident
+        /* info: */ {
+            $base_type: ty,
+            $size: expr,
+            $class_visibility: tt,
+            $gc: tt,
+            /* base_type_object: */ {},
+            $type_name: tt,
+            $subclassable: tt,
+            $datas: tt,
+            $var_data: tt
+        }
+        $slots:tt $impls:tt $members:tt $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py
+        /* info: */ {
+            $base_type,
+            $size,
+            $class_visibility,
+            $gc,
+            /* base_type_object: */ { $base_expr },
+            $type_name,
+            $subclassable,
+            $datas,
+            $var_data
+        }
+        $slots $impls $members $props
+    }};
+    { { @metaclass; $($tail:tt)* }
+        $class:ident $py:ident
+        /* info: */ {
+            $base_type: ty,
+            $size: expr,
+            $class_visibility: tt,
+            $gc: tt,
+            /* base_type_object: */ {},
+            $type_name: tt,
+            $subclassable: tt,
+            $datas: tt,
+            $var_data: tt
+        }
+        $slots:tt $impls:tt $members:tt $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py
+        /* info: */ {
+            $base_type,
+            $size,
+            $class_visibility,
+            $gc,
+            /* base_type_object: */ { @metaclass },
+            $type_name,
+            $subclassable,
+            $datas,
+            $var_data
+        }
+        $slots $impls $members $props
+    }};
+    { { @module $module_expr:expr; $($tail:tt)* }
+        $class:ident $py:ident
+        /* info: */ {
+            $base_type: ty,
+            $size: expr,
+            $class_visibility: tt,
+            $gc: tt,
+            $base_type_object: tt,
+            /* type_name: */ { module: {}, qualname: $qualname:tt },
+            $subclassable: tt,
+            $datas: tt,
+            $var_data: tt
+        }
+        $slots:tt $impls:tt $members:tt $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py
+        /* info: */ {
+            $base_type,
+            $size,
+            $class_visibility,
+            $gc,
+            $base_type_object,
+            /* type_name: */ { module: { $module_expr }, qualname: $qualname },
+            $subclassable,
+            $datas,
+            $var_data
+        }
+        $slots $impls $members $props
+    }};
+    { { @qualname $qualname_expr:expr; $($tail:tt)* }
+        $class:ident $py:ident
+        /* info: */ {
+            $base_type: ty,
+            $size: expr,
+            $class_visibility: tt,
+            $gc: tt,
+            $base_type_object: tt,
+            /* type_name: */ { module: $module:tt, qualname: {} },
+            $subclassable: tt,
+            $datas: tt,
+            $var_data: tt
+        }
+        $slots:tt $impls:tt $members:tt $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py
+        /* info: */ {
+            $base_type,
+            $size,
+            $class_visibility,
+            $gc,
+            $base_type_object,
+            /* type_name: */ { module: $module, qualname: { $qualname_expr } },
+            $subclassable,
+            $datas,
+            $var_data
+        }
+        $slots $impls $members $props
+    }};
+    { { @subclassable; $($tail:tt)* }
+        $class:ident $py:ident
+        /* info: */ {
+            $base_type: ty,
+            $size: expr,
+            $class_visibility: tt,
+            $gc: tt,
+            $base_type_object: tt,
+            $type_name: tt,
+            /* subclassable: */ false,
+            $datas: tt,
+            $var_data: tt
+        }
+        $slots:tt $impls:tt $members:tt $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py
+        /* info: */ {
+            $base_type,
+            $size,
+            $class_visibility,
+            $gc,
+            $base_type_object,
+            $type_name,
+            /* subclassable: */ true,
+            $datas,
+            $var_data
+        }
+        $slots $impls $members $props
+    }};
+    { { @unhashable; $($tail:tt)* }
+        $class:ident $py:ident $info:tt
+        /* slots: */ {
+            /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
+        }
+        $impls:tt $members:tt $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py $info
+        /* slots: */ {
+            /* type_slots */ [
+                $( $tp_slot_name : $tp_slot_value, )*
+                tp_hash: Some($crate::_detail::ffi::PyObject_HashNotImplemented),
+            ]
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
+        }
+        $impls $members $props
+    }};
     { { def __abs__(&$slf:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -385,7 +999,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_absolute: $crate::py_class_unary_slot!($class::__abs__, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -402,7 +1016,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -415,7 +1029,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_add: $crate::py_class_binary_numeric_slot!($class::__add__),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -444,7 +1058,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -457,7 +1071,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_and: $crate::py_class_binary_numeric_slot!($class::__and__),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -478,7 +1092,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -491,7 +1105,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_bool: $crate::py_class_unary_slot!($class::__bool__, $crate::_detail::libc::c_int, $crate::py_class::slots::BoolConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -508,6 +1122,9 @@ macro_rules! py_class_impl {
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
             $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            /* vectorcall */ [
+                vc_vectorcall: {},
+            ]
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -517,9 +1134,12 @@ macro_rules! py_class_impl {
         /* slots: */ {
             /* type_slots */ [
                 $( $tp_slot_name : $tp_slot_value, )*
-                tp_call: $crate::py_class_call_slot!{$class::__call__ []},
+                tp_call: $crate::py_class_call_slot!{$py, $class::__call__ []},
             ]
             $as_number $as_sequence $as_mapping $setdelitem
+            /* vectorcall */ [
+                vc_vectorcall: { $crate::py_class_vectorcall_slot!{$py, $class::__call__ []} },
+            ]
         }
         /* impl: */ {
             $($imp)*
@@ -532,6 +1152,9 @@ macro_rules! py_class_impl {
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
             $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            /* vectorcall */ [
+                vc_vectorcall: {},
+            ]
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -541,9 +1164,12 @@ macro_rules! py_class_impl {
         /* slots: */ {
             /* type_slots */ [
                 $( $tp_slot_name : $tp_slot_value, )*
-                tp_call: $crate::py_class_call_slot!{$class::__call__ []},
+                tp_call: $crate::py_class_call_slot!{$py, $class::__call__ []},
             ]
             $as_number $as_sequence $as_mapping $setdelitem
+            /* vectorcall */ [
+                vc_vectorcall: { $crate::py_class_vectorcall_slot!{$py, $class::__call__ []} },
+            ]
         }
         /* impl: */ {
             $($imp)*
@@ -556,6 +1182,9 @@ macro_rules! py_class_impl {
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
             $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            /* vectorcall */ [
+                vc_vectorcall: {},
+            ]
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -565,9 +1194,12 @@ macro_rules! py_class_impl {
         /* slots: */ {
             /* type_slots */ [
                 $( $tp_slot_name : $tp_slot_value, )*
-                tp_call: $crate::py_argparse_parse_plist_impl!{py_class_call_slot {$class::__call__} [] ($($p)+,)},
+                tp_call: $crate::py_argparse_parse_plist_impl!{py_class_call_slot {$py, $class::__call__} [] ($($p)+,)},
             ]
             $as_number $as_sequence $as_mapping $setdelitem
+            /* vectorcall */ [
+                vc_vectorcall: { $crate::py_argparse_parse_plist_impl!{py_class_vectorcall_slot {$py, $class::__call__} [] ($($p)+,)} },
+            ]
         }
         /* impl: */ {
             $($imp)*
@@ -583,6 +1215,9 @@ macro_rules! py_class_impl {
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
             $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            /* vectorcall */ [
+                vc_vectorcall: {},
+            ]
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -592,9 +1227,12 @@ macro_rules! py_class_impl {
         /* slots: */ {
             /* type_slots */ [
                 $( $tp_slot_name : $tp_slot_value, )*
-                tp_call: $crate::py_argparse_parse_plist_impl!{py_class_call_slot {$class::__call__} [] ($($p)+,)},
+                tp_call: $crate::py_argparse_parse_plist_impl!{py_class_call_slot {$py, $class::__call__} [] ($($p)+,)},
             ]
             $as_number $as_sequence $as_mapping $setdelitem
+            /* vectorcall */ [
+                vc_vectorcall: { $crate::py_argparse_parse_plist_impl!{py_class_vectorcall_slot {$py, $class::__call__} [] ($($p)+,)} },
+            ]
         }
         /* impl: */ {
             $($imp)*
@@ -606,6 +1244,10 @@ macro_rules! py_class_impl {
         $members $props
     }};
 
+    { { def __class_getitem__ $($tail:tt)* } $( $stuff:tt )* } => {
+        $crate::py_error! { "__class_getitem__ must be declared as a @classmethod." }
+    };
+
     { { def __cmp__ $($tail:tt)* } $( $stuff:tt )* } => {
         $crate::py_error! { "__cmp__ is not supported by py_class! use __richcmp__ instead." }
     };
@@ -622,7 +1264,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt $as_number:tt
             /* as_sequence */ [ $( $sq_slot_name:ident : $sq_slot_value:expr, )* ]
-            $as_mapping:tt $setdelitem:tt
+            $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -635,7 +1277,7 @@ macro_rules! py_class_impl {
                 $( $sq_slot_name : $sq_slot_value, )*
                 sq_contains: $crate::py_class_contains_slot!($class::__contains__, [Option<&$item_name>]),
             ]
-            $as_mapping $setdelitem
+            $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -648,7 +1290,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt $as_number:tt
             /* as_sequence */ [ $( $sq_slot_name:ident : $sq_slot_value:expr, )* ]
-            $as_mapping:tt $setdelitem:tt
+            $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -661,7 +1303,7 @@ macro_rules! py_class_impl {
                 $( $sq_slot_name : $sq_slot_value, )*
                 sq_contains: $crate::py_class_contains_slot!($class::__contains__, [&$item_name]),
             ]
-            $as_mapping $setdelitem
+            $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -674,7 +1316,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt $as_number:tt
             /* as_sequence */ [ $( $sq_slot_name:ident : $sq_slot_value:expr, )* ]
-            $as_mapping:tt $setdelitem:tt
+            $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -687,7 +1329,7 @@ macro_rules! py_class_impl {
                 $( $sq_slot_name : $sq_slot_value, )*
                 sq_contains: $crate::py_class_contains_slot!($class::__contains__, [$item_name]),
             ]
-            $as_mapping $setdelitem
+            $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -699,9 +1341,33 @@ macro_rules! py_class_impl {
     { { def __contains__ $($tail:tt)* } $( $stuff:tt )* } => {
         $crate::py_error! { "Invalid signature for operator __contains__" }
     };
+    { { def __del__(&$slf:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt
+        /* slots: */ {
+            /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
+        }
+        { $( $imp:item )* }
+        $members:tt $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py $info
+        /* slots: */ {
+            /* type_slots */ [
+                $( $tp_slot_name : $tp_slot_value, )*
+                tp_finalize: $crate::py_class_finalize_slot!($class::__del__),
+            ]
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
+        }
+        /* impl: */ {
+            $($imp)*
+            $crate::py_class_impl_item! { $class, $py, pub, __del__(&$slf,) $res_type; { $($body)* } [] }
+        }
+        $members $props
+    }};
 
     { { def __del__ $($tail:tt)* } $( $stuff:tt )* } => {
-        $crate::py_error! { "__del__ is not supported by py_class!; Use a data member with a Drop impl instead." }
+        $crate::py_error! { "Invalid signature for __del__" }
     };
 
     { { def __delattr__ $($tail:tt)* } $( $stuff:tt )* } => {
@@ -719,6 +1385,7 @@ macro_rules! py_class_impl {
                 sdi_setitem: $sdi_setitem_slot_value:tt,
                 sdi_delitem: {},
             ]
+            $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -731,6 +1398,7 @@ macro_rules! py_class_impl {
                 sdi_setitem: $sdi_setitem_slot_value,
                 sdi_delitem: { $crate::py_class_binary_slot!($class::__delitem__, [Option<&$key_name>], $crate::_detail::libc::c_int, $crate::py_class::slots::UnitCallbackConverter) },
             ]
+            $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -746,6 +1414,7 @@ macro_rules! py_class_impl {
                 sdi_setitem: $sdi_setitem_slot_value:tt,
                 sdi_delitem: {},
             ]
+            $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -758,6 +1427,7 @@ macro_rules! py_class_impl {
                 sdi_setitem: $sdi_setitem_slot_value,
                 sdi_delitem: { $crate::py_class_binary_slot!($class::__delitem__, [&$key_name], $crate::_detail::libc::c_int, $crate::py_class::slots::UnitCallbackConverter) },
             ]
+            $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -773,6 +1443,7 @@ macro_rules! py_class_impl {
                 sdi_setitem: $sdi_setitem_slot_value:tt,
                 sdi_delitem: {},
             ]
+            $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -785,56 +1456,129 @@ macro_rules! py_class_impl {
                 sdi_setitem: $sdi_setitem_slot_value,
                 sdi_delitem: { $crate::py_class_binary_slot!($class::__delitem__, [$key_name], $crate::_detail::libc::c_int, $crate::py_class::slots::UnitCallbackConverter) },
             ]
+            $vectorcall
+        }
+        /* impl: */ {
+            $($imp)*
+            $crate::py_class_impl_item! { $class, $py, pub, __delitem__(&$slf,) $res_type; { $($body)* } [{ $key : $key_name = {} }] }
+        }
+        $members $props
+    }};
+
+    { { def __delitem__ $($tail:tt)* } $( $stuff:tt )* } => {
+        $crate::py_error! { "Invalid signature for operator __delitem__" }
+    };
+
+    { { def __dir__ $($tail:tt)* } $( $stuff:tt )* } => {
+        $crate::py_error! { "__dir__ is not supported by py_class! yet." }
+    };
+
+    { { def __div__ $($tail:tt)* } $( $stuff:tt )* } => {
+        $crate::py_error! { "__div__ is not supported by py_class! yet." }
+    };
+
+    { { def __divmod__ $($tail:tt)* } $( $stuff:tt )* } => {
+        $crate::py_error! { "__divmod__ is not supported by py_class! yet." }
+    };
+
+    { { def __eq__ $($tail:tt)* } $( $stuff:tt )* } => {
+        $crate::py_error! { "__eq__ is not supported by py_class! use __richcmp__ instead." }
+    };
+
+    { { def __float__ $($tail:tt)* } $( $stuff:tt )* } => {
+        $crate::py_error! { "__float__ is not supported by py_class! yet." }
+    };
+
+    { { def __floordiv__ $($tail:tt)* } $( $stuff:tt )* } => {
+        $crate::py_error! { "__floordiv__ is not supported by py_class! yet." }
+    };
+
+    { { def __ge__ $($tail:tt)* } $( $stuff:tt )* } => {
+        $crate::py_error! { "__ge__ is not supported by py_class! use __richcmp__ instead." }
+    };
+
+    { { def __get__ $($tail:tt)* } $( $stuff:tt )* } => {
+        $crate::py_error! { "__get__ is not supported by py_class! yet." }
+    };
+
+    { { def __getattr__ $($tail:tt)* } $( $stuff:tt )* } => {
+        $crate::py_error! { "__getattr__ is not supported by py_class! yet." }
+    };
+    { { def __getattribute__(&$slf:ident, $name:ident : Option<&$name_name:ty>) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt
+        /* slots: */ {
+            /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
+        }
+        { $( $imp:item )* }
+        $members:tt $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py $info
+        /* slots: */ {
+            /* type_slots */ [
+                $( $tp_slot_name : $tp_slot_value, )*
+                tp_getattro: $crate::py_class_binary_slot!($class::__getattribute__, [Option<&$name_name>], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
+            ]
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
+        }
+        /* impl: */ {
+            $($imp)*
+            $crate::py_class_impl_item! { $class, $py, pub, __getattribute__(&$slf,) $res_type; { $($body)* } [{ $name : Option<&$name_name> = {} }] }
+        }
+        $members $props
+    }};
+    { { def __getattribute__(&$slf:ident, $name:ident : &$name_name:ty) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt
+        /* slots: */ {
+            /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
+        }
+        { $( $imp:item )* }
+        $members:tt $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py $info
+        /* slots: */ {
+            /* type_slots */ [
+                $( $tp_slot_name : $tp_slot_value, )*
+                tp_getattro: $crate::py_class_binary_slot!($class::__getattribute__, [&$name_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
+            ]
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
+        }
+        /* impl: */ {
+            $($imp)*
+            $crate::py_class_impl_item! { $class, $py, pub, __getattribute__(&$slf,) $res_type; { $($body)* } [{ $name : &$name_name = {} }] }
+        }
+        $members $props
+    }};
+    { { def __getattribute__(&$slf:ident, $name:ident : $name_name:ty) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt
+        /* slots: */ {
+            /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
+        }
+        { $( $imp:item )* }
+        $members:tt $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py $info
+        /* slots: */ {
+            /* type_slots */ [
+                $( $tp_slot_name : $tp_slot_value, )*
+                tp_getattro: $crate::py_class_binary_slot!($class::__getattribute__, [$name_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
+            ]
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
-            $crate::py_class_impl_item! { $class, $py, pub, __delitem__(&$slf,) $res_type; { $($body)* } [{ $key : $key_name = {} }] }
+            $crate::py_class_impl_item! { $class, $py, pub, __getattribute__(&$slf,) $res_type; { $($body)* } [{ $name : $name_name = {} }] }
         }
         $members $props
     }};
 
-    { { def __delitem__ $($tail:tt)* } $( $stuff:tt )* } => {
-        $crate::py_error! { "Invalid signature for operator __delitem__" }
-    };
-
-    { { def __dir__ $($tail:tt)* } $( $stuff:tt )* } => {
-        $crate::py_error! { "__dir__ is not supported by py_class! yet." }
-    };
-
-    { { def __div__ $($tail:tt)* } $( $stuff:tt )* } => {
-        $crate::py_error! { "__div__ is not supported by py_class! yet." }
-    };
-
-    { { def __divmod__ $($tail:tt)* } $( $stuff:tt )* } => {
-        $crate::py_error! { "__divmod__ is not supported by py_class! yet." }
-    };
-
-    { { def __eq__ $($tail:tt)* } $( $stuff:tt )* } => {
-        $crate::py_error! { "__eq__ is not supported by py_class! use __richcmp__ instead." }
-    };
-
-    { { def __float__ $($tail:tt)* } $( $stuff:tt )* } => {
-        $crate::py_error! { "__float__ is not supported by py_class! yet." }
-    };
-
-    { { def __floordiv__ $($tail:tt)* } $( $stuff:tt )* } => {
-        $crate::py_error! { "__floordiv__ is not supported by py_class! yet." }
-    };
-
-    { { def __ge__ $($tail:tt)* } $( $stuff:tt )* } => {
-        $crate::py_error! { "__ge__ is not supported by py_class! use __richcmp__ instead." }
-    };
-
-    { { def __get__ $($tail:tt)* } $( $stuff:tt )* } => {
-        $crate::py_error! { "__get__ is not supported by py_class! yet." }
-    };
-
-    { { def __getattr__ $($tail:tt)* } $( $stuff:tt )* } => {
-        $crate::py_error! { "__getattr__ is not supported by py_class! yet." }
-    };
-
     { { def __getattribute__ $($tail:tt)* } $( $stuff:tt )* } => {
-        $crate::py_error! { "__getattribute__ is not supported by py_class! yet." }
+        $crate::py_error! { "Invalid signature for operator __getattribute__" }
     };
     { { def __getitem__(&$slf:ident, $key:ident : Option<&$key_name:ty>) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
         $class:ident $py:ident $info:tt
@@ -842,7 +1586,7 @@ macro_rules! py_class_impl {
             $type_slots:tt $as_number:tt
             /* as_sequence */ [ $( $sq_slot_name:ident : $sq_slot_value:expr, )* ]
             /* as_mapping */ [ $( $mp_slot_name:ident : $mp_slot_value:expr, )* ]
-            $setdelitem:tt
+            $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -859,7 +1603,7 @@ macro_rules! py_class_impl {
                 $( $mp_slot_name : $mp_slot_value, )*
                 mp_subscript: $crate::py_class_binary_slot!($class::__getitem__, [Option<&$key_name>], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $setdelitem
+            $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -873,7 +1617,7 @@ macro_rules! py_class_impl {
             $type_slots:tt $as_number:tt
             /* as_sequence */ [ $( $sq_slot_name:ident : $sq_slot_value:expr, )* ]
             /* as_mapping */ [ $( $mp_slot_name:ident : $mp_slot_value:expr, )* ]
-            $setdelitem:tt
+            $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -890,7 +1634,7 @@ macro_rules! py_class_impl {
                 $( $mp_slot_name : $mp_slot_value, )*
                 mp_subscript: $crate::py_class_binary_slot!($class::__getitem__, [&$key_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $setdelitem
+            $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -904,7 +1648,7 @@ macro_rules! py_class_impl {
             $type_slots:tt $as_number:tt
             /* as_sequence */ [ $( $sq_slot_name:ident : $sq_slot_value:expr, )* ]
             /* as_mapping */ [ $( $mp_slot_name:ident : $mp_slot_value:expr, )* ]
-            $setdelitem:tt
+            $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -921,7 +1665,7 @@ macro_rules! py_class_impl {
                 $( $mp_slot_name : $mp_slot_value, )*
                 mp_subscript: $crate::py_class_binary_slot!($class::__getitem__, [$key_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $setdelitem
+            $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -941,7 +1685,7 @@ macro_rules! py_class_impl {
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -953,7 +1697,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_hash: $crate::py_class_unary_slot!($class::__hash__, $crate::Py_hash_t, $crate::py_class::slots::HashConverter),
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -970,7 +1714,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -983,7 +1727,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_add: $crate::py_class_binary_slot!($class::__iadd__, [Option<&$other_name>], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -996,7 +1740,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1009,7 +1753,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_add: $crate::py_class_binary_slot!($class::__iadd__, [&$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1022,7 +1766,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1035,7 +1779,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_add: $crate::py_class_binary_slot!($class::__iadd__, [$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1052,7 +1796,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1065,7 +1809,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_and: $crate::py_class_binary_slot!($class::__iand__, [Option<&$other_name>], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1078,7 +1822,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1091,7 +1835,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_and: $crate::py_class_binary_slot!($class::__iand__, [&$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1104,7 +1848,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1117,7 +1861,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_and: $crate::py_class_binary_slot!($class::__iand__, [$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1138,7 +1882,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1151,7 +1895,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_floor_divide: $crate::py_class_binary_slot!($class::__ifloordiv__, [Option<&$other_name>], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1164,7 +1908,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1177,7 +1921,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_floor_divide: $crate::py_class_binary_slot!($class::__ifloordiv__, [&$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1190,7 +1934,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1203,7 +1947,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_floor_divide: $crate::py_class_binary_slot!($class::__ifloordiv__, [$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1220,7 +1964,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1233,7 +1977,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_lshift: $crate::py_class_binary_slot!($class::__ilshift__, [Option<&$other_name>], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1246,7 +1990,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1259,7 +2003,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_lshift: $crate::py_class_binary_slot!($class::__ilshift__, [&$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1272,7 +2016,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1285,7 +2029,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_lshift: $crate::py_class_binary_slot!($class::__ilshift__, [$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1302,7 +2046,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1315,7 +2059,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_matrix_multiply: $crate::py_class_binary_slot!($class::__imatmul__, [Option<&$other_name>], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1328,7 +2072,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1341,7 +2085,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_matrix_multiply: $crate::py_class_binary_slot!($class::__imatmul__, [&$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1354,7 +2098,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1367,7 +2111,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_matrix_multiply: $crate::py_class_binary_slot!($class::__imatmul__, [$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1384,7 +2128,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1397,7 +2141,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_remainder: $crate::py_class_binary_slot!($class::__imod__, [Option<&$other_name>], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1410,7 +2154,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1423,7 +2167,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_remainder: $crate::py_class_binary_slot!($class::__imod__, [&$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1436,7 +2180,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1449,7 +2193,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_remainder: $crate::py_class_binary_slot!($class::__imod__, [$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1466,7 +2210,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1479,7 +2223,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_multiply: $crate::py_class_binary_slot!($class::__imul__, [Option<&$other_name>], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1492,7 +2236,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1505,7 +2249,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_multiply: $crate::py_class_binary_slot!($class::__imul__, [&$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1518,7 +2262,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1531,7 +2275,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_multiply: $crate::py_class_binary_slot!($class::__imul__, [$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1543,14 +2287,208 @@ macro_rules! py_class_impl {
     { { def __imul__ $($tail:tt)* } $( $stuff:tt )* } => {
         $crate::py_error! { "Invalid signature for operator __imul__" }
     };
+    { { def __index__(&$slf:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt
+        /* slots: */ {
+            $type_slots:tt
+            /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
+        }
+        { $( $imp:item )* }
+        $members:tt $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py $info
+        /* slots: */ {
+            $type_slots
+            /* as_number */ [
+                $( $nb_slot_name : $nb_slot_value, )*
+                nb_index: $crate::py_class_unary_slot!($class::__index__, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
+            ]
+            $as_sequence $as_mapping $setdelitem $vectorcall
+        }
+        /* impl: */ {
+            $($imp)*
+            $crate::py_class_impl_item! { $class, $py, pub, __index__(&$slf,) $res_type; { $($body)* } [] }
+        }
+        $members $props
+    }};
 
     { { def __index__ $($tail:tt)* } $( $stuff:tt )* } => {
-        $crate::py_error! { "__index__ is not supported by py_class! yet." }
-    };
-
-    { { def __init__ $($tail:tt)* } $( $stuff:tt )* } => {
-        $crate::py_error! { "__init__ is not supported by py_class!; use __new__ instead." }
+        $crate::py_error! { "Invalid signature for operator __index__" }
     };
+    { {   def __init__ (&$slf:ident) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt
+        /* slots: */ {
+            /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
+        }
+        { $( $imp:item )* }
+        $members:tt $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py $info
+        /* slots: */ {
+            /* type_slots */ [
+                $( $tp_slot_name : $tp_slot_value, )*
+                tp_init: $crate::py_class_init_slot!{$py, $class::__init__ []},
+            ]
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
+        }
+        /* impl: */ {
+            $($imp)*
+            $crate::py_class_impl_item! { $class, $py, pub, __init__(&$slf,) $res_type; { $($body)* } [] }
+        }
+        $members $props
+    }};
+    { {  $visibility:vis def __init__ (&$slf:ident) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt
+        /* slots: */ {
+            /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
+        }
+        { $( $imp:item )* }
+        $members:tt $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py $info
+        /* slots: */ {
+            /* type_slots */ [
+                $( $tp_slot_name : $tp_slot_value, )*
+                tp_init: $crate::py_class_init_slot!{$py, $class::__init__ []},
+            ]
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
+        }
+        /* impl: */ {
+            $($imp)*
+            $crate::py_class_impl_item! { $class, $py, $visibility, __init__(&$slf,) $res_type; { $($body)* } [] }
+        }
+        $members $props
+    }};
+    { {   def __init__ (&$slf:ident, $($p:tt)+) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt
+        /* slots: */ {
+            /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
+        }
+        { $( $imp:item )* }
+        $members:tt $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py $info
+        /* slots: */ {
+            /* type_slots */ [
+                $( $tp_slot_name : $tp_slot_value, )*
+                tp_init: $crate::py_argparse_parse_plist_impl!{py_class_init_slot {$py, $class::__init__} [] ($($p)+,)},
+            ]
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
+        }
+        /* impl: */ {
+            $($imp)*
+            $crate::py_argparse_parse_plist_impl!{
+                py_class_impl_item { $class, $py, pub, __init__(&$slf,) $res_type; { $($body)* } }
+                [] ($($p)+,)
+            }
+        }
+        $members $props
+    }};
+    { {  $visibility:vis def __init__ (&$slf:ident, $($p:tt)+) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt
+        /* slots: */ {
+            /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
+        }
+        { $( $imp:item )* }
+        $members:tt $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py $info
+        /* slots: */ {
+            /* type_slots */ [
+                $( $tp_slot_name : $tp_slot_value, )*
+                tp_init: $crate::py_argparse_parse_plist_impl!{py_class_init_slot {$py, $class::__init__} [] ($($p)+,)},
+            ]
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
+        }
+        /* impl: */ {
+            $($imp)*
+            $crate::py_argparse_parse_plist_impl!{
+                py_class_impl_item { $class, $py, $visibility, __init__(&$slf,) $res_type; { $($body)* } }
+                [] ($($p)+,)
+            }
+        }
+        $members $props
+    }};
+    { { $(#[doc=$doc:expr])*  def __init_subclass__ ($cls:ident) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt $slots:tt
+        { $( $imp:item )* }
+        { $( $member_name:ident = $member_expr:expr; )* } $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py $info $slots
+        /* impl: */ {
+            $($imp)*
+            $crate::py_class_impl_item! { $class, $py, pub, __init_subclass__($cls: &$crate::PyType,) $res_type; { $($body)* } [] }
+        }
+        /* members: */ {
+            $( $member_name = $member_expr; )*
+            __init_subclass__ = $crate::py_class_class_method!{$py, $class::__init_subclass__, { concat!($($doc, "\n"),*) } []};
+        } $props
+    }};
+    { { $(#[doc=$doc:expr])* $visibility:vis def __init_subclass__ ($cls:ident) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt $slots:tt
+        { $( $imp:item )* }
+        { $( $member_name:ident = $member_expr:expr; )* } $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py $info $slots
+        /* impl: */ {
+            $($imp)*
+            $crate::py_class_impl_item! { $class, $py, $visibility, __init_subclass__($cls: &$crate::PyType,) $res_type; { $($body)* } [] }
+        }
+        /* members: */ {
+            $( $member_name = $member_expr; )*
+            __init_subclass__ = $crate::py_class_class_method!{$py, $class::__init_subclass__, { concat!($($doc, "\n"),*) } []};
+        } $props
+    }};
+    { { $(#[doc=$doc:expr])*  def __init_subclass__ ($cls:ident, $($p:tt)+) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt $slots:tt
+        { $( $imp:item )* }
+        { $( $member_name:ident = $member_expr:expr; )* } $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py $info $slots
+        /* impl: */ {
+            $($imp)*
+            $crate::py_argparse_parse_plist_impl!{
+                py_class_impl_item { $class, $py, pub, __init_subclass__($cls: &$crate::PyType,) $res_type; { $($body)* } }
+                [] ($($p)+,)
+            }
+        }
+        /* members: */ {
+            $( $member_name = $member_expr; )*
+            __init_subclass__ = $crate::py_argparse_parse_plist_impl!{py_class_class_method {$py, $class::__init_subclass__, { concat!($($doc, "\n"),*) }} [] ($($p)+,)};
+        } $props
+    }};
+    { { $(#[doc=$doc:expr])* $visibility:vis def __init_subclass__ ($cls:ident, $($p:tt)+) -> $res_type:ty { $( $body:tt )* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt $slots:tt
+        { $( $imp:item )* }
+        { $( $member_name:ident = $member_expr:expr; )* } $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py $info $slots
+        /* impl: */ {
+            $($imp)*
+            $crate::py_argparse_parse_plist_impl!{
+                py_class_impl_item { $class, $py, $visibility, __init_subclass__($cls: &$crate::PyType,) $res_type; { $($body)* } }
+                [] ($($p)+,)
+            }
+        }
+        /* members: */ {
+            $( $member_name = $member_expr; )*
+            __init_subclass__ = $crate::py_argparse_parse_plist_impl!{py_class_class_method {$py, $class::__init_subclass__, { concat!($($doc, "\n"),*) }} [] ($($p)+,)};
+        } $props
+    }};
 
     { { def __instancecheck__ $($tail:tt)* } $( $stuff:tt )* } => {
         $crate::py_error! { "__instancecheck__ is not supported by py_class! yet." }
@@ -1564,7 +2502,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1577,7 +2515,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_invert: $crate::py_class_unary_slot!($class::__invert__, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1594,7 +2532,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1607,7 +2545,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_or: $crate::py_class_binary_slot!($class::__ior__, [Option<&$other_name>], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1620,7 +2558,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1633,7 +2571,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_or: $crate::py_class_binary_slot!($class::__ior__, [&$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1646,7 +2584,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1659,7 +2597,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_or: $crate::py_class_binary_slot!($class::__ior__, [$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1680,7 +2618,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1693,7 +2631,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_rshift: $crate::py_class_binary_slot!($class::__irshift__, [Option<&$other_name>], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1706,7 +2644,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1719,7 +2657,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_rshift: $crate::py_class_binary_slot!($class::__irshift__, [&$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1732,7 +2670,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1745,7 +2683,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_rshift: $crate::py_class_binary_slot!($class::__irshift__, [$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1762,7 +2700,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1775,7 +2713,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_subtract: $crate::py_class_binary_slot!($class::__isub__, [Option<&$other_name>], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1788,7 +2726,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1801,7 +2739,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_subtract: $crate::py_class_binary_slot!($class::__isub__, [&$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1814,7 +2752,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1827,7 +2765,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_subtract: $crate::py_class_binary_slot!($class::__isub__, [$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1843,7 +2781,7 @@ macro_rules! py_class_impl {
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1855,7 +2793,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_iter: $crate::py_class_unary_slot!($class::__iter__, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1872,7 +2810,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1885,7 +2823,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_true_divide: $crate::py_class_binary_slot!($class::__itruediv__, [Option<&$other_name>], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1898,7 +2836,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1911,7 +2849,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_true_divide: $crate::py_class_binary_slot!($class::__itruediv__, [&$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1924,7 +2862,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1937,7 +2875,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_true_divide: $crate::py_class_binary_slot!($class::__itruediv__, [$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1954,7 +2892,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1967,7 +2905,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_xor: $crate::py_class_binary_slot!($class::__ixor__, [Option<&$other_name>], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -1980,7 +2918,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -1993,7 +2931,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_xor: $crate::py_class_binary_slot!($class::__ixor__, [&$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2006,7 +2944,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2019,7 +2957,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_inplace_xor: $crate::py_class_binary_slot!($class::__ixor__, [$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2041,7 +2979,7 @@ macro_rules! py_class_impl {
             $type_slots:tt $as_number:tt
             /* as_sequence */ [ $( $sq_slot_name:ident : $sq_slot_value:expr, )* ]
             /* as_mapping */ [ $( $mp_slot_name:ident : $mp_slot_value:expr, )* ]
-            $setdelitem:tt
+            $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2058,7 +2996,7 @@ macro_rules! py_class_impl {
                 $( $mp_slot_name : $mp_slot_value, )*
                 mp_length: Some($crate::_detail::ffi::PySequence_Size),
             ]
-            $setdelitem
+            $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2079,7 +3017,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2092,7 +3030,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_lshift: $crate::py_class_binary_numeric_slot!($class::__lshift__),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2108,9 +3046,35 @@ macro_rules! py_class_impl {
     { { def __lt__ $($tail:tt)* } $( $stuff:tt )* } => {
         $crate::py_error! { "__lt__ is not supported by py_class! use __richcmp__ instead." }
     };
+    { { def __matmul__($left:ident, $right:ident) -> $res_type:ty { $($body:tt)* } $($tail:tt)* }
+        $class:ident $py:ident $info:tt
+        /* slots: */ {
+            $type_slots:tt
+            /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
+        }
+        { $( $imp:item )* }
+        $members:tt $props:tt
+    } => { $crate::py_class_impl! {
+        { $($tail)* }
+        $class $py $info
+        /* slots: */ {
+            $type_slots
+            /* as_number */ [
+                $( $nb_slot_name : $nb_slot_value, )*
+                nb_matrix_multiply: $crate::py_class_binary_numeric_slot!($class::__matmul__),
+            ]
+            $as_sequence $as_mapping $setdelitem $vectorcall
+        }
+        /* impl: */ {
+            $($imp)*
+            $crate::py_class_impl_item! { $class, $py, pub, __matmul__() $res_type; { $($body)* } [ { $left : &$crate::PyObject = {} } { $right : &$crate::PyObject = {} } ] }
+        }
+        $members $props
+    }};
 
     { { def __matmul__ $($tail:tt)* } $( $stuff:tt )* } => {
-        $crate::py_error! { "__matmul__ is not supported by py_class! yet." }
+        $crate::py_error! { "Invalid signature for binary numeric operator __matmul__" }
     };
 
     { { def __mod__ $($tail:tt)* } $( $stuff:tt )* } => {
@@ -2121,7 +3085,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2134,7 +3098,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_multiply: $crate::py_class_binary_numeric_slot!($class::__mul__),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2155,7 +3119,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2168,7 +3132,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_negative: $crate::py_class_unary_slot!($class::__neg__, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2184,7 +3148,7 @@ macro_rules! py_class_impl {
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2194,9 +3158,9 @@ macro_rules! py_class_impl {
         /* slots: */ {
             /* type_slots */ [
                 $( $tp_slot_name : $tp_slot_value, )*
-                tp_new: $crate::py_class_wrap_newfunc!{$class::__new__ []},
+                tp_new: $crate::py_class_wrap_newfunc!{$py, $class::__new__ []},
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2208,7 +3172,7 @@ macro_rules! py_class_impl {
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2218,9 +3182,9 @@ macro_rules! py_class_impl {
         /* slots: */ {
             /* type_slots */ [
                 $( $tp_slot_name : $tp_slot_value, )*
-                tp_new: $crate::py_class_wrap_newfunc!{$class::__new__ []},
+                tp_new: $crate::py_class_wrap_newfunc!{$py, $class::__new__ []},
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2232,7 +3196,7 @@ macro_rules! py_class_impl {
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2242,9 +3206,9 @@ macro_rules! py_class_impl {
         /* slots: */ {
             /* type_slots */ [
                 $( $tp_slot_name : $tp_slot_value, )*
-                tp_new: $crate::py_argparse_parse_plist_impl!{py_class_wrap_newfunc {$class::__new__} [] ($($p)+,)},
+                tp_new: $crate::py_argparse_parse_plist_impl!{py_class_wrap_newfunc {$py, $class::__new__} [] ($($p)+,)},
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2259,7 +3223,7 @@ macro_rules! py_class_impl {
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2269,9 +3233,9 @@ macro_rules! py_class_impl {
         /* slots: */ {
             /* type_slots */ [
                 $( $tp_slot_name : $tp_slot_value, )*
-                tp_new: $crate::py_argparse_parse_plist_impl!{py_class_wrap_newfunc {$class::__new__} [] ($($p)+,)},
+                tp_new: $crate::py_argparse_parse_plist_impl!{py_class_wrap_newfunc {$py, $class::__new__} [] ($($p)+,)},
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2286,7 +3250,7 @@ macro_rules! py_class_impl {
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2298,7 +3262,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_iternext: $crate::py_class_unary_slot!($class::__next__, *mut $crate::_detail::ffi::PyObject, $crate::py_class::slots::IterNextResultConverter),
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2319,7 +3283,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2332,7 +3296,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_or: $crate::py_class_binary_numeric_slot!($class::__or__),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2349,7 +3313,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2362,7 +3326,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_positive: $crate::py_class_unary_slot!($class::__pos__, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2398,7 +3362,7 @@ macro_rules! py_class_impl {
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2410,7 +3374,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_repr: $crate::py_class_unary_slot!($class::__repr__, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PythonObjectCallbackConverter::<$crate::PyString>(std::marker::PhantomData)),
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2430,7 +3394,7 @@ macro_rules! py_class_impl {
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2442,7 +3406,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_richcompare: $crate::py_class_richcompare_slot!($class::__richcmp__, [Option<&$other_name>], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2454,7 +3418,7 @@ macro_rules! py_class_impl {
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2466,7 +3430,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_richcompare: $crate::py_class_richcompare_slot!($class::__richcmp__, [&$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2478,7 +3442,7 @@ macro_rules! py_class_impl {
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2490,7 +3454,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_richcompare: $crate::py_class_richcompare_slot!($class::__richcmp__, [$other_name], *mut $crate::_detail::ffi::PyObject, $crate::_detail::PyObjectCallbackConverter),
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2523,10 +3487,6 @@ macro_rules! py_class_impl {
         $crate::py_error! { "Reflected numeric operator __ror__ is not supported by py_class! Use __or__ instead!" }
     };
 
-    { { def __round__ $($tail:tt)* } $( $stuff:tt )* } => {
-        $crate::py_error! { "__round__ is not supported by py_class! yet." }
-    };
-
     { { def __rpow__ $($tail:tt)* } $( $stuff:tt )* } => {
         $crate::py_error! { "Reflected numeric operator __rpow__ is not supported by py_class! Use __pow__ instead!" }
     };
@@ -2539,7 +3499,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2552,7 +3512,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_rshift: $crate::py_class_binary_numeric_slot!($class::__rshift__),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2592,6 +3552,7 @@ macro_rules! py_class_impl {
                 sdi_setitem: {},
                 sdi_delitem: $sdi_delitem_slot_value:tt,
             ]
+            $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2604,6 +3565,7 @@ macro_rules! py_class_impl {
                 sdi_setitem: { $crate::py_class_ternary_slot!($class::__setitem__, [Option<&$key_name>], $value_name, $crate::_detail::libc::c_int, $crate::py_class::slots::UnitCallbackConverter) },
                 sdi_delitem: $sdi_delitem_slot_value,
             ]
+            $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2619,6 +3581,7 @@ macro_rules! py_class_impl {
                 sdi_setitem: {},
                 sdi_delitem: $sdi_delitem_slot_value:tt,
             ]
+            $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2631,6 +3594,7 @@ macro_rules! py_class_impl {
                 sdi_setitem: { $crate::py_class_ternary_slot!($class::__setitem__, [&$key_name], $value_name, $crate::_detail::libc::c_int, $crate::py_class::slots::UnitCallbackConverter) },
                 sdi_delitem: $sdi_delitem_slot_value,
             ]
+            $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2646,6 +3610,7 @@ macro_rules! py_class_impl {
                 sdi_setitem: {},
                 sdi_delitem: $sdi_delitem_slot_value:tt,
             ]
+            $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2658,6 +3623,7 @@ macro_rules! py_class_impl {
                 sdi_setitem: { $crate::py_class_ternary_slot!($class::__setitem__, [$key_name], $value_name, $crate::_detail::libc::c_int, $crate::py_class::slots::UnitCallbackConverter) },
                 sdi_delitem: $sdi_delitem_slot_value,
             ]
+            $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2673,7 +3639,7 @@ macro_rules! py_class_impl {
         $class:ident $py:ident $info:tt
         /* slots: */ {
             /* type_slots */ [ $( $tp_slot_name:ident : $tp_slot_value:expr, )* ]
-            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2685,7 +3651,7 @@ macro_rules! py_class_impl {
                 $( $tp_slot_name : $tp_slot_value, )*
                 tp_str: $crate::py_class_unary_slot!($class::__str__, *mut $crate::_detail::ffi::PyObject, $crate::_detail::PythonObjectCallbackConverter::<$crate::PyString>(std::marker::PhantomData)),
             ]
-            $as_number $as_sequence $as_mapping $setdelitem
+            $as_number $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2702,7 +3668,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2715,7 +3681,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_subtract: $crate::py_class_binary_numeric_slot!($class::__sub__),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -2732,6 +3698,10 @@ macro_rules! py_class_impl {
         $crate::py_error! { "__subclasscheck__ is not supported by py_class! yet." }
     };
 
+    { { def __subclasshook__ $($tail:tt)* } $( $stuff:tt )* } => {
+        $crate::py_error! { "__subclasshook__ must be declared as a @classmethod." }
+    };
+
     { { def __truediv__ $($tail:tt)* } $( $stuff:tt )* } => {
         $crate::py_error! { "__truediv__ is not supported by py_class! yet." }
     };
@@ -2740,7 +3710,7 @@ macro_rules! py_class_impl {
         /* slots: */ {
             $type_slots:tt
             /* as_number */ [ $( $nb_slot_name:ident : $nb_slot_value:expr, )* ]
-            $as_sequence:tt $as_mapping:tt $setdelitem:tt
+            $as_sequence:tt $as_mapping:tt $setdelitem:tt $vectorcall:tt
         }
         { $( $imp:item )* }
         $members:tt $props:tt
@@ -2753,7 +3723,7 @@ macro_rules! py_class_impl {
                 $( $nb_slot_name : $nb_slot_value, )*
                 nb_xor: $crate::py_class_binary_numeric_slot!($class::__xor__),
             ]
-            $as_sequence $as_mapping $setdelitem
+            $as_sequence $as_mapping $setdelitem $vectorcall
         }
         /* impl: */ {
             $($imp)*
@@ -18,14 +18,14 @@
 
 use libc::{c_char, c_int};
 use std::ffi::CString;
-use std::{isize, mem, ptr};
+use std::{isize, mem, panic, ptr};
 
 use crate::conversion::ToPyObject;
 use crate::err::{PyErr, PyResult};
 use crate::exc;
 use crate::ffi;
 use crate::function::CallbackConverter;
-use crate::objects::PyObject;
+use crate::objects::{PyObject, PyType};
 use crate::py_class::CompareOp;
 use crate::python::{Python, PythonObject};
 use crate::Py_hash_t;
@@ -35,17 +35,19 @@ use crate::Py_hash_t;
 macro_rules! py_class_type_object_static_init {
     ($class_name:ident,
      $gc:tt,
+     $subclassable:tt,
     /* slots: */ {
         /* type_slots */  [ $( $slot_name:ident : $slot_value:expr, )* ]
         $as_number:tt
         $as_sequence:tt
         $as_mapping:tt
         $setdelitem:tt
+        $vectorcall:tt
     }) => (
         $crate::_detail::ffi::PyTypeObject {
             $( $slot_name : $slot_value, )*
             tp_dealloc: Some($crate::py_class::slots::tp_dealloc_callback::<$class_name>),
-            tp_flags: $crate::py_class_type_object_flags!($gc),
+            tp_flags: $crate::py_class_type_object_flags!($gc, $subclassable),
             tp_traverse: $crate::py_class_tp_traverse!($class_name, $gc),
             ..
             $crate::_detail::ffi::PyTypeObject_INIT
@@ -53,19 +55,33 @@ macro_rules! py_class_type_object_static_init {
     );
 }
 
+#[macro_export]
+#[doc(hidden)]
+macro_rules! py_class_tp_doc {
+    () => {
+        0 as *const $crate::_detail::libc::c_char
+    };
+    ($($doc:expr),+) => {
+        concat!($($doc, "\n",)+ "\0").as_ptr() as *const $crate::_detail::libc::c_char
+    };
+}
+
 #[macro_export]
 #[doc(hidden)]
 macro_rules! py_class_type_object_flags {
+    ($gc:tt, /* subclassable: */ true) => {
+        $crate::py_class_type_object_flags!($gc, false) | $crate::_detail::ffi::Py_TPFLAGS_BASETYPE
+    };
     (/* gc: */ {
         /* traverse_proc: */ None,
         /* traverse_data: */ [ /*name*/ ]
-    }) => {
+    }, /* subclassable: */ false) => {
         $crate::py_class::slots::TPFLAGS_DEFAULT
     };
     (/* gc: */ {
         $traverse_proc: expr,
         $traverse_data: tt
-    }) => {
+    }, /* subclassable: */ false) => {
         $crate::py_class::slots::TPFLAGS_DEFAULT | $crate::_detail::ffi::Py_TPFLAGS_HAVE_GC
     };
 }
@@ -73,8 +89,10 @@ macro_rules! py_class_type_object_flags {
 #[cfg(feature = "python27-sys")]
 pub const TPFLAGS_DEFAULT: ::libc::c_long = ffi::Py_TPFLAGS_DEFAULT | ffi::Py_TPFLAGS_CHECKTYPES;
 
+// Before Python 3.8, `tp_finalize` is ignored unless Py_TPFLAGS_HAVE_FINALIZE is set.
 #[cfg(feature = "python3-sys")]
-pub const TPFLAGS_DEFAULT: ::libc::c_ulong = ffi::Py_TPFLAGS_DEFAULT;
+pub const TPFLAGS_DEFAULT: ::libc::c_ulong =
+    ffi::Py_TPFLAGS_DEFAULT | ffi::Py_TPFLAGS_HAVE_FINALIZE;
 
 #[macro_export]
 #[doc(hidden)]
@@ -87,16 +105,24 @@ macro_rules! py_class_type_object_dynamic_init {
             $as_sequence:tt
             $as_mapping:tt
             $setdelitem:tt
+            $vectorcall:tt
         }
         $props:tt
+        $base_type_object:tt
+        $type_name:tt
     ) => {
         unsafe {
             $type_object.init_ob_type(&mut $crate::_detail::ffi::PyType_Type);
-            $type_object.tp_name =
-                $crate::py_class::slots::build_tp_name($module_name, stringify!($class));
+            $type_object.tp_name = $crate::py_class::slots::build_tp_name(
+                $crate::py_class_type_name!(module, $module_name, $type_name),
+                $crate::py_class_type_name!(qualname, $class, $type_name),
+            );
             $type_object.tp_basicsize = <$class as $crate::py_class::BaseObject>::size()
                 as $crate::_detail::ffi::Py_ssize_t;
+            $type_object.tp_itemsize = <$class as $crate::py_class::VarSizedObject>::item_size()
+                as $crate::_detail::ffi::Py_ssize_t;
         }
+        $crate::py_class_type_object_base!($py, $type_object, $base_type_object);
         // call slot macros outside of unsafe block
         *(unsafe { &mut $type_object.tp_as_sequence }) =
             $crate::py_class_as_sequence!($as_sequence);
@@ -106,6 +132,140 @@ macro_rules! py_class_type_object_dynamic_init {
     };
 }
 
+#[macro_export]
+#[doc(hidden)]
+macro_rules! py_class_type_object_base {
+    ($py:ident, $type_object:ident, {}) => {};
+    ($py:ident, $type_object:ident, { @metaclass }) => {{
+        $crate::py_class::slots::set_metaclass_base($py, unsafe { &mut $type_object })?;
+    }};
+    ($py:ident, $type_object:ident, { $base_expr:expr }) => {{
+        let base: $crate::PyType = $base_expr;
+        $crate::py_class::slots::set_base_type($py, unsafe { &mut $type_object }, base)?;
+    }};
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! py_class_type_name {
+    // module name: the one declared with `@module`, or the name of the module
+    // the class is being added to
+    (module, $module_name:ident, { module: {}, qualname: $qualname:tt }) => {
+        $module_name
+    };
+    (module, $module_name:ident, { module: { $module_expr:expr }, qualname: $qualname:tt }) => {
+        Some($module_expr)
+    };
+    // qualified name: the one declared with `@qualname`, or the name of the Rust struct
+    (qualname, $class:ident, { module: $module:tt, qualname: {} }) => {
+        stringify!($class)
+    };
+    (qualname, $class:ident, { module: $module:tt, qualname: { $qualname_expr:expr } }) => {
+        $qualname_expr
+    };
+}
+
+/// Sets `tp_base` for a class declared with `@base`.
+///
+/// Instances of the class are laid out as a plain `PyObject` followed by the data fields,
+/// so the base type must not add any instance data of its own.
+/// The type object is static, so the base type must be static as well.
+pub fn set_base_type(
+    py: Python,
+    type_object: &mut ffi::PyTypeObject,
+    base: PyType,
+) -> PyResult<()> {
+    let base_ptr = base.as_type_ptr();
+    unsafe {
+        if (*base_ptr).tp_flags & ffi::Py_TPFLAGS_BASETYPE == 0 {
+            return Err(PyErr::new::<exc::TypeError, _>(
+                py,
+                format!("type '{}' is not an acceptable base type", base.name(py)),
+            ));
+        }
+        // A static type object cannot refer to a base type that might be deallocated.
+        if (*base_ptr).tp_flags & ffi::Py_TPFLAGS_HEAPTYPE != 0 {
+            return Err(PyErr::new::<exc::TypeError, _>(
+                py,
+                format!(
+                    "base type '{}' must be a static type, not a class defined in Python",
+                    base.name(py)
+                ),
+            ));
+        }
+        if (*base_ptr).tp_basicsize as usize != mem::size_of::<ffi::PyObject>()
+            || (*base_ptr).tp_itemsize != 0
+        {
+            return Err(PyErr::new::<exc::TypeError, _>(
+                py,
+                format!(
+                    "base type '{}' must not have instance data (use `__slots__ = ()`)",
+                    base.name(py)
+                ),
+            ));
+        }
+    }
+    // Unlike subclasses of `object`, the class would otherwise inherit `tp_new`
+    // from the base type, which would create instances with uninitialized data.
+    if type_object.tp_new.is_none() {
+        type_object.tp_new = Some(tp_new_disallowed);
+    }
+    // The type object is static, so the reference to the base type is never released.
+    type_object.tp_base = base.into_object().steal_ptr() as *mut ffi::PyTypeObject;
+    Ok(())
+}
+
+/// Makes a class declared with `@metaclass` a subclass of `type`.
+///
+/// Instances of the class are type objects, laid out and deallocated by `type` itself,
+/// so the class cannot have data fields or garbage collector integration of its own.
+pub fn set_metaclass_base(py: Python, type_object: &mut ffi::PyTypeObject) -> PyResult<()> {
+    if type_object.tp_basicsize as usize != mem::size_of::<ffi::PyObject>()
+        || type_object.tp_itemsize != 0
+    {
+        return Err(PyErr::new::<exc::TypeError, _>(
+            py,
+            "a metaclass defined with py_class! cannot have data fields",
+        ));
+    }
+    if type_object.tp_traverse.is_some() || type_object.tp_clear.is_some() {
+        return Err(PyErr::new::<exc::TypeError, _>(
+            py,
+            "a metaclass defined with py_class! cannot define __traverse__ or __clear__",
+        ));
+    }
+    // Inherit the layout, deallocation and GC support of `type` in PyType_Ready().
+    // `tp_new` is inherited from `type` as well, unless the class defines `__new__`.
+    type_object.tp_basicsize = 0;
+    type_object.tp_dealloc = None;
+    type_object.tp_base = unsafe { &mut ffi::PyType_Type };
+    Ok(())
+}
+
+/// Checks that `cls`, the type passed to `create_instance_of()`, is `base` or a subclass of it.
+pub fn check_subtype(py: Python, cls: &PyType, base: &PyType) -> PyResult<()> {
+    if cls.is_subtype_of(py, base) {
+        Ok(())
+    } else {
+        Err(PyErr::new::<exc::TypeError, _>(
+            py,
+            format!("{} is not a subtype of {}", cls.name(py), base.name(py)),
+        ))
+    }
+}
+
+unsafe extern "C" fn tp_new_disallowed(
+    subtype: *mut ffi::PyTypeObject,
+    _args: *mut ffi::PyObject,
+    _kwds: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    let py = Python::assume_gil_acquired();
+    let ty = PyType::from_type_ptr(py, subtype);
+    PyErr::new::<exc::TypeError, _>(py, format!("cannot create '{}' instances", ty.name(py)))
+        .restore(py);
+    ptr::null_mut()
+}
+
 pub fn build_tp_name(module_name: Option<&str>, type_name: &str) -> *mut c_char {
     let name = match module_name {
         Some(module_name) => CString::new(format!("{}.{}", module_name, type_name)),
@@ -121,15 +281,68 @@ where
 {
     let guard = crate::function::AbortOnDrop("Cannot unwind out of tp_dealloc");
     let py = Python::assume_gil_acquired();
-    let r = T::dealloc(py, obj);
+    // Run `__del__` before destroying the object (PEP 442).
+    // For instances of subclasses defined in Python, `subtype_dealloc` already did this.
+    #[cfg(Py_3_4)]
+    {
+        if (*ffi::Py_TYPE(obj)).tp_finalize.is_some()
+            && ffi::PyType_HasFeature(ffi::Py_TYPE(obj), ffi::Py_TPFLAGS_HEAPTYPE) == 0
+            && ffi::PyObject_CallFinalizerFromDealloc(obj) < 0
+        {
+            // The finalizer resurrected the object.
+            mem::forget(guard);
+            return;
+        }
+    }
+    super::enter_dealloc(py);
+    T::dealloc(py, obj);
+    super::leave_dealloc(py);
     mem::forget(guard);
-    r
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! py_class_finalize_slot {
+    ($class:ident :: $f:ident) => {{
+        unsafe extern "C" fn wrap_finalize(slf: *mut $crate::_detail::ffi::PyObject) {
+            const LOCATION: &'static str = concat!(stringify!($class), ".", stringify!($f), "()");
+            $crate::py_class::slots::handle_finalize(LOCATION, slf, |py| {
+                let slf =
+                    $crate::PyObject::from_borrowed_ptr(py, slf).unchecked_cast_into::<$class>();
+                let ret = slf.$f(py);
+                $crate::PyDrop::release_ref(slf, py);
+                ret
+            })
+        }
+        Some(wrap_finalize)
+    }};
+}
+
+/// Calls `f` as the `tp_finalize` of `slf`.
+///
+/// `tp_finalize` cannot report errors and must leave the current exception unchanged,
+/// so exceptions raised by `f` are passed to `sys.unraisablehook` (or printed,
+/// before Python 3.8) and the previously set exception is restored afterwards.
+#[doc(hidden)]
+pub unsafe fn handle_finalize<F>(location: &str, slf: *mut ffi::PyObject, f: F)
+where
+    F: FnOnce(Python) -> PyResult<()>,
+    F: panic::UnwindSafe,
+{
+    let mut ptype = ptr::null_mut();
+    let mut pvalue = ptr::null_mut();
+    let mut ptraceback = ptr::null_mut();
+    ffi::PyErr_Fetch(&mut ptype, &mut pvalue, &mut ptraceback);
+    if crate::function::handle_callback(location, UnitCallbackConverter, f) < 0 {
+        ffi::PyErr_WriteUnraisable(slf);
+    }
+    ffi::PyErr_Restore(ptype, pvalue, ptraceback);
 }
 
 #[macro_export]
 #[doc(hidden)]
 macro_rules! py_class_wrap_newfunc {
-    ($class:ident :: $f:ident [ $( { $pname:ident : $ptype:ty = $detail:tt } )* ]) => {{
+    ($py:ident, $class:ident :: $f:ident [ $( { $pname:ident : $ptype:ty = $detail:tt } )* ]) => {{
         unsafe extern "C" fn wrap_newfunc(
             cls: *mut $crate::_detail::ffi::PyTypeObject,
             args: *mut $crate::_detail::ffi::PyObject,
@@ -140,6 +353,9 @@ macro_rules! py_class_wrap_newfunc {
             $crate::_detail::handle_callback(
                 LOCATION, $crate::_detail::PyObjectCallbackConverter,
                 |py| {
+                    // Default value expressions may refer to the `py` token of the class.
+                    #[allow(unused_variables)]
+                    let $py = py;
                     $crate::py_argparse_raw!(py, Some(LOCATION), args, kwargs,
                         [ $( { $pname : $ptype = $detail } )* ]
                         {
@@ -666,7 +882,7 @@ impl CallbackConverter<bool> for BoolConverter {
 #[macro_export]
 #[doc(hidden)]
 macro_rules! py_class_call_slot {
-    ($class:ident :: $f:ident [ $( { $pname:ident : $ptype:ty = $detail:tt } )* ]) => {{
+    ($py:ident, $class:ident :: $f:ident [ $( { $pname:ident : $ptype:ty = $detail:tt } )* ]) => {{
         unsafe extern "C" fn wrap_call(
             slf: *mut $crate::_detail::ffi::PyObject,
             args: *mut $crate::_detail::ffi::PyObject,
@@ -677,6 +893,9 @@ macro_rules! py_class_call_slot {
             $crate::_detail::handle_callback(
                 LOCATION, $crate::_detail::PyObjectCallbackConverter,
                 |py| {
+                    // Default value expressions may refer to the `py` token of the class.
+                    #[allow(unused_variables)]
+                    let $py = py;
                     $crate::py_argparse_raw!(py, Some(LOCATION), args, kwargs,
                         [ $( { $pname : $ptype = $detail } )* ]
                         {
@@ -691,6 +910,146 @@ macro_rules! py_class_call_slot {
     }}
 }
 
+/// The type of a PEP 590 vectorcall entry point.
+///
+/// This is `ffi::vectorcallfunc`, but is also available on Python versions without vectorcall
+/// support, so that the code generated by `py_class!` does not depend on the Python version.
+pub type VectorcallFunc = unsafe extern "C" fn(
+    callable: *mut ffi::PyObject,
+    args: *const *mut ffi::PyObject,
+    nargsf: libc::size_t,
+    kwnames: *mut ffi::PyObject,
+) -> *mut ffi::PyObject;
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! py_class_vectorcall_slot {
+    ($py:ident, $class:ident :: $f:ident [ $( { $pname:ident : $ptype:ty = $detail:tt } )* ]) => {{
+        unsafe extern "C" fn wrap_vectorcall(
+            slf: *mut $crate::_detail::ffi::PyObject,
+            args: *const *mut $crate::_detail::ffi::PyObject,
+            nargsf: $crate::_detail::libc::size_t,
+            kwnames: *mut $crate::_detail::ffi::PyObject)
+        -> *mut $crate::_detail::ffi::PyObject
+        {
+            const LOCATION: &'static str = concat!(stringify!($class), ".", stringify!($f), "()");
+            $crate::_detail::handle_callback(
+                LOCATION, $crate::_detail::PyObjectCallbackConverter,
+                |py| {
+                    // Default value expressions may refer to the `py` token of the class.
+                    #[allow(unused_variables)]
+                    let $py = py;
+                    $crate::py_argparse_vectorcall!(py, Some(LOCATION), args, nargsf, kwnames,
+                        [ $( { $pname : $ptype = $detail } )* ]
+                        {
+                            let slf = $crate::PyObject::from_borrowed_ptr(py, slf).unchecked_cast_into::<$class>();
+                            let ret = slf.$f(py $(, $pname )* );
+                            $crate::PyDrop::release_ref(slf, py);
+                            ret
+                        })
+                })
+        }
+        wrap_vectorcall as $crate::py_class::slots::VectorcallFunc
+    }}
+}
+
+// Instances of classes with a vectorcall entry point store a pointer to it
+// after all other fixed-size data.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! py_class_vectorcall_size {
+    ($size:expr, { $type_slots:tt $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+        /* vectorcall */ [ vc_vectorcall: {}, ]
+    }) => {
+        $size
+    };
+    ($size:expr, { $type_slots:tt $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+        /* vectorcall */ [ vc_vectorcall: { $vectorcall:expr }, ]
+    }) => {
+        $crate::py_class::data_new_size::<$crate::py_class::slots::VectorcallFunc>($size)
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! py_class_vectorcall_init {
+    ($py:ident, $obj:expr, $size:expr, { $type_slots:tt $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+        /* vectorcall */ [ vc_vectorcall: {}, ]
+    }) => {};
+    ($py:ident, $obj:expr, $size:expr, { $type_slots:tt $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+        /* vectorcall */ [ vc_vectorcall: { $vectorcall:expr }, ]
+    }) => {
+        $crate::py_class::data_init::<$crate::py_class::slots::VectorcallFunc>(
+            $py,
+            $obj,
+            $crate::py_class::data_offset::<$crate::py_class::slots::VectorcallFunc>($size),
+            $vectorcall,
+        );
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! py_class_type_object_vectorcall {
+    ($type_object:ident, $size:expr, { $type_slots:tt $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+        /* vectorcall */ [ vc_vectorcall: {}, ]
+    }) => {};
+    ($type_object:ident, $size:expr, { $type_slots:tt $as_number:tt $as_sequence:tt $as_mapping:tt $setdelitem:tt
+        /* vectorcall */ [ vc_vectorcall: { $vectorcall:expr }, ]
+    }) => {
+        $crate::py_class::slots::set_vectorcall_offset(
+            unsafe { &mut $type_object },
+            $crate::py_class::data_offset::<$crate::py_class::slots::VectorcallFunc>($size),
+        );
+    };
+}
+
+/// Enables the vectorcall protocol for instances of the type,
+/// which store their vectorcall entry point at `offset`.
+/// Vectorcall is used on Python 3.9 and later; older versions only use `tp_call`.
+pub fn set_vectorcall_offset(type_object: &mut ffi::PyTypeObject, offset: usize) {
+    #[cfg(all(Py_3_9, not(Py_LIMITED_API)))]
+    {
+        type_object.tp_vectorcall_offset = offset as ffi::Py_ssize_t;
+        type_object.tp_flags |= ffi::Py_TPFLAGS_HAVE_VECTORCALL;
+    }
+    #[cfg(not(all(Py_3_9, not(Py_LIMITED_API))))]
+    {
+        let _ = (type_object, offset);
+    }
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! py_class_init_slot {
+    ($py:ident, $class:ident :: $f:ident [ $( { $pname:ident : $ptype:ty = $detail:tt } )* ]) => {{
+        unsafe extern "C" fn wrap_init(
+            slf: *mut $crate::_detail::ffi::PyObject,
+            args: *mut $crate::_detail::ffi::PyObject,
+            kwargs: *mut $crate::_detail::ffi::PyObject)
+        -> $crate::_detail::libc::c_int
+        {
+            const LOCATION: &'static str = concat!(stringify!($class), ".", stringify!($f), "()");
+            $crate::_detail::handle_callback(
+                LOCATION, $crate::py_class::slots::UnitCallbackConverter,
+                |py| {
+                    // Default value expressions may refer to the `py` token of the class.
+                    #[allow(unused_variables)]
+                    let $py = py;
+                    $crate::py_argparse_raw!(py, Some(LOCATION), args, kwargs,
+                        [ $( { $pname : $ptype = $detail } )* ]
+                        {
+                            let slf = $crate::PyObject::from_borrowed_ptr(py, slf).unchecked_cast_into::<$class>();
+                            let ret = slf.$f(py $(, $pname )* );
+                            $crate::PyDrop::release_ref(slf, py);
+                            ret
+                        })
+                })
+        }
+        Some(wrap_init)
+    }}
+}
+
 /// Used as implementation in the `sq_item` slot to forward calls to the `mp_subscript` slot.
 pub unsafe extern "C" fn sq_item(
     obj: *mut ffi::PyObject,
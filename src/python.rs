@@ -22,7 +22,9 @@ use std::marker::PhantomData;
 
 use crate::err::{self, PyErr, PyResult};
 use crate::ffi;
-use crate::objects::{PyBool, PyDict, PyModule, PyObject, PyType};
+use crate::objects::{
+    CompileMode, PyBool, PyCode, PyDict, PyFrame, PyList, PyModule, PyObject, PyType,
+};
 use crate::pythonrun::GILGuard;
 
 /// Marker type that indicates that the GIL is currently held.
@@ -213,9 +215,41 @@ impl<'p> Python<'p> {
     /// Retrieve Python instance under the assumption that the GIL is already acquired at this point,
     /// and stays acquired for the lifetime `'p`.
     ///
+    /// This is intended for callbacks that CPython invokes with the GIL held,
+    /// such as custom type slots (`tp_dealloc`, `tp_traverse`, ...) or functions
+    /// registered with `Py_AddPendingCall`.
+    /// In those contexts, calling `Python::acquire_gil()` would be unnecessary,
+    /// and `assume_gil_acquired()` obtains a token without touching the thread state.
+    /// Note that C-level signal handlers do *not* run with the GIL held;
+    /// use `Py_AddPendingCall` or the Python `signal` module instead.
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that:
+    ///
+    ///  * the current thread holds the GIL when this function is called, and
+    ///  * the GIL is not released while the returned token (or any value derived from it)
+    ///    is in use -- in particular, the token must not outlive the callback it was obtained in.
+    ///
     /// Because the output lifetime `'p` is not connected to any input parameter,
     /// care must be taken that the compiler infers an appropriate lifetime for `'p`
-    /// when calling this function.
+    /// when calling this function. Keep the token local to the function that created it.
+    ///
+    /// Violating these requirements allows unsynchronized access to Python objects,
+    /// which is undefined behavior.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::Python;
+    ///
+    /// // A callback that CPython only calls with the GIL held.
+    /// unsafe extern "C" fn callback(_arg: *mut std::os::raw::c_void) -> std::os::raw::c_int {
+    ///     let py = Python::assume_gil_acquired();
+    ///     let _none = py.None();
+    ///     0
+    /// }
+    /// # fn main() {}
+    /// ```
     #[inline]
     pub unsafe fn assume_gil_acquired() -> Python<'p> {
         Python(PhantomData)
@@ -225,30 +259,76 @@ impl<'p> Python<'p> {
     ///
     /// If the Python runtime is not already initialized, this function will initialize it.
     /// See [prepare_freethreaded_python()](fn.prepare_freethreaded_python.html) for details.
+    ///
+    /// Any reference counts left pending by `PyObject`s that were dropped on threads
+    /// not holding the GIL are released at this point.
     #[inline]
     pub fn acquire_gil() -> GILGuard {
         GILGuard::acquire()
     }
 
     /// Temporarily releases the GIL, thus allowing other Python threads to run.
+    ///
+    /// The GIL is reacquired when `f` returns, and also if `f` panics.
+    ///
+    /// Use this within `py_fn!` functions and `py_class!` methods that perform long-running
+    /// Rust computations. `f` cannot use `py` or any other Python objects, as the GIL is not held.
+    /// Errors can't be converted into a `PyErr` without the GIL either: return them from `f`,
+    /// and convert them after `allow_threads` returns.
+    /// The [release_gil!](macro.release_gil.html) macro provides a shorthand for both.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{exc, py_fn, ObjectProtocol, PyErr, PyResult, Python};
+    ///
+    /// fn parse_sum(py: Python, text: String) -> PyResult<u64> {
+    ///     let sum = py.allow_threads(|| -> Result<u64, std::num::ParseIntError> {
+    ///         let mut sum = 0;
+    ///         for word in text.split_whitespace() {
+    ///             sum += word.parse::<u64>()?;
+    ///         }
+    ///         Ok(sum)
+    ///     });
+    ///     sum.map_err(|e| PyErr::new::<exc::ValueError, _>(py, e.to_string()))
+    /// }
+    ///
+    /// fn main() {
+    ///     let gil = Python::acquire_gil();
+    ///     let py = gil.python();
+    ///     let f = py_fn!(py, parse_sum(text: String));
+    ///     let v: u64 = f.call(py, ("1 2 3",), None).unwrap().extract(py).unwrap();
+    ///     assert_eq!(v, 6);
+    ///     assert!(f.call(py, ("1 x",), None).is_err());
+    /// }
+    /// ```
     pub fn allow_threads<T, F>(self, f: F) -> T
     where
         F: Send + FnOnce() -> T,
     {
+        // Reacquires the GIL when dropped, even during unwinding.
+        struct RestoreGuard(*mut ffi::PyThreadState);
+
+        impl Drop for RestoreGuard {
+            fn drop(&mut self) {
+                unsafe { ffi::PyEval_RestoreThread(self.0) }
+            }
+        }
+
         // The `Send` bound on the closure prevents the user from
         // transferring the `Python` token into the closure.
-        unsafe {
-            let save = ffi::PyEval_SaveThread();
-            let result = f();
-            ffi::PyEval_RestoreThread(save);
-            result
-        }
+        let _guard = RestoreGuard(unsafe { ffi::PyEval_SaveThread() });
+        f()
     }
 
     /// Evaluates a Python expression in the given context and returns the result.
+    /// This wraps `PyRun_String` with `Py_eval_input`.
     ///
     /// If `globals` is `None`, it defaults to Python module `__main__`.
     /// If `locals` is `None`, it defaults to the value of `globals`.
+    /// As with the Python `eval()` builtin, if `globals` does not contain `__builtins__`,
+    /// a reference to the builtins module's dictionary is inserted.
+    ///
+    /// Code that fails to compile results in a `SyntaxError`.
     pub fn eval(
         self,
         code: &str,
@@ -259,9 +339,14 @@ impl<'p> Python<'p> {
     }
 
     /// Executes one or more Python statements in the given context.
+    /// This wraps `PyRun_String` with `Py_file_input`.
     ///
     /// If `globals` is `None`, it defaults to Python module `__main__`.
     /// If `locals` is `None`, it defaults to the value of `globals`.
+    /// As with the Python `exec()` builtin, if `globals` does not contain `__builtins__`,
+    /// a reference to the builtins module's dictionary is inserted.
+    ///
+    /// Code that fails to compile results in a `SyntaxError`.
     pub fn run(
         self,
         code: &str,
@@ -286,30 +371,98 @@ impl<'p> Python<'p> {
         locals: Option<&PyDict>,
     ) -> PyResult<PyObject> {
         let code = CString::new(code).unwrap();
+        let (globals, locals) = self.run_namespaces(globals, locals)?;
 
         unsafe {
-            let mptr = ffi::PyImport_AddModule("__main__\0".as_ptr() as *const _);
-
-            if mptr.is_null() {
-                return Err(PyErr::fetch(self));
-            }
+            let res_ptr =
+                ffi::PyRun_StringFlags(code.as_ptr(), start, globals, locals, std::ptr::null_mut());
 
-            let mdict = ffi::PyModule_GetDict(mptr);
+            err::result_from_owned_ptr(self, res_ptr)
+        }
+    }
 
+    /// Resolves the `globals` and `locals` namespaces used to run code.
+    ///
+    /// If `globals` is `None`, it defaults to the dict of Python module `__main__`.
+    /// If `locals` is `None`, it defaults to the value of `globals`.
+    /// If `globals` does not contain `__builtins__`, it is inserted.
+    ///
+    /// The returned pointers are borrowed.
+    pub(crate) fn run_namespaces(
+        self,
+        globals: Option<&PyDict>,
+        locals: Option<&PyDict>,
+    ) -> PyResult<(*mut ffi::PyObject, *mut ffi::PyObject)> {
+        unsafe {
             let globals = match globals {
                 Some(g) => g.as_ptr(),
-                None => mdict,
+                None => {
+                    let mptr = ffi::PyImport_AddModule("__main__\0".as_ptr() as *const _);
+                    if mptr.is_null() {
+                        return Err(PyErr::fetch(self));
+                    }
+                    ffi::PyModule_GetDict(mptr)
+                }
             };
 
+            let builtins_key = "__builtins__\0".as_ptr() as *const _;
+            if ffi::PyDict_GetItemString(globals, builtins_key).is_null() {
+                let builtins = ffi::PyEval_GetBuiltins();
+                if ffi::PyDict_SetItemString(globals, builtins_key, builtins) < 0 {
+                    return Err(PyErr::fetch(self));
+                }
+            }
+
             let locals = match locals {
                 Some(l) => l.as_ptr(),
                 None => globals,
             };
 
-            let res_ptr =
-                ffi::PyRun_StringFlags(code.as_ptr(), start, globals, locals, std::ptr::null_mut());
+            Ok((globals, locals))
+        }
+    }
 
-            err::result_from_owned_ptr(self, res_ptr)
+    /// Compiles Python source code into a code object that can be run repeatedly.
+    /// This is equivalent to the Python expression `compile(code, filename, mode)`.
+    ///
+    /// `filename` is used in tracebacks and error messages.
+    /// Code that fails to compile results in a `SyntaxError`.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::{CompileMode, PyDict, Python};
+    ///
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let code = py.compile("a + b", "<expr>", CompileMode::Eval).unwrap();
+    /// let locals = PyDict::new(py);
+    /// locals.set_item(py, "a", 40).unwrap();
+    /// locals.set_item(py, "b", 2).unwrap();
+    /// let v: i32 = code.run(py, None, Some(&locals)).unwrap().extract(py).unwrap();
+    /// assert_eq!(v, 42);
+    /// ```
+    pub fn compile(self, code: &str, filename: &str, mode: CompileMode) -> PyResult<PyCode> {
+        PyCode::compile(self, code, filename, mode)
+    }
+
+    /// Gets the frame of the Python code that is currently executing in this thread,
+    /// or `None` if no Python code is executing (e.g. when called directly from Rust,
+    /// and not from a Rust function invoked by Python).
+    ///
+    /// This wraps `PyEval_GetFrame()`, and is equivalent to `sys._getframe()`.
+    /// Within a Rust function called from Python, this is the frame of the caller;
+    /// use `PyFrame::back()` to walk further up the stack.
+    pub fn current_frame(self) -> Option<PyFrame> {
+        unsafe {
+            let frame = ffi::PyEval_GetFrame();
+            if frame.is_null() {
+                None
+            } else {
+                Some(
+                    PyObject::from_borrowed_ptr(self, frame as *mut ffi::PyObject)
+                        .unchecked_cast_into(),
+                )
+            }
         }
     }
 
@@ -337,12 +490,23 @@ impl<'p> Python<'p> {
     }
 
     /// Gets the Python builtin value `NotImplemented`.
+    ///
+    /// The returned `PyObject` owns a new reference, so it can be returned directly
+    /// from binary operators and `__richcmp__` in `py_class!` (`Ok(py.NotImplemented())`)
+    /// to make Python try the other operand instead.
     #[allow(non_snake_case)] // the Python keyword starts with uppercase
     #[inline]
     pub fn NotImplemented(self) -> PyObject {
         unsafe { PyObject::from_borrowed_ptr(self, ffi::Py_NotImplemented()) }
     }
 
+    /// Gets the Python builtin value `Ellipsis` (written as `...` in Python 3).
+    #[allow(non_snake_case)] // the Python keyword starts with uppercase
+    #[inline]
+    pub fn Ellipsis(self) -> PyObject {
+        unsafe { PyObject::from_borrowed_ptr(self, ffi::Py_Ellipsis()) }
+    }
+
     /// Gets the Python type object for type T.
     pub fn get_type<T>(self) -> PyType
     where
@@ -355,6 +519,93 @@ impl<'p> Python<'p> {
     pub fn import(self, name: &str) -> PyResult<PyModule> {
         PyModule::import(self, name)
     }
+
+    /// Imports the module `module` and returns its attribute `attr`.
+    /// This is equivalent to the Python statement `from module import attr`,
+    /// except that `attr` is not imported as a submodule if it is missing.
+    ///
+    /// Modules are only executed on the first import; later calls find them in
+    /// `sys.modules`, so this is cheap enough to call whenever the value is needed.
+    ///
+    /// # Example
+    /// ```
+    /// use cpython::Python;
+    ///
+    /// let gil = Python::acquire_gil();
+    /// let py = gil.python();
+    /// let pi: f64 = py.module_attr("math", "pi").unwrap().extract(py).unwrap();
+    /// assert_eq!(pi, std::f64::consts::PI);
+    /// ```
+    pub fn module_attr(self, module: &str, attr: &str) -> PyResult<PyObject> {
+        self.import(module)?.get(self, attr)
+    }
+
+    /// Import the Python module with the specified name, together with the names in `fromlist`.
+    /// See [PyModule::import_from()](struct.PyModule.html#method.import_from).
+    pub fn import_from(self, name: &str, fromlist: &[&str]) -> PyResult<PyModule> {
+        PyModule::import_from(self, name, fromlist)
+    }
+
+    /// Returns the exception that is currently being handled, if any.
+    /// This corresponds to the Python function `sys.exc_info()`.
+    ///
+    /// When Rust code is called from within an `except` block (or a function called from it),
+    /// this returns the exception caught by that block.
+    /// The returned `PyErr` holds new references to the exception type, value and traceback;
+    /// the interpreter state is neither cleared nor modified.
+    ///
+    /// This is unrelated to the error indicator queried by `PyErr::occurred()`:
+    /// an exception that was raised but not yet caught is not "being handled".
+    pub fn current_exception(self) -> Option<PyErr> {
+        PyErr::handled(self)
+    }
+
+    /// Returns the type of the error currently set in the Python interpreter's global state,
+    /// without clearing it. Returns `None` if no error is set.
+    ///
+    /// This wraps `PyErr_Occurred()`; it does not fetch or normalize the error,
+    /// so it is cheap enough to branch on the type of an error before deciding
+    /// whether to take it with `PyErr::fetch()`.
+    pub fn err_occurred(self) -> Option<PyType> {
+        unsafe {
+            PyObject::from_borrowed_ptr_opt(self, ffi::PyErr_Occurred())
+                .map(|ty| ty.unchecked_cast_into::<PyType>())
+        }
+    }
+
+    /// Returns the reference count of `obj`, as reported by `sys.getrefcount(obj)`
+    /// minus the temporary reference held by that call.
+    ///
+    /// See the [diagnostics](diagnostics/index.html) module for more leak diagnostics.
+    pub fn getrefcount(self, obj: &PyObject) -> PyResult<isize> {
+        crate::diagnostics::getrefcount(self, obj)
+    }
+
+    /// Returns the list of objects directly referred to by `obj`.
+    /// This is equivalent to the Python expression `gc.get_referents(obj)`.
+    ///
+    /// Only objects that support garbage collection are reported.
+    /// This is intended as a diagnostic aid (e.g. for tracking down reference leaks),
+    /// and may be slow.
+    pub fn get_referents(self, obj: &PyObject) -> PyResult<PyList> {
+        let gc = self.import("gc")?;
+        Ok(gc
+            .call(self, "get_referents", (obj,), None)?
+            .cast_into(self)?)
+    }
+
+    /// Returns the list of objects that directly refer to `obj`.
+    /// This is equivalent to the Python expression `gc.get_referrers(obj)`.
+    ///
+    /// Only objects that support garbage collection are reported.
+    /// This walks all objects tracked by the garbage collector, so it is slow
+    /// and should only be used for debugging.
+    pub fn get_referrers(self, obj: &PyObject) -> PyResult<PyList> {
+        let gc = self.import("gc")?;
+        Ok(gc
+            .call(self, "get_referrers", (obj,), None)?
+            .cast_into(self)?)
+    }
 }
 
 impl<'p> std::fmt::Debug for PythonObjectDowncastError<'p> {
@@ -365,7 +616,25 @@ impl<'p> std::fmt::Debug for PythonObjectDowncastError<'p> {
 
 #[cfg(test)]
 mod test {
-    use crate::{PyDict, Python};
+    use super::ToPythonPointer;
+    use crate::{exc, ObjectProtocol, PyBool, PyClone, PyDict, PyList, Python, PythonObject};
+
+    #[test]
+    fn test_singletons() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        for (value, name) in &[
+            (py.None(), "None"),
+            (py.True().into_object(), "True"),
+            (py.False().into_object(), "False"),
+            (py.NotImplemented(), "NotImplemented"),
+            (py.Ellipsis(), "Ellipsis"),
+            (PyBool::get(py, true).into_object(), "True"),
+        ] {
+            let builtin = py.eval(name, None, None).unwrap();
+            assert_eq!(value.as_ptr(), builtin.as_ptr(), "{}", name);
+        }
+    }
 
     #[test]
     fn test_eval() {
@@ -398,5 +667,133 @@ mod test {
             .extract(py)
             .unwrap();
         assert_eq!(v, 2);
+
+        // Builtins are available with a fresh global namespace, too
+        let g = PyDict::new(py);
+        let v: i32 = py
+            .eval("len([1, 2, 3])", Some(&g), None)
+            .unwrap()
+            .extract(py)
+            .unwrap();
+        assert_eq!(v, 3);
+        assert!(g.contains(py, "__builtins__").unwrap());
+
+        // Compilation errors are reported as SyntaxError
+        let err = py.eval("1 +", None, None).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::SyntaxError>()));
+    }
+
+    #[test]
+    fn test_run() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let g = PyDict::new(py);
+        py.run(
+            "x = sum(range(5))\ndef f(y):\n    return x + y",
+            Some(&g),
+            None,
+        )
+        .unwrap();
+        let v: i32 = g.get_item(py, "x").unwrap().extract(py).unwrap();
+        assert_eq!(v, 10);
+        let v: i32 = py
+            .eval("f(5)", Some(&g), None)
+            .unwrap()
+            .extract(py)
+            .unwrap();
+        assert_eq!(v, 15);
+
+        let err = py.run("def f(:\n    pass", Some(&g), None).unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::SyntaxError>()));
+    }
+
+    #[test]
+    fn test_assume_gil_acquired() {
+        let gil = Python::acquire_gil();
+        let _py = gil.python();
+        let py = unsafe { Python::assume_gil_acquired() };
+        assert!(
+            py.eval("1 + 1", None, None)
+                .unwrap()
+                .extract::<i32>(py)
+                .unwrap()
+                == 2
+        );
+    }
+
+    #[test]
+    fn test_allow_threads_panic() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            py.allow_threads(|| panic!("in allow_threads"))
+        }));
+        assert!(result.is_err());
+        // The GIL was reacquired while unwinding.
+        let v: i32 = py.eval("1 + 1", None, None).unwrap().extract(py).unwrap();
+        assert_eq!(v, 2);
+    }
+
+    #[test]
+    fn test_get_referents() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+
+        let item = PyDict::new(py).into_object();
+        let list = PyList::new(py, &[item.clone_ref(py)]).into_object();
+
+        let referents = py.get_referents(&list).unwrap();
+        assert!(referents.iter(py).any(|obj| obj == item));
+
+        let referrers = py.get_referrers(&item).unwrap();
+        assert!(referrers.iter(py).any(|obj| obj == list));
+    }
+
+    #[test]
+    fn test_import_from() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let top = py.import_from("xml.dom", &[]).unwrap();
+        assert_eq!(top.name(py).unwrap(), "xml");
+
+        let dom = py.import_from("xml.dom", &["minidom"]).unwrap();
+        assert_eq!(dom.name(py).unwrap(), "xml.dom");
+        // The submodule in the fromlist was imported and bound on the package.
+        let sys = py.import("sys").unwrap();
+        assert!(sys
+            .get(py, "modules")
+            .unwrap()
+            .get_item(py, "xml.dom.minidom")
+            .is_ok());
+        assert!(dom.get(py, "minidom").is_ok());
+
+        let err = py.import_from("no_such_module_abc", &["x"]).err().unwrap();
+        assert!(err.matches(py, py.get_type::<crate::exc::ImportError>()));
+    }
+
+    #[test]
+    fn test_reload() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let module = py.import("colorsys").unwrap();
+        module.add(py, "added_later", 1).unwrap();
+        let reloaded = module.reload(py).unwrap();
+        assert_eq!(reloaded.as_object(), module.as_object());
+        // Reloading re-executes the module code in the existing namespace.
+        assert!(reloaded.get(py, "rgb_to_hsv").is_ok());
+        assert!(reloaded.get(py, "added_later").is_ok());
+    }
+
+    #[test]
+    fn test_module_attr() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let sep: String = py.module_attr("os", "sep").unwrap().extract(py).unwrap();
+        assert!(sep == "/" || sep == "\\");
+        let err = py.module_attr("os", "no_such_attr").unwrap_err();
+        assert!(err.matches(py, py.get_type::<crate::exc::AttributeError>()));
+        let err = py.module_attr("no_such_module_abc", "x").unwrap_err();
+        assert!(err.matches(py, py.get_type::<crate::exc::ImportError>()));
     }
 }
@@ -16,12 +16,43 @@
 // OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
 // DEALINGS IN THE SOFTWARE.
 
-use std::{marker, rc, sync};
+use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+use std::{marker, ptr, rc, thread};
 
 use crate::ffi;
 use crate::python::Python;
 
-static START: sync::Once = sync::Once::new();
+/// Set once `prepare_freethreaded_python()` has verified or performed
+/// the initialization; cleared by `finalize_python()`.
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+/// Set once `finalize_python()` has shut down the interpreter.
+static FINALIZED: AtomicBool = AtomicBool::new(false);
+
+/// Spin lock serializing initialization and finalization.
+/// (`std::sync::Mutex` cannot be used in a static on our minimum Rust version,
+/// and `std::sync::Once` cannot be reset after finalization.)
+static LIFECYCLE_LOCK: AtomicBool = AtomicBool::new(false);
+
+struct LifecycleLock;
+
+impl LifecycleLock {
+    fn acquire() -> LifecycleLock {
+        while LIFECYCLE_LOCK
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            thread::yield_now();
+        }
+        LifecycleLock
+    }
+}
+
+impl Drop for LifecycleLock {
+    fn drop(&mut self) {
+        LIFECYCLE_LOCK.store(false, Ordering::Release);
+    }
+}
 
 /// Prepares the use of Python in a free-threaded context.
 ///
@@ -44,12 +75,21 @@ static START: sync::Once = sync::Once::new();
 /// When writing an extension module, the `py_module_initializer!` macro
 /// will ensure that Python threading is initialized.
 ///
+/// After [finalize_python()](fn.finalize_python.html), calling this function
+/// initializes a fresh interpreter.
 pub fn prepare_freethreaded_python() {
+    if INITIALIZED.load(Ordering::Acquire) {
+        return;
+    }
     // Protect against race conditions when Python is not yet initialized
     // and multiple threads concurrently call 'prepare_freethreaded_python()'.
     // Note that we do not protect against concurrent initialization of the Python runtime
     // by other users of the Python C API.
-    START.call_once(|| unsafe {
+    let _lock = LifecycleLock::acquire();
+    if INITIALIZED.load(Ordering::Relaxed) {
+        return;
+    }
+    unsafe {
         if ffi::Py_IsInitialized() != 0 {
             // If Python is already initialized, we expect Python threading to also be initialized,
             // as we can't make the existing Python main thread acquire the GIL.
@@ -58,7 +98,9 @@ pub fn prepare_freethreaded_python() {
             #[cfg(feature = "python27-sys")]
             {
                 // If Python isn't initialized yet, we expect that Python threading isn't initialized either.
-                assert!(ffi::PyEval_ThreadsInitialized() == 0);
+                // (Unless we finalized Python before: Python 2 keeps the threading state across
+                // Py_Finalize() and Py_Initialize().)
+                assert!(FINALIZED.load(Ordering::Relaxed) || ffi::PyEval_ThreadsInitialized() == 0);
                 // Note: starting with Python 3.2 it's no longer possible to initialize threading
                 // without initializing Python; and in Python 3.7 PyEval_ThreadsInitialized() started
                 // misbehaving when Python was not initialized yet.
@@ -78,11 +120,62 @@ pub fn prepare_freethreaded_python() {
             // Note that the PyThreadState returned by PyEval_SaveThread is also held in TLS by the Python runtime,
             // and will be restored by PyGILState_Ensure.
         }
-    });
+    }
+    INITIALIZED.store(true, Ordering::Release);
+}
+
+/// Shuts down the Python interpreter.
+///
+/// This is mainly useful for test harnesses and embedding applications that want to
+/// control the interpreter lifecycle. Pending decrefs of objects dropped without
+/// the GIL are released before finalization. Afterwards, the next call to
+/// [prepare_freethreaded_python()](fn.prepare_freethreaded_python.html) (or to
+/// `Python::acquire_gil()`, unless the `no-auto-initialize` feature is enabled)
+/// initializes a new interpreter.
+///
+/// Returns `false` if Python reported an error while flushing buffered data
+/// (Python 3.6 and later). If the interpreter is not initialized, this function has no effect.
+///
+/// # Safety
+/// * No thread may hold the GIL or use the Python runtime concurrently with or after
+///   this call (until Python is re-initialized).
+/// * All Rust-held Python objects (`PyObject` and friends, including those stored in
+///   `static`s or `GILProtected`) must have been dropped or forgotten before this call.
+///   They are not tracked; dropping one after finalization is undefined behavior.
+/// * Type objects created by `py_class!` are cached per Rust type, and are not valid in
+///   a re-initialized interpreter. Classes must not be used again after finalization.
+/// * Some extension modules (e.g. numpy) do not support being loaded into more than one
+///   interpreter per process.
+pub unsafe fn finalize_python() -> bool {
+    let _lock = LifecycleLock::acquire();
+    if ffi::Py_IsInitialized() == 0 {
+        return true;
+    }
+    // Py_Finalize() must be called with the GIL held; the thread state is destroyed
+    // by the finalization, so the GIL is not released afterwards.
+    ffi::PyGILState_Ensure();
+    drain_pending_decrefs(Python::assume_gil_acquired());
+    crate::function::clear_cached_objects(Python::assume_gil_acquired());
+    #[cfg(Py_3_6)]
+    let ok = ffi::Py_FinalizeEx() == 0;
+    #[cfg(not(Py_3_6))]
+    let ok = {
+        ffi::Py_Finalize();
+        true
+    };
+    INITIALIZED.store(false, Ordering::Release);
+    FINALIZED.store(true, Ordering::Relaxed);
+    ok
 }
 
 /// RAII type that represents the Global Interpreter Lock acquisition.
 ///
+/// `GILGuard` uses `PyGILState_Ensure()`, so it can be used on any thread,
+/// including threads created outside of Python (e.g. by a server embedding Python).
+/// On such a thread, the first `GILGuard` creates a Python thread state and the last one
+/// to be dropped destroys it again. Use a [ThreadStateGuard](struct.ThreadStateGuard.html)
+/// to keep the thread state alive across multiple GIL acquisitions.
+///
 /// # Example
 /// ```
 /// use cpython::Python;
@@ -117,10 +210,82 @@ impl GILGuard {
             crate::pythonrun::prepare_freethreaded_python();
         }
         let gstate = unsafe { ffi::PyGILState_Ensure() }; // acquire GIL
-        GILGuard {
+        let guard = GILGuard {
             gstate,
             no_send: marker::PhantomData,
+        };
+        drain_pending_decrefs(guard.python());
+        guard
+    }
+
+    /// Retrieves the marker type that proves that the GIL was acquired.
+    #[inline]
+    pub fn python(&self) -> Python<'_> {
+        unsafe { Python::assume_gil_acquired() }
+    }
+}
+
+/// RAII type that registers the current thread with the Python interpreter
+/// and holds the GIL.
+///
+/// A thread created outside of Python (e.g. by a server embedding Python) has no Python
+/// thread state. Each time such a thread acquires the GIL with `Python::acquire_gil()` while
+/// no other `GILGuard` exists on it, Python creates a fresh thread state, and destroys it when
+/// the GIL is released. This loses per-thread Python state (`threading.local` values,
+/// the thread's `contextvars` context, its `threading.current_thread()` object) between
+/// acquisitions, and makes each acquisition more expensive.
+///
+/// Like `GILGuard`, a `ThreadStateGuard` calls `PyGILState_Ensure()` when created and
+/// `PyGILState_Release()` when dropped, and provides the `Python` token via `python()`.
+/// Create one at the start of such a thread (e.g. in a worker thread of a thread pool)
+/// and keep it for the lifetime of the thread; use `py.allow_threads()` to release the GIL
+/// while doing work that doesn't need Python. The thread state stays alive until the guard
+/// is dropped, so code within `allow_threads()` that calls `Python::acquire_gil()` reuses it.
+///
+/// Use `Python::acquire_gil()` for short-lived acquisitions, and on threads created by
+/// Python itself or the thread that initialized Python: these already have a long-lived
+/// thread state.
+///
+/// # Example
+/// ```
+/// use cpython::{ObjectProtocol, ThreadStateGuard};
+///
+/// std::thread::spawn(|| {
+///     let guard = ThreadStateGuard::register();
+///     let py = guard.python();
+///     let local = py.eval("__import__('threading').local()", None, None).unwrap();
+///     for i in 0..3 {
+///         py.allow_threads(|| {
+///             // ... work without the GIL ...
+///         });
+///         local.setattr(py, "counter", i).unwrap();
+///     }
+/// }).join().unwrap();
+/// ```
+#[must_use]
+pub struct ThreadStateGuard {
+    gstate: ffi::PyGILState_STATE,
+    // hack to opt out of Send on stable rust, which doesn't
+    // have negative impls
+    no_send: marker::PhantomData<rc::Rc<()>>,
+}
+
+impl ThreadStateGuard {
+    /// Registers the current thread with the Python interpreter and acquires the GIL.
+    ///
+    /// If the Python runtime is not already initialized, this function will initialize it.
+    /// See [prepare_freethreaded_python()](fn.prepare_freethreaded_python.html) for details.
+    pub fn register() -> ThreadStateGuard {
+        if !cfg!(feature = "no-auto-initialize") {
+            crate::pythonrun::prepare_freethreaded_python();
         }
+        let gstate = unsafe { ffi::PyGILState_Ensure() };
+        let guard = ThreadStateGuard {
+            gstate,
+            no_send: marker::PhantomData,
+        };
+        drain_pending_decrefs(guard.python());
+        guard
     }
 
     /// Retrieves the marker type that proves that the GIL was acquired.
@@ -130,6 +295,68 @@ impl GILGuard {
     }
 }
 
+/// The Drop implementation for ThreadStateGuard releases the GIL, and destroys the
+/// thread state if it was created by `register()`.
+impl Drop for ThreadStateGuard {
+    fn drop(&mut self) {
+        unsafe { ffi::PyGILState_Release(self.gstate) }
+    }
+}
+
+struct PendingDecref {
+    ptr: *mut ffi::PyObject,
+    next: *mut PendingDecref,
+}
+
+/// Intrusive stack of objects that were dropped by threads not holding the GIL.
+///
+/// Pushing is a single compare-and-swap; the whole stack is taken at once
+/// by the next thread that acquires the GIL.
+static PENDING_DECREFS: AtomicPtr<PendingDecref> = AtomicPtr::new(ptr::null_mut());
+
+/// Defers `Py_DECREF(ptr)` until some thread next acquires the GIL.
+///
+/// Safe to call without holding the GIL.
+#[cfg(feature = "python3-sys")]
+pub(crate) fn register_decref(ptr: *mut ffi::PyObject) {
+    let node = Box::into_raw(Box::new(PendingDecref {
+        ptr,
+        next: ptr::null_mut(),
+    }));
+    let mut head = PENDING_DECREFS.load(Ordering::Relaxed);
+    loop {
+        unsafe { (*node).next = head };
+        match PENDING_DECREFS.compare_exchange_weak(
+            head,
+            node,
+            Ordering::Release,
+            Ordering::Relaxed,
+        ) {
+            Ok(_) => return,
+            Err(current) => head = current,
+        }
+    }
+}
+
+/// Releases the references queued by `register_decref`.
+///
+/// Called whenever the GIL is acquired through `GILGuard` and
+/// on entry to every Rust callback invoked by Python.
+pub(crate) fn drain_pending_decrefs(_py: Python) {
+    if PENDING_DECREFS.load(Ordering::Relaxed).is_null() {
+        return;
+    }
+    let mut node = PENDING_DECREFS.swap(ptr::null_mut(), Ordering::Acquire);
+    while !node.is_null() {
+        // Py_DECREF may run arbitrary Python code (`__del__`),
+        // which may itself queue further decrefs; those are simply
+        // left for the next drain.
+        let boxed = unsafe { Box::from_raw(node) };
+        node = boxed.next;
+        unsafe { ffi::Py_DECREF(boxed.ptr) };
+    }
+}
+
 /// Mutex-like wrapper object for data that is protected by the Python GIL.
 ///
 /// # Example
@@ -178,3 +405,60 @@ impl<T> GILProtected<T> {
         self.data
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "python3-sys")]
+mod test {
+    use super::ThreadStateGuard;
+    use crate::diagnostics::getrefcount;
+    use crate::objects::PyList;
+    use crate::python::{PyClone, Python, PythonObject};
+
+    #[test]
+    fn test_drop_without_gil_is_deferred() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let obj = PyList::new(py, &[]).into_object();
+        let before = getrefcount(py, &obj).unwrap();
+        let clone = obj.clone_ref(py);
+        assert_eq!(getrefcount(py, &obj).unwrap(), before + 1);
+
+        // The other thread cannot acquire the GIL while we hold it;
+        // dropping there must neither block nor touch the refcount.
+        std::thread::spawn(move || drop(clone)).join().unwrap();
+        assert_eq!(getrefcount(py, &obj).unwrap(), before + 1);
+
+        // Acquiring the GIL drains the pending queue.
+        let nested = Python::acquire_gil();
+        assert_eq!(getrefcount(nested.python(), &obj).unwrap(), before);
+    }
+
+    #[test]
+    fn test_thread_state_guard_keeps_thread_state() {
+        let run = |code: &str| {
+            let gil = Python::acquire_gil();
+            gil.python().run(code, None, None).unwrap();
+        };
+        let gil = Python::acquire_gil();
+        gil.python().allow_threads(|| {
+            std::thread::spawn(move || {
+                let guard = ThreadStateGuard::register();
+                let py = guard.python();
+                py.run(
+                    "import threading; threading._test_local = threading.local()",
+                    None,
+                    None,
+                )
+                .unwrap();
+                py.allow_threads(|| {
+                    run("import threading; threading._test_local.x = 1");
+                    run("import threading; assert threading._test_local.x == 1");
+                });
+                py.run("import threading; del threading._test_local", None, None)
+                    .unwrap();
+            })
+            .join()
+            .unwrap();
+        });
+    }
+}
@@ -178,6 +178,46 @@ impl<'a, T: ?Sized> PySharedRef<'a, T> {
         Ok(data_ref)
     }
 
+    /// Immutably borrows the wrapped value, raising `RuntimeError` if the value
+    /// is currently mutably borrowed.
+    ///
+    /// Unlike `try_borrow()`, the error can be propagated to Python using `?`.
+    /// This is useful in methods that may be re-entered from Python while the
+    /// value is borrowed, e.g. when calling back into Python code.
+    pub fn borrow_checked(&self) -> PyResult<Ref<'a, T>> {
+        self.try_borrow().map_err(|_| {
+            PyErr::new::<exc::RuntimeError, _>(self.py, "Cannot borrow while mutably borrowed")
+        })
+    }
+
+    /// Mutably borrows the wrapped value, raising `RuntimeError` if the value
+    /// is currently borrowed.
+    ///
+    /// Unlike `try_borrow_mut()`, the error can be propagated to Python using `?`.
+    ///
+    /// ```
+    /// # use cpython::*;
+    /// py_class!(class Counter |py| {
+    ///     @shared data count: u32;
+    ///     data callback: PyObject;
+    ///
+    ///     def increment(&self) -> PyResult<u32> {
+    ///         let mut count = self.count(py).borrow_mut_checked()?;
+    ///         // If the callback calls `increment()` again, the nested call
+    ///         // raises RuntimeError instead of panicking.
+    ///         self.callback(py).call(py, NoArgs, None)?;
+    ///         *count += 1;
+    ///         Ok(*count)
+    ///     }
+    /// });
+    /// # fn main() {}
+    /// ```
+    pub fn borrow_mut_checked(&self) -> PyResult<RefMut<'a, T>> {
+        self.try_borrow_mut().map_err(|_| {
+            PyErr::new::<exc::RuntimeError, _>(self.py, "Cannot mutably borrow while borrowed")
+        })
+    }
+
     /// Creates an immutable reference which is not bound to lifetime.
     ///
     /// # Panics
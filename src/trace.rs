@@ -0,0 +1,260 @@
+// Copyright (c) 2015 Daniel Grunwald
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this
+// software and associated documentation files (the "Software"), to deal in the Software
+// without restriction, including without limitation the rights to use, copy, modify, merge,
+// publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons
+// to whom the Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED,
+// INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR
+// PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE
+// FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
+// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Installing Rust functions as Python trace and profile hooks.
+//!
+//! These wrap `PyEval_SetTrace` and `PyEval_SetProfile`, the C-level equivalents
+//! of `sys.settrace()` and `sys.setprofile()`.
+//! Like those, they only affect the current thread.
+
+use std::cell::RefCell;
+
+use libc::c_int;
+
+use crate::err::{PyErr, PyResult};
+use crate::ffi;
+use crate::function::{boxed_capsule, capsule_data, handle_callback};
+use crate::objects::{exc, PyObject};
+use crate::py_class::slots::UnitCallbackConverter;
+use crate::python::{Python, ToPythonPointer};
+
+/// The kind of event reported to a trace or profile function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceEvent {
+    /// A Python function is called, or a generator is entered.
+    Call,
+    /// An exception was raised.
+    /// The argument is the tuple `(exception, value, traceback)`.
+    Exception,
+    /// A new line of code is about to be executed. Only reported to trace functions.
+    Line,
+    /// A Python function is about to return.
+    /// The argument is the value being returned, or `None` if an exception is propagated.
+    Return,
+    /// A C function is about to be called. The argument is the function object.
+    /// Only reported to profile functions.
+    CCall,
+    /// A C function raised an exception. The argument is the function object.
+    /// Only reported to profile functions.
+    CException,
+    /// A C function has returned. The argument is the function object.
+    /// Only reported to profile functions.
+    CReturn,
+    /// A new opcode is about to be executed (Python 3.7 and later).
+    /// Only reported to trace functions, and only if `f_trace_opcodes` is set on the frame.
+    Opcode,
+}
+
+impl TraceEvent {
+    fn from_what(what: c_int) -> Option<TraceEvent> {
+        match what {
+            ffi::PyTrace_CALL => Some(TraceEvent::Call),
+            ffi::PyTrace_EXCEPTION => Some(TraceEvent::Exception),
+            ffi::PyTrace_LINE => Some(TraceEvent::Line),
+            ffi::PyTrace_RETURN => Some(TraceEvent::Return),
+            ffi::PyTrace_C_CALL => Some(TraceEvent::CCall),
+            ffi::PyTrace_C_EXCEPTION => Some(TraceEvent::CException),
+            ffi::PyTrace_C_RETURN => Some(TraceEvent::CReturn),
+            #[cfg(Py_3_7)]
+            ffi::PyTrace_OPCODE => Some(TraceEvent::Opcode),
+            _ => None,
+        }
+    }
+}
+
+type TraceFn = Box<dyn FnMut(Python, &PyObject, TraceEvent, &PyObject) -> PyResult<()> + Send>;
+
+unsafe extern "C" fn trace_trampoline(
+    obj: *mut ffi::PyObject,
+    frame: *mut ffi::PyFrameObject,
+    what: c_int,
+    arg: *mut ffi::PyObject,
+) -> c_int {
+    let event = match TraceEvent::from_what(what) {
+        Some(event) => event,
+        None => return 0,
+    };
+    handle_callback("trace_trampoline", UnitCallbackConverter, |py| {
+        // Hold a reference for the duration of the call, in case the callback
+        // replaces the trace function (which releases the previous one).
+        let func = PyObject::from_borrowed_ptr(py, obj);
+        let frame = PyObject::from_borrowed_ptr(py, frame as *mut ffi::PyObject);
+        let arg = if arg.is_null() {
+            py.None()
+        } else {
+            PyObject::from_borrowed_ptr(py, arg)
+        };
+        let callback = capsule_data::<RefCell<TraceFn>>(func.as_ptr());
+        let mut callback = match callback.try_borrow_mut() {
+            Ok(f) => f,
+            Err(_) => {
+                return Err(PyErr::new::<exc::RuntimeError, _>(
+                    py,
+                    "Rust trace function called recursively",
+                ))
+            }
+        };
+        (*callback)(py, &frame, event, &arg)
+    })
+}
+
+fn make_trace_func<F>(py: Python, f: F) -> PyObject
+where
+    F: FnMut(Python, &PyObject, TraceEvent, &PyObject) -> PyResult<()> + Send + 'static,
+{
+    let callback: TraceFn = Box::new(f);
+    boxed_capsule(py, RefCell::new(callback))
+}
+
+/// Installs `f` as the trace function of the current thread,
+/// replacing any function installed by `sys.settrace()` or a previous call.
+///
+/// `f` is called with the frame object, the event, and the event's argument
+/// (`None` if the event has no argument); see [TraceEvent](enum.TraceEvent.html).
+/// If `f` returns an error, the error is raised in the traced code.
+///
+/// Unlike `sys.settrace()`, there is no separate local trace function:
+/// `f` receives `Line` events for every frame.
+///
+/// # Example
+/// ```
+/// use cpython::trace::{self, TraceEvent};
+/// use cpython::Python;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let calls = Arc::new(AtomicUsize::new(0));
+/// let counter = calls.clone();
+/// trace::set_trace(py, move |_py, _frame, event, _arg| {
+///     if event == TraceEvent::Call {
+///         counter.fetch_add(1, Ordering::SeqCst);
+///     }
+///     Ok(())
+/// });
+/// py.run("def f(): pass\nf(); f()", None, None).unwrap();
+/// trace::clear_trace(py);
+/// assert!(calls.load(Ordering::SeqCst) >= 2);
+/// ```
+pub fn set_trace<F>(py: Python, f: F)
+where
+    F: FnMut(Python, &PyObject, TraceEvent, &PyObject) -> PyResult<()> + Send + 'static,
+{
+    let func = make_trace_func(py, f);
+    // PyEval_SetTrace keeps its own reference to `func`.
+    unsafe { ffi::PyEval_SetTrace(Some(trace_trampoline), func.as_ptr()) }
+}
+
+/// Removes the trace function of the current thread.
+/// This is equivalent to the Python expression `sys.settrace(None)`.
+pub fn clear_trace(_py: Python) {
+    unsafe { ffi::PyEval_SetTrace(None, std::ptr::null_mut()) }
+}
+
+/// Installs `f` as the profile function of the current thread,
+/// replacing any function installed by `sys.setprofile()` or a previous call.
+///
+/// `f` is called as for [set_trace](fn.set_trace.html), but does not receive
+/// `Line` or `Opcode` events; instead, calls to C functions are reported.
+pub fn set_profile<F>(py: Python, f: F)
+where
+    F: FnMut(Python, &PyObject, TraceEvent, &PyObject) -> PyResult<()> + Send + 'static,
+{
+    let func = make_trace_func(py, f);
+    // PyEval_SetProfile keeps its own reference to `func`.
+    unsafe { ffi::PyEval_SetProfile(Some(trace_trampoline), func.as_ptr()) }
+}
+
+/// Removes the profile function of the current thread.
+/// This is equivalent to the Python expression `sys.setprofile(None)`.
+pub fn clear_profile(_py: Python) {
+    unsafe { ffi::PyEval_SetProfile(None, std::ptr::null_mut()) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TraceEvent;
+    use crate::objectprotocol::ObjectProtocol;
+    use crate::objects::exc;
+    use crate::python::Python;
+    use crate::PyErr;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_set_trace() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        super::set_trace(py, move |py, frame, event, _arg| {
+            let name: String = frame
+                .getattr(py, "f_code")?
+                .getattr(py, "co_name")?
+                .extract(py)?;
+            if name == "traced" {
+                recorded.lock().unwrap().push(event);
+            }
+            Ok(())
+        });
+        py.run(
+            "def traced():\n    x = 1\n    return x\ntraced()",
+            None,
+            None,
+        )
+        .unwrap();
+        super::clear_trace(py);
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.first(), Some(&TraceEvent::Call));
+        assert!(events.contains(&TraceEvent::Line));
+        assert_eq!(events.last(), Some(&TraceEvent::Return));
+    }
+
+    #[test]
+    fn test_set_profile() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        super::set_profile(py, move |_py, _frame, event, _arg| {
+            recorded.lock().unwrap().push(event);
+            Ok(())
+        });
+        py.run("len([1, 2])", None, None).unwrap();
+        super::clear_profile(py);
+
+        let events = events.lock().unwrap();
+        assert!(events.contains(&TraceEvent::CCall));
+        assert!(events.contains(&TraceEvent::CReturn));
+        assert!(!events.contains(&TraceEvent::Line));
+    }
+
+    #[test]
+    fn test_trace_error() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        super::set_trace(py, |py, _frame, _event, _arg| {
+            Err(PyErr::new::<exc::ValueError, _>(py, "stop tracing"))
+        });
+        let err = py.run("def f(): pass\nf()", None, None).unwrap_err();
+        super::clear_trace(py);
+        assert!(err.matches(py, py.get_type::<exc::ValueError>()));
+        py.run("def f(): pass\nf()", None, None).unwrap();
+    }
+}
@@ -0,0 +1,248 @@
+// Copyright (c) 2015 Daniel Grunwald
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of this
+// software and associated documentation files (the "Software"), to deal in the Software
+// without restriction, including without limitation the rights to use, copy, modify, merge,
+// publish, distribute, sublicense, and/or sell copies of the Software, and to permit persons
+// to whom the Software is furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all copies or
+// substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED,
+// INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR
+// PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE
+// FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR
+// OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
+// DEALINGS IN THE SOFTWARE.
+
+//! Controlling the warning filters, and recording warnings.
+//!
+//! These are thin wrappers around the functions of the Python `warnings` module.
+//! Warnings are issued from Rust using [PyErr::warn](../struct.PyErr.html#method.warn).
+//!
+//! Like the `warnings` module itself, the filters are global to the interpreter,
+//! so changing them affects all threads.
+
+use crate::err::PyResult;
+use crate::objectprotocol::ObjectProtocol;
+use crate::objects::{NoArgs, PyDict, PyList, PyObject, PyType};
+use crate::python::Python;
+
+/// The action of a warning filter, see `warnings.simplefilter()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterAction {
+    /// Turn matching warnings into exceptions.
+    Error,
+    /// Never print matching warnings.
+    Ignore,
+    /// Always print matching warnings.
+    Always,
+    /// Print the first occurrence of matching warnings for each location.
+    Default,
+    /// Print the first occurrence of matching warnings for each module.
+    Module,
+    /// Print only the first occurrence of matching warnings.
+    Once,
+}
+
+impl FilterAction {
+    /// Gets the name of the action, as used by the `warnings` module.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FilterAction::Error => "error",
+            FilterAction::Ignore => "ignore",
+            FilterAction::Always => "always",
+            FilterAction::Default => "default",
+            FilterAction::Module => "module",
+            FilterAction::Once => "once",
+        }
+    }
+}
+
+/// Inserts a filter that applies `action` to all warnings of the given category
+/// (or of any category, if `category` is `None`).
+/// This is equivalent to the Python expression `warnings.simplefilter(action, category)`.
+pub fn simplefilter(py: Python, action: FilterAction, category: Option<&PyType>) -> PyResult<()> {
+    let kwargs = PyDict::new(py);
+    if let Some(category) = category {
+        kwargs.set_item(py, "category", category)?;
+    }
+    py.import("warnings")?
+        .call(py, "simplefilter", (action.as_str(),), Some(&kwargs))?;
+    Ok(())
+}
+
+/// Inserts a filter that applies `action` to the warnings whose message matches
+/// the regular expression `message`, issued from a module whose name matches `module`.
+/// An empty `message` or `module` matches everything.
+/// This is equivalent to the Python expression
+/// `warnings.filterwarnings(action, message, category, module)`.
+pub fn filterwarnings(
+    py: Python,
+    action: FilterAction,
+    message: &str,
+    category: Option<&PyType>,
+    module: &str,
+) -> PyResult<()> {
+    let kwargs = PyDict::new(py);
+    kwargs.set_item(py, "message", message)?;
+    kwargs.set_item(py, "module", module)?;
+    if let Some(category) = category {
+        kwargs.set_item(py, "category", category)?;
+    }
+    py.import("warnings")?
+        .call(py, "filterwarnings", (action.as_str(),), Some(&kwargs))?;
+    Ok(())
+}
+
+/// Removes all warning filters, including those set with the `-W` command line option.
+/// This is equivalent to the Python expression `warnings.resetwarnings()`.
+pub fn resetwarnings(py: Python) -> PyResult<()> {
+    py.import("warnings")?
+        .call(py, "resetwarnings", NoArgs, None)?;
+    Ok(())
+}
+
+/// A warning recorded by [CatchWarnings](struct.CatchWarnings.html).
+pub struct RecordedWarning {
+    /// The warning category, e.g. `DeprecationWarning`.
+    pub category: PyType,
+    /// The warning message.
+    pub message: String,
+    /// The file name of the location the warning is attributed to.
+    pub filename: String,
+    /// The line number within `filename`.
+    pub lineno: usize,
+}
+
+/// Records the warnings issued while it is alive.
+///
+/// This is the equivalent of `with warnings.catch_warnings(record=True):
+/// warnings.simplefilter('always')`: on creation, the warning filters are saved
+/// and all warnings are set to be recorded instead of printed.
+/// Dropping the guard restores the saved filters, so any filters changed in the
+/// meantime (e.g. with `simplefilter()`) are reset as well.
+///
+/// Like `warnings.catch_warnings`, this modifies the global state of the `warnings`
+/// module, and is thus not thread-safe: warnings issued by other threads are recorded
+/// as well, and guards that are not dropped in the reverse order of their creation
+/// restore the wrong filters.
+///
+/// # Example
+/// ```
+/// use cpython::warnings::CatchWarnings;
+/// use cpython::{exc, PyErr, Python, PythonObject};
+///
+/// let gil = Python::acquire_gil();
+/// let py = gil.python();
+/// let category = py.get_type::<exc::DeprecationWarning>();
+/// let recorder = CatchWarnings::new(py).unwrap();
+/// PyErr::warn(py, category.as_object(), "old_function() is deprecated", 1).unwrap();
+/// let warnings = recorder.warnings(py).unwrap();
+/// assert_eq!(warnings.len(), 1);
+/// assert!(warnings[0].category == category);
+/// assert_eq!(warnings[0].message, "old_function() is deprecated");
+/// ```
+pub struct CatchWarnings {
+    context: PyObject,
+    log: PyList,
+}
+
+impl CatchWarnings {
+    /// Starts recording warnings.
+    pub fn new(py: Python) -> PyResult<CatchWarnings> {
+        let kwargs = PyDict::new(py);
+        kwargs.set_item(py, "record", true)?;
+        let context = py
+            .import("warnings")?
+            .call(py, "catch_warnings", NoArgs, Some(&kwargs))?;
+        let log = context
+            .call_method(py, "__enter__", NoArgs, None)?
+            .cast_into::<PyList>(py)?;
+        let guard = CatchWarnings { context, log };
+        simplefilter(py, FilterAction::Always, None)?;
+        Ok(guard)
+    }
+
+    /// Gets the warnings recorded so far, oldest first.
+    pub fn warnings(&self, py: Python) -> PyResult<Vec<RecordedWarning>> {
+        let mut result = Vec::with_capacity(self.log.len(py));
+        for warning in self.log.iter(py) {
+            result.push(RecordedWarning {
+                category: warning.getattr(py, "category")?.cast_into(py)?,
+                message: warning
+                    .getattr(py, "message")?
+                    .str(py)?
+                    .to_string(py)?
+                    .into_owned(),
+                filename: warning.getattr(py, "filename")?.extract(py)?,
+                lineno: warning.getattr(py, "lineno")?.extract(py)?,
+            });
+        }
+        Ok(result)
+    }
+}
+
+impl Drop for CatchWarnings {
+    fn drop(&mut self) {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        if let Err(e) = self.context.call_method(py, "__exit__", NoArgs, None) {
+            e.print(py);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{filterwarnings, simplefilter, CatchWarnings, FilterAction};
+    use crate::err::PyErr;
+    use crate::objectprotocol::ObjectProtocol;
+    use crate::objects::exc;
+    use crate::python::{Python, PythonObject};
+
+    // The warning filters are global state, so they are tested in a single test
+    // rather than in tests that could run concurrently.
+    #[test]
+    fn test_catch_warnings_and_filters() {
+        let gil = Python::acquire_gil();
+        let py = gil.python();
+        let deprecation = py.get_type::<exc::DeprecationWarning>();
+        let user = py.get_type::<exc::UserWarning>();
+
+        let recorder = CatchWarnings::new(py).unwrap();
+        PyErr::warn(py, deprecation.as_object(), "first", 1).unwrap();
+        PyErr::warn(py, user.as_object(), "second", 1).unwrap();
+        let warnings = recorder.warnings(py).unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].category == deprecation);
+        assert_eq!(warnings[0].message, "first");
+        assert!(warnings[1].category == user);
+        assert_eq!(warnings[1].message, "second");
+        drop(recorder);
+
+        let warnings_module = py.import("warnings").unwrap();
+        let filter_count = || warnings_module.get(py, "filters").unwrap().len(py).unwrap();
+        let initial_filters = filter_count();
+
+        let recorder = CatchWarnings::new(py).unwrap();
+        simplefilter(py, FilterAction::Error, Some(&deprecation)).unwrap();
+        let err = PyErr::warn(py, deprecation.as_object(), "deprecated", 1).unwrap_err();
+        assert!(err.matches(py, &deprecation));
+        PyErr::warn(py, user.as_object(), "not an error", 1).unwrap();
+
+        filterwarnings(py, FilterAction::Ignore, "ignore.*", None, "").unwrap();
+        PyErr::warn(py, user.as_object(), "ignore me", 1).unwrap();
+        PyErr::warn(py, deprecation.as_object(), "ignore me too", 1).unwrap();
+
+        let warnings = recorder.warnings(py).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].message, "not an error");
+        assert!(filter_count() > initial_filters);
+        drop(recorder);
+
+        // the filters are restored when the recorder is dropped
+        assert_eq!(filter_count(), initial_filters);
+    }
+}
@@ -4,7 +4,7 @@ use cpython::_detail::ffi;
 use cpython::*;
 use std::cell::{Cell, RefCell};
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::{isize, iter, mem};
 
 macro_rules! py_run {
@@ -72,6 +72,25 @@ fn empty_class_in_module() {
     );
 }
 
+py_class!(class RenamedClass |py| {
+    @module "test_module.renamed";
+    @qualname "Renamed";
+});
+
+#[test]
+fn class_with_module_and_qualname() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let module = PyModule::new(py, "test_module.other").unwrap();
+    module.add_class::<RenamedClass>(py).unwrap();
+
+    let ty = module.get(py, "Renamed").unwrap();
+    py_assert!(py, ty, "ty.__name__ == 'Renamed'");
+    py_assert!(py, ty, "ty.__module__ == 'test_module.renamed'");
+    py_assert!(py, ty, "'test_module.renamed.Renamed' in repr(ty)");
+    assert!(module.get(py, "RenamedClass").is_err());
+}
+
 py_class!(class EmptyClassWithNew |py| {
     def __new__(_cls) -> PyResult<EmptyClassWithNew> {
         EmptyClassWithNew::create_instance(py)
@@ -132,6 +151,367 @@ fn new_with_two_args() {
     assert_eq!(*obj._data2(py), 20);
 }
 
+py_class!(class NewWithDefaultFactory |py| {
+    data items: PyList;
+    def __new__(_cls, items: PyList = PyList::new(py, &[])) -> PyResult<NewWithDefaultFactory> {
+        NewWithDefaultFactory::create_instance(py, items)
+    }
+    def add(&self, item: i32) -> PyResult<usize> {
+        self.items(py).append(py, item)?;
+        Ok(self.items(py).len(py))
+    }
+});
+
+#[test]
+fn new_with_default_factory() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let typeobj = py.get_type::<NewWithDefaultFactory>();
+    let obj1 = typeobj
+        .call(py, NoArgs, None)
+        .unwrap()
+        .cast_into::<NewWithDefaultFactory>(py)
+        .unwrap();
+    let obj2 = typeobj
+        .call(py, NoArgs, None)
+        .unwrap()
+        .cast_into::<NewWithDefaultFactory>(py)
+        .unwrap();
+    obj1.add(py, 1).unwrap();
+    assert_eq!(obj1.items(py).len(py), 1);
+    assert_eq!(obj2.items(py).len(py), 0);
+
+    let list = PyList::new(py, &[]);
+    let obj3 = typeobj
+        .call(py, (list.clone_ref(py),), None)
+        .unwrap()
+        .cast_into::<NewWithDefaultFactory>(py)
+        .unwrap();
+    obj3.add(py, 2).unwrap();
+    assert_eq!(list.len(py), 1);
+}
+
+py_class!(class Subclassable |py| {
+    @subclassable;
+    data value: i32;
+    data dropped: TestDropCall;
+    def __new__(cls, value: i32) -> PyResult<Subclassable> {
+        Subclassable::create_instance_of(py, cls, value, TestDropCall {
+            drop_called: Arc::new(AtomicBool::new(false)),
+        })
+    }
+    def get_value(&self) -> PyResult<i32> {
+        Ok(*self.value(py))
+    }
+    def describe(&self) -> PyResult<String> {
+        Ok(format!("value={}", self.value(py)))
+    }
+});
+
+#[test]
+fn subclass_in_python() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let d = PyDict::new(py);
+    d.set_item(py, "Subclassable", py.get_type::<Subclassable>())
+        .unwrap();
+    py.run(
+        "
+class Sub(Subclassable):
+    def __init__(self, value):
+        self.doubled = value * 2
+    def describe(self):
+        return 'sub ' + super(Sub, self).describe()
+",
+        Some(&d),
+        None,
+    )
+    .unwrap();
+
+    py_assert!(py, d, "type(d['Sub'](21)) is d['Sub']");
+    py_assert!(py, d, "isinstance(d['Sub'](21), d['Subclassable'])");
+    py_assert!(py, d, "d['Sub'](21).get_value() == 21");
+    py_assert!(py, d, "d['Sub'](21).doubled == 42");
+    py_assert!(py, d, "d['Sub'](21).describe() == 'sub value=21'");
+    py_assert!(py, d, "type(d['Subclassable'](1)) is d['Subclassable']");
+
+    // instances of the subclass can be used from Rust
+    let sub = d.get_item(py, "Sub").unwrap();
+    let obj = sub.call(py, (5,), None).unwrap();
+    let inst = obj.cast_as::<Subclassable>(py).unwrap();
+    assert_eq!(*inst.value(py), 5);
+    let drop_called = inst.dropped(py).drop_called.clone();
+
+    // data is dropped, and the reference held on the subclass is released exactly once
+    let refcount = cpython::diagnostics::getrefcount(py, &sub).unwrap();
+    drop(obj);
+    assert!(drop_called.load(Ordering::Relaxed));
+    for _ in 0..10 {
+        sub.call(py, (1,), None).unwrap();
+    }
+    assert_eq!(
+        cpython::diagnostics::getrefcount(py, &sub).unwrap(),
+        refcount - 1
+    );
+}
+
+#[test]
+fn create_instance_of_unrelated_type() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let err = Subclassable::create_instance_of(
+        py,
+        &py.get_type::<PyDict>(),
+        1,
+        TestDropCall {
+            drop_called: Arc::new(AtomicBool::new(false)),
+        },
+    )
+    .err()
+    .unwrap();
+    assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+}
+
+#[test]
+fn subclass_without_new() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let base = py.get_type::<SharedMethods>();
+    py_expect_exception!(py, base, "class Sub(base): pass\nSub()", TypeError);
+}
+
+#[test]
+fn not_subclassable() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let base = py.get_type::<EmptyClassWithNew>();
+    py_expect_exception!(py, base, "class Sub(base): pass", TypeError);
+}
+
+py_class!(class NewAndInit |py| {
+    @subclassable;
+    data label: RefCell<String>;
+    data init_calls: Cell<u32>;
+    def __new__(cls, *args, **kwargs) -> PyResult<NewAndInit> {
+        NewAndInit::create_instance_of(py, cls, RefCell::new(String::new()), Cell::new(0))
+    }
+    def __init__(&self, label: String, suffix: &str = "") -> PyResult<()> {
+        *self.label(py).borrow_mut() = label + suffix;
+        self.init_calls(py).set(self.init_calls(py).get() + 1);
+        Ok(())
+    }
+    def get_label(&self) -> PyResult<String> {
+        Ok(self.label(py).borrow().clone())
+    }
+});
+
+#[test]
+fn new_and_init() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let d = PyDict::new(py);
+    d.set_item(py, "NewAndInit", py.get_type::<NewAndInit>())
+        .unwrap();
+    py.run(
+        "
+class Sub(NewAndInit):
+    def __init__(self, value):
+        super(Sub, self).__init__('sub', suffix=str(value))
+        self.value = value
+",
+        Some(&d),
+        None,
+    )
+    .unwrap();
+
+    py_assert!(py, d, "d['NewAndInit']('a').get_label() == 'a'");
+    py_assert!(py, d, "d['NewAndInit']('a', 'b').get_label() == 'ab'");
+    py_assert!(py, d, "d['Sub'](1).get_label() == 'sub1'");
+    py_assert!(py, d, "d['Sub'](1).value == 1");
+    py_expect_exception!(py, d, "d['NewAndInit']()", TypeError);
+
+    // __init__ can be called again on an existing instance
+    let obj = d
+        .get_item(py, "NewAndInit")
+        .unwrap()
+        .call(py, ("x",), None)
+        .unwrap();
+    let inst = obj.cast_as::<NewAndInit>(py).unwrap();
+    assert_eq!(inst.init_calls(py).get(), 1);
+    obj.call_method(py, "__init__", ("y",), None).unwrap();
+    assert_eq!(*inst.label(py).borrow(), "y");
+    assert_eq!(inst.init_calls(py).get(), 2);
+
+    // create_instance() does not call __init__
+    let inst = NewAndInit::create_instance(py, RefCell::new("r".to_owned()), Cell::new(0)).unwrap();
+    assert_eq!(inst.get_label(py).unwrap(), "r");
+    assert_eq!(inst.init_calls(py).get(), 0);
+}
+
+py_class!(class Quacks |py| {
+    def quack(&self) -> PyResult<&'static str> {
+        Ok("quack")
+    }
+});
+
+#[test]
+fn register_virtual_subclass() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let abc = py
+        .eval(
+            "__import__('abc').ABCMeta('Duck', (object,), {})",
+            None,
+            None,
+        )
+        .unwrap()
+        .cast_into::<PyType>(py)
+        .unwrap();
+    let quacks = py.get_type::<Quacks>();
+    abc.register_virtual_subclass(py, &quacks).unwrap();
+    let obj = Quacks::create_instance(py).unwrap();
+    let d = PyDict::new(py);
+    d.set_item(py, "Duck", &abc).unwrap();
+    d.set_item(py, "obj", &obj).unwrap();
+    py.run("assert isinstance(obj, Duck)", None, Some(&d))
+        .unwrap();
+    // only real base classes are considered by is_instance()
+    assert!(!abc.is_instance(py, obj.as_object()));
+
+    // not an abstract base class
+    let err = py
+        .get_type::<PyDict>()
+        .register_virtual_subclass(py, &quacks)
+        .unwrap_err();
+    assert!(err.matches(py, py.get_type::<exc::AttributeError>()));
+}
+
+py_class!(class QuackHook |py| {
+    @subclassable;
+    @classmethod def __subclasshook__(_cls, subclass: PyType) -> PyResult<PyObject> {
+        if subclass.as_object().hasattr(py, "quack")? {
+            Ok(true.to_py_object(py).into_object())
+        } else {
+            Ok(py.NotImplemented())
+        }
+    }
+});
+
+#[test]
+fn subclasshook() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let hook = py.get_type::<QuackHook>();
+    py_run!(
+        py,
+        hook,
+        "
+import abc
+Duck = abc.ABCMeta('Duck', (hook,), {})
+class Mallard(object):
+    def quack(self):
+        pass
+assert issubclass(Mallard, Duck)
+assert isinstance(Mallard(), Duck)
+assert not issubclass(int, Duck)
+"
+    );
+}
+
+py_class!(class GenericContainer |py| {
+    @classmethod def __class_getitem__(cls, item: PyObject) -> PyResult<PyObject> {
+        let types = py.import("types")?;
+        if types.as_object().hasattr(py, "GenericAlias")? {
+            types.call(py, "GenericAlias", (cls, item), None)
+        } else {
+            Ok((cls, item).to_py_object(py).into_object())
+        }
+    }
+});
+
+py_class!(class CustomClassGetitem |py| {
+    @classmethod def __class_getitem__(_cls, item: String) -> PyResult<String> {
+        Ok(format!("custom[{}]", item))
+    }
+});
+
+#[test]
+fn class_getitem() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let version: (i32, i32) = py
+        .eval("__import__('sys').version_info[:2]", None, None)
+        .unwrap()
+        .extract(py)
+        .unwrap();
+
+    let custom = py.get_type::<CustomClassGetitem>();
+    py_assert!(py, custom, "custom.__class_getitem__('x') == 'custom[x]'");
+
+    let generic = py.get_type::<GenericContainer>();
+    if version >= (3, 7) {
+        py_assert!(py, custom, "custom['int'] == 'custom[int]'");
+        if version >= (3, 9) {
+            py_assert!(py, generic, "generic[int].__origin__ is generic");
+            py_assert!(py, generic, "generic[int].__args__ == (int,)");
+        } else {
+            py_assert!(py, generic, "generic[int] == (generic, int)");
+        }
+    }
+}
+
+py_class!(class AttributeData |py| {
+    /// The x coordinate.
+    @property data x: i32, setter set_x;
+    @property data label: String;
+    def __new__(_cls, x: i32, label: String) -> PyResult<AttributeData> {
+        AttributeData::create_instance(py, x, label)
+    }
+});
+
+#[test]
+fn attribute_data() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let obj = AttributeData::create_instance(py, 1, "a".to_owned()).unwrap();
+
+    py_assert!(py, obj, "obj.x == 1");
+    py_assert!(py, obj, "obj.label == 'a'");
+    py_run!(py, obj, "obj.x = 42");
+    assert_eq!(obj.x(py).unwrap(), 42);
+    obj.set_x(py, Some(7)).unwrap();
+    py_assert!(py, obj, "obj.x == 7");
+    py_assert!(py, obj, "'x coordinate' in type(obj).x.__doc__");
+    py_assert!(py, obj, "type(obj)(3, 'b').x == 3");
+    py_assert!(py, obj, "not hasattr(obj, '__dict__')");
+
+    py_expect_exception!(py, obj, "obj.x = 'not an int'", TypeError);
+    py_expect_exception!(py, obj, "obj.label = 'b'", AttributeError);
+    py_expect_exception!(py, obj, "del obj.x", AttributeError);
+    py_expect_exception!(py, obj, "obj.y = 1", AttributeError);
+    assert_eq!(obj.x(py).unwrap(), 7);
+}
+
+#[test]
+fn downcast_from_object() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let obj = NewWithTwoArgs::create_instance(py, 1, 2)
+        .unwrap()
+        .into_object();
+    let inst = obj.cast_as::<NewWithTwoArgs>(py).unwrap();
+    assert_eq!(*inst._data2(py), 2);
+    assert!(obj.cast_as::<NewWithOneArg>(py).is_err());
+
+    let not_a_class = 5i32.to_py_object(py).into_object();
+    let err: PyErr = not_a_class
+        .cast_into::<NewWithTwoArgs>(py)
+        .err()
+        .unwrap()
+        .into();
+    assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+}
+
 struct TestDropCall {
     drop_called: Arc<AtomicBool>,
 }
@@ -169,6 +549,64 @@ fn data_is_dropped() {
     assert!(drop_called2.load(Ordering::Relaxed) == true);
 }
 
+py_class!(class VarSizedData |py| {
+    data name: String;
+    data items: [TestDropCall];
+    data flag: Cell<bool>;
+});
+
+py_class!(class VarSizedBytes |py| {
+    data bytes: [u8];
+});
+
+#[test]
+fn var_sized_data() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let drop_called: Vec<_> = (0..3).map(|_| Arc::new(AtomicBool::new(false))).collect();
+    let items = drop_called
+        .iter()
+        .map(|d| TestDropCall {
+            drop_called: d.clone(),
+        })
+        .collect();
+    let inst =
+        VarSizedData::create_instance(py, "name".to_owned(), Cell::new(true), items).unwrap();
+    assert_eq!(inst.name(py), "name");
+    assert!(inst.flag(py).get());
+    assert_eq!(inst.items(py).len(), 3);
+    assert!(Arc::ptr_eq(&inst.items(py)[1].drop_called, &drop_called[1]));
+    assert!(drop_called.iter().all(|d| !d.load(Ordering::Relaxed)));
+    drop(inst);
+    assert!(drop_called.iter().all(|d| d.load(Ordering::Relaxed)));
+
+    let empty =
+        VarSizedData::create_instance(py, String::new(), Cell::new(false), Vec::new()).unwrap();
+    assert!(empty.items(py).is_empty());
+}
+
+#[test]
+fn var_sized_data_size() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let small = VarSizedBytes::create_instance(py, vec![1, 2, 3]).unwrap();
+    let large = VarSizedBytes::create_instance(py, (0..100).collect()).unwrap();
+    assert_eq!(small.bytes(py), &[1, 2, 3]);
+    assert_eq!(large.bytes(py)[99], 99);
+
+    // The items are stored in the object itself.
+    let sys = py.import("sys").unwrap();
+    let size = |obj: &VarSizedBytes| -> usize {
+        sys.call(py, "getsizeof", (obj,), None)
+            .unwrap()
+            .extract(py)
+            .unwrap()
+    };
+    assert_eq!(size(&large) - size(&small), 97);
+}
+
 py_class!(class InstanceMethod |py| {
     data member: i32;
 
@@ -407,6 +845,201 @@ fn gc_integration() {
     assert!(drop_called.load(Ordering::Relaxed));
 }
 
+#[test]
+fn gc_integration_through_generator() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let drop_called = Arc::new(AtomicBool::new(false));
+    let inst = GCIntegration::create_instance(
+        py,
+        RefCell::new(py.None()),
+        TestDropCall {
+            drop_called: drop_called.clone(),
+        },
+    )
+    .unwrap();
+    // instance -> iterator -> instance
+    let it = py_generator_with_state(py, inst.clone_ref(py), |_, _: &GCIntegration| {
+        None::<PyResult<i32>>
+    })
+    .unwrap();
+    *inst.self_ref(py).borrow_mut() = it;
+    inst.release_ref(py);
+
+    py.run("import gc; gc.collect()", None, None).unwrap();
+    assert!(drop_called.load(Ordering::Relaxed));
+}
+
+struct EventLog(Arc<Mutex<Vec<&'static str>>>);
+impl Drop for EventLog {
+    fn drop(&mut self) {
+        self.0.lock().unwrap().push("drop");
+    }
+}
+
+#[cfg(feature = "python3-sys")]
+py_class!(class Finalizer |py| {
+    data log: EventLog;
+    data action: Cell<&'static str>;
+
+    def __del__(&self) -> PyResult<()> {
+        self.log(py).0.lock().unwrap().push("del");
+        match self.action(py).replace("") {
+            "resurrect" => py.import("sys")?.as_object().setattr(py, "_finalizer_test", self),
+            "raise" => Err(PyErr::new::<exc::ValueError, _>(py, "error in __del__")),
+            _ => Ok(()),
+        }
+    }
+});
+
+#[test]
+#[cfg(feature = "python3-sys")]
+fn finalizer() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let inst = Finalizer::create_instance(py, EventLog(log.clone()), Cell::new("")).unwrap();
+    drop(inst);
+    assert_eq!(*log.lock().unwrap(), vec!["del", "drop"]);
+
+    // Errors are reported as unraisable and don't affect the current exception.
+    // The report is written to a replacement for sys.stderr.
+    let d = PyDict::new(py);
+    py.run(
+        "
+import sys
+class Capture(object):
+    text = ''
+    def write(self, s):
+        self.text += s
+    def flush(self):
+        pass
+saved, sys.stderr = sys.stderr, Capture()
+",
+        Some(&d),
+        None,
+    )
+    .unwrap();
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let inst = Finalizer::create_instance(py, EventLog(log.clone()), Cell::new("raise")).unwrap();
+    PyErr::new::<exc::KeyError, _>(py, "key").restore(py);
+    drop(inst);
+    assert!(PyErr::fetch(py).matches(py, py.get_type::<exc::KeyError>()));
+    assert_eq!(*log.lock().unwrap(), vec!["del", "drop"]);
+    py.run(
+        "captured, sys.stderr = sys.stderr.text, saved",
+        Some(&d),
+        None,
+    )
+    .unwrap();
+    let captured: String = d.get_item(py, "captured").unwrap().extract(py).unwrap();
+    assert!(
+        captured.contains("ValueError: error in __del__"),
+        "{}",
+        captured
+    );
+}
+
+#[test]
+#[cfg(feature = "python3-sys")]
+fn finalizer_resurrection() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let inst =
+        Finalizer::create_instance(py, EventLog(log.clone()), Cell::new("resurrect")).unwrap();
+    drop(inst);
+    assert_eq!(*log.lock().unwrap(), vec!["del"]);
+
+    let sys = py.import("sys").unwrap();
+    let inst = sys.get(py, "_finalizer_test").unwrap();
+    assert!(inst.cast_as::<Finalizer>(py).is_ok());
+    sys.as_object().delattr(py, "_finalizer_test").unwrap();
+    drop(inst);
+    assert_eq!(*log.lock().unwrap(), vec!["del", "del", "drop"]);
+}
+
+#[cfg(feature = "python3-sys")]
+py_class!(class FinalizerWithGC |py| {
+    data self_ref: RefCell<PyObject>;
+    data log: EventLog;
+
+    def __traverse__(&self, visit) {
+        visit.call(&*self.self_ref(py).borrow())
+    }
+
+    def __clear__(&self) {
+        self.log(py).0.lock().unwrap().push("clear");
+        let old_ref = mem::replace(&mut *self.self_ref(py).borrow_mut(), py.None());
+        old_ref.release_ref(py);
+    }
+
+    def __del__(&self) -> PyResult<()> {
+        self.log(py).0.lock().unwrap().push("del");
+        // The reference cycle is still intact.
+        assert!(self.self_ref(py).borrow().as_ptr() == self.as_object().as_ptr());
+        Ok(())
+    }
+});
+
+#[test]
+#[cfg(feature = "python3-sys")]
+fn finalizer_in_cycle() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let inst = FinalizerWithGC::create_instance(py, RefCell::new(py.None()), EventLog(log.clone()))
+        .unwrap();
+    *inst.self_ref(py).borrow_mut() = inst.as_object().clone_ref(py);
+    inst.release_ref(py);
+
+    py.run("import gc; gc.collect()", None, None).unwrap();
+    assert_eq!(*log.lock().unwrap(), vec!["del", "clear", "drop"]);
+}
+
+struct DeferredCleanup(Arc<Mutex<Vec<&'static str>>>);
+impl Drop for DeferredCleanup {
+    fn drop(&mut self) {
+        let log = self.0.clone();
+        log.lock().unwrap().push("drop");
+        let gil = Python::acquire_gil();
+        py_class::defer_cleanup(gil.python(), move |py| {
+            // Running Python code is safe here.
+            let n: i32 = py.eval("1 + 1", None, None).unwrap().extract(py).unwrap();
+            assert_eq!(n, 2);
+            log.lock().unwrap().push("cleanup");
+        });
+    }
+}
+
+py_class!(class DeferCleanup |py| {
+    data first: DeferredCleanup;
+    data second: EventLog;
+});
+
+#[test]
+fn defer_cleanup() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let inst =
+        DeferCleanup::create_instance(py, DeferredCleanup(log.clone()), EventLog(log.clone()))
+            .unwrap();
+    // The cleanup runs only after all data members were dropped.
+    drop(inst);
+    assert_eq!(*log.lock().unwrap(), vec!["drop", "drop", "cleanup"]);
+
+    // Outside of a deallocation, the cleanup runs immediately.
+    let log = Arc::new(Mutex::new(Vec::new()));
+    drop(DeferredCleanup(log.clone()));
+    assert_eq!(*log.lock().unwrap(), vec!["drop", "cleanup"]);
+}
+
 py_class!(class Len |py| {
     data l: usize;
 
@@ -431,6 +1064,58 @@ fn len() {
     py_expect_exception!(py, inst, "len(inst)", OverflowError);
 }
 
+py_class!(
+    /// Class docs.
+    /// Second line.
+    class Documented |py| {
+        /// Method docs.
+        def method(&self) -> PyResult<i32> {
+            Ok(1)
+        }
+
+        /// Class method docs.
+        @classmethod
+        def class_method(cls) -> PyResult<i32> {
+            Ok(2)
+        }
+
+        /// Static method docs.
+        @staticmethod
+        def static_method() -> PyResult<i32> {
+            Ok(3)
+        }
+    }
+);
+
+py_class!(pub class Undocumented |py| {});
+
+#[test]
+fn docstrings() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let typeobj = py.get_type::<Documented>();
+    py_assert!(
+        py,
+        typeobj,
+        "typeobj.__doc__ == ' Class docs.\\n Second line.\\n'"
+    );
+    py_assert!(py, typeobj, "'Method docs.' in typeobj.method.__doc__");
+    py_assert!(
+        py,
+        typeobj,
+        "'Class method docs.' in typeobj.class_method.__doc__"
+    );
+    py_assert!(
+        py,
+        typeobj,
+        "'Static method docs.' in typeobj.static_method.__doc__"
+    );
+
+    let typeobj = py.get_type::<Undocumented>();
+    py_assert!(py, typeobj, "typeobj.__doc__ is None");
+}
+
 py_class!(class Iterator |py| {
     data iter: RefCell<Box<dyn iter::Iterator<Item=i32> + Send>>;
 
@@ -453,6 +1138,61 @@ fn iterator() {
     py_assert!(py, inst, "list(inst) == [5, 6, 7]");
 }
 
+py_class!(class Reversible |py| {
+    data items: Vec<i32>;
+
+    def __len__(&self) -> PyResult<usize> {
+        Ok(self.items(py).len())
+    }
+
+    def __getitem__(&self, index: usize) -> PyResult<i32> {
+        match self.items(py).get(index) {
+            Some(&item) => Ok(item),
+            None => Err(PyErr::new::<exc::IndexError, _>(py, "index out of range")),
+        }
+    }
+
+    def __reversed__(&self) -> PyResult<PyObject> {
+        let mut remaining = self.items(py).len();
+        py_generator_with_state(py, self.clone_ref(py), move |py, this: &Reversible| {
+            remaining = remaining.checked_sub(1)?;
+            Some(Ok(this.items(py)[remaining] * 10))
+        })
+    }
+});
+
+py_class!(class SequenceOnly |py| {
+    data items: Vec<i32>;
+
+    def __len__(&self) -> PyResult<usize> {
+        Ok(self.items(py).len())
+    }
+
+    def __getitem__(&self, index: usize) -> PyResult<i32> {
+        match self.items(py).get(index) {
+            Some(&item) => Ok(item),
+            None => Err(PyErr::new::<exc::IndexError, _>(py, "index out of range")),
+        }
+    }
+});
+
+#[test]
+fn reversed_precedence() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    // a user-defined __reversed__ takes precedence over __len__/__getitem__
+    let inst = Reversible::create_instance(py, vec![1, 2, 3]).unwrap();
+    py_assert!(py, inst, "list(reversed(inst)) == [30, 20, 10]");
+    py_assert!(py, inst, "list(inst.__reversed__()) == [30, 20, 10]");
+
+    let empty = Reversible::create_instance(py, vec![]).unwrap();
+    py_assert!(py, empty, "list(reversed(empty)) == []");
+
+    let inst = SequenceOnly::create_instance(py, vec![1, 2, 3]).unwrap();
+    py_assert!(py, inst, "list(reversed(inst)) == [3, 2, 1]");
+}
+
 py_class!(class StringMethods |py| {
     def __str__(&self) -> PyResult<&'static str> {
         Ok("str")
@@ -462,48 +1202,222 @@ py_class!(class StringMethods |py| {
         Ok("repr")
     }
 
-    def __format__(&self, format_spec: &str) -> PyResult<String> {
-        Ok(format!("format({})", format_spec))
-    }
+    def __format__(&self, format_spec: &str) -> PyResult<String> {
+        Ok(format!("format({})", format_spec))
+    }
+
+    def __unicode__(&self) -> PyResult<PyUnicode> {
+        Ok(PyUnicode::new(py, "unicode"))
+    }
+
+    def __bytes__(&self) -> PyResult<PyBytes> {
+        Ok(PyBytes::new(py, b"bytes"))
+    }
+});
+
+#[test]
+fn string_methods() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj = StringMethods::create_instance(py).unwrap();
+    py_assert!(py, obj, "str(obj) == 'str'");
+    py_assert!(py, obj, "repr(obj) == 'repr'");
+    py_assert!(py, obj, "'{0:x}'.format(obj) == 'format(x)'");
+}
+
+#[test]
+#[cfg(feature = "python27-sys")]
+fn python2_string_methods() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj = StringMethods::create_instance(py).unwrap();
+    py_assert!(py, obj, "unicode(obj) == u'unicode'");
+}
+
+#[test]
+#[cfg(feature = "python3-sys")]
+fn python3_string_methods() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj = StringMethods::create_instance(py).unwrap();
+    py_assert!(py, obj, "bytes(obj) == b'bytes'");
+}
+
+py_class!(class ReprNode |py| {
+    data name: String;
+    data child: RefCell<Option<PyObject>>;
+
+    def __repr__(&self) -> PyResult<String> {
+        let child = self.child(py).borrow();
+        match *child {
+            Some(ref obj) => Ok(format!("{}({})", self.name(py), obj.repr(py)?.to_string(py)?)),
+            None => Ok(self.name(py).clone()),
+        }
+    }
+
+    def __str__(&self) -> PyResult<String> {
+        Ok(self.name(py).clone())
+    }
+});
+
+#[test]
+fn string_methods_reentrant() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let leaf = ReprNode::create_instance(py, "leaf".to_string(), RefCell::new(None)).unwrap();
+    let obj = ReprNode::create_instance(
+        py,
+        "node".to_string(),
+        RefCell::new(Some(leaf.into_object())),
+    )
+    .unwrap();
+    py_assert!(py, obj, "repr(obj) == 'node(leaf)'");
+
+    // The child's `__repr__` calls back into `obj` while `obj.__repr__`
+    // still holds a shared borrow of its `child` field.
+    let d = PyDict::new(py);
+    d.set_item(py, "obj", &obj).unwrap();
+    py.run(
+        "class Child(object):\n    def __repr__(self):\n        return 'child of ' + str(obj)\nchild = Child()",
+        Some(&d),
+        None,
+    )
+    .unwrap();
+    *obj.child(py).borrow_mut() = d.get_item(py, "child");
+    py_assert!(py, obj, "repr(obj) == 'node(child of node)'");
+    *obj.child(py).borrow_mut() = None;
+}
+
+py_class!(class SharedMethods |py| {
+    @subclassable;
+    def describe(&self) -> PyResult<String> {
+        let len = self.as_object().len(py)?;
+        Ok(format!("{} with {} keys", self.as_object().get_type(py).name(py), len))
+    }
+});
+
+py_class!(class KeyMapping |py| {
+    @base py.get_type::<SharedMethods>();
+    data keys: Vec<String>;
+
+    def __getitem__(&self, key: String) -> PyResult<usize> {
+        match self.keys(py).iter().position(|k| *k == key) {
+            Some(index) => Ok(index),
+            None => Err(PyErr::new::<exc::KeyError, _>(py, key)),
+        }
+    }
+
+    def __len__(&self) -> PyResult<usize> {
+        Ok(self.keys(py).len())
+    }
+});
+
+#[test]
+fn base_type_object() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj = KeyMapping::create_instance(py, vec!["a".to_string(), "b".to_string()]).unwrap();
+    let d = PyDict::new(py);
+    d.set_item(py, "obj", &obj).unwrap();
+    d.set_item(py, "SharedMethods", py.get_type::<SharedMethods>())
+        .unwrap();
+    py.run(
+        "assert isinstance(obj, SharedMethods)\n\
+         assert obj.describe() == 'KeyMapping with 2 keys'\n\
+         assert obj['b'] == 1",
+        None,
+        Some(&d),
+    )
+    .unwrap();
+    py_expect_exception!(py, obj, "type(obj)()", TypeError);
+}
+
+py_class!(class BaseWithDict |py| {
+    @base py.get_type::<PyDict>();
+});
+
+py_class!(class BaseDefinedInPython |py| {
+    @base py.eval("type('Base', (object,), {'__slots__': ()})", None, None)?.cast_into::<PyType>(py)?;
+});
+
+#[test]
+fn base_type_object_with_instance_data() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let err = <BaseWithDict as py_class::PythonObjectFromPyClassMacro>::initialize(py, None)
+        .err()
+        .unwrap();
+    assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+}
+
+#[test]
+fn base_type_object_defined_in_python() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let err = <BaseDefinedInPython as py_class::PythonObjectFromPyClassMacro>::initialize(py, None)
+        .err()
+        .unwrap();
+    assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+}
+
+py_class!(class Registry |py| {
+    @metaclass;
 
-    def __unicode__(&self) -> PyResult<PyUnicode> {
-        Ok(PyUnicode::new(py, "unicode"))
+    def __new__(mcs, name: String, bases: PyTuple, namespace: PyDict) -> PyResult<PyType> {
+        let class = PyType::create_with_metaclass(py, mcs, &name, &bases, &namespace)?;
+        class.as_object().setattr(py, "registered", true)?;
+        Ok(class)
     }
 
-    def __bytes__(&self) -> PyResult<PyBytes> {
-        Ok(PyBytes::new(py, b"bytes"))
+    def class_name(&self) -> PyResult<PyObject> {
+        self.as_object().getattr(py, "__name__")
     }
 });
 
 #[test]
-fn string_methods() {
+fn metaclass() {
     let gil = Python::acquire_gil();
     let py = gil.python();
 
-    let obj = StringMethods::create_instance(py).unwrap();
-    py_assert!(py, obj, "str(obj) == 'str'");
-    py_assert!(py, obj, "repr(obj) == 'repr'");
-    py_assert!(py, obj, "'{0:x}'.format(obj) == 'format(x)'");
+    let meta = py.get_type::<Registry>();
+    py_assert!(py, meta, "issubclass(meta, type)");
+    py_run!(
+        py,
+        meta,
+        "Foo = meta('Foo', (object,), {'x': 1})\n\
+         assert isinstance(Foo, meta) and isinstance(Foo, type)\n\
+         assert Foo.registered and Foo.x == 1\n\
+         assert Foo.class_name() == 'Foo'\n\
+         assert isinstance(Foo(), Foo)\n\
+         class Bar(Foo): pass\n\
+         assert type(Bar) is meta and Bar.registered\n\
+         assert Bar.class_name() == 'Bar'"
+    );
+    let err = Registry::create_instance(py).err().unwrap();
+    assert!(err.matches(py, py.get_type::<exc::TypeError>()));
 }
 
-#[test]
-#[cfg(feature = "python27-sys")]
-fn python2_string_methods() {
-    let gil = Python::acquire_gil();
-    let py = gil.python();
-
-    let obj = StringMethods::create_instance(py).unwrap();
-    py_assert!(py, obj, "unicode(obj) == u'unicode'");
-}
+py_class!(class MetaclassWithData |py| {
+    @metaclass;
+    data value: i32;
+});
 
 #[test]
-#[cfg(feature = "python3-sys")]
-fn python3_string_methods() {
+fn metaclass_with_data() {
     let gil = Python::acquire_gil();
     let py = gil.python();
 
-    let obj = StringMethods::create_instance(py).unwrap();
-    py_assert!(py, obj, "bytes(obj) == b'bytes'");
+    let err = <MetaclassWithData as py_class::PythonObjectFromPyClassMacro>::initialize(py, None)
+        .err()
+        .unwrap();
+    assert!(err.matches(py, py.get_type::<exc::TypeError>()));
 }
 
 py_class!(class Comparisons |py| {
@@ -535,6 +1449,87 @@ fn comparisons() {
     py_assert!(py, zero, "not zero");
 }
 
+py_class!(class Unhashable |py| {
+    data items: RefCell<Vec<i32>>;
+    @unhashable;
+});
+
+py_class!(class RichcmpWithoutHash |py| {
+    def __richcmp__(&self, other: PyObject, op: CompareOp) -> PyResult<bool> {
+        Ok(false)
+    }
+});
+
+#[test]
+fn unhashable() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj = Unhashable::create_instance(py, RefCell::new(vec![])).unwrap();
+    py_expect_exception!(py, obj, "hash(obj)", TypeError);
+    py_assert!(py, obj, "type(obj).__hash__ is None");
+    #[cfg(feature = "python3-sys")]
+    py_assert!(
+        py,
+        obj,
+        "not isinstance(obj, __import__('collections.abc').abc.Hashable)"
+    );
+    assert!(obj.as_object().hash(py).is_err());
+
+    let obj = RichcmpWithoutHash::create_instance(py).unwrap();
+    py_expect_exception!(py, obj, "hash(obj)", TypeError);
+
+    // without comparisons, instances are hashable by identity
+    let obj = EmptyClass::create_instance(py).unwrap();
+    py_assert!(py, obj, "hash(obj) == hash(obj)");
+}
+
+py_class!(class BoolAndLen |py| {
+    data truthy: bool;
+    data length: usize;
+
+    def __bool__(&self) -> PyResult<bool> {
+        Ok(*self.truthy(py))
+    }
+
+    def __len__(&self) -> PyResult<usize> {
+        Ok(*self.length(py))
+    }
+});
+
+py_class!(class LenOnly |py| {
+    data length: usize;
+
+    def __len__(&self) -> PyResult<usize> {
+        Ok(*self.length(py))
+    }
+});
+
+#[test]
+fn bool_and_len() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    // __bool__ takes precedence over __len__ for truthiness
+    let c = BoolAndLen::create_instance(py, true, 0).unwrap();
+    py_assert!(py, c, "bool(c) is True");
+    py_assert!(py, c, "len(c) == 0");
+    let c = BoolAndLen::create_instance(py, false, 3).unwrap();
+    py_assert!(py, c, "bool(c) is False");
+    py_assert!(py, c, "len(c) == 3");
+
+    // without __bool__, __len__ is used for truthiness
+    let c = LenOnly::create_instance(py, 0).unwrap();
+    py_assert!(py, c, "bool(c) is False");
+    let c = LenOnly::create_instance(py, 2).unwrap();
+    py_assert!(py, c, "bool(c) is True");
+    py_assert!(py, c, "len(c) == 2");
+
+    // __bool__ alone does not make the object sized
+    let c = Comparisons::create_instance(py, 1).unwrap();
+    py_expect_exception!(py, c, "len(c)", TypeError);
+}
+
 py_class!(class Sequence |py| {
     def __len__(&self) -> PyResult<usize> {
         Ok(5)
@@ -608,6 +1603,52 @@ fn callable() {
     py_assert!(py, nc, "not callable(nc)");
 }
 
+py_class!(class CallableWithParams |py| {
+    data factor: i32;
+
+    def __call__(&self, value: i32, offset: i32 = 0, label: Option<&str> = None) -> PyResult<String> {
+        Ok(format!("{}{}", label.unwrap_or(""), value * *self.factor(py) + offset))
+    }
+});
+
+py_class!(class CallableVarArgs |py| {
+    def __call__(&self, *args, **kwargs) -> PyResult<(usize, usize)> {
+        Ok((args.len(py), kwargs.map_or(0, |kwargs| kwargs.len(py))))
+    }
+});
+
+#[test]
+fn callable_arguments() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    // On Python 3.9 and later, these calls go through the vectorcall entry point.
+    let c = CallableWithParams::create_instance(py, 2).unwrap();
+    py_assert!(py, c, "c(5) == '10'");
+    py_assert!(py, c, "c(5, 1) == '11'");
+    py_assert!(py, c, "c(5, label='x') == 'x10'");
+    py_assert!(py, c, "c(offset=3, value=5) == '13'");
+    py_assert!(py, c, "c(*[5], **{'offset': 1, 'label': 'y'}) == 'y11'");
+    py_expect_exception!(py, c, "c()", TypeError);
+    py_expect_exception!(py, c, "c(1, 2, 'a', 4)", TypeError);
+    py_expect_exception!(py, c, "c(1, value=2)", TypeError);
+    py_expect_exception!(py, c, "c(1, unknown=2)", TypeError);
+    py_expect_exception!(py, c, "c('a')", TypeError);
+
+    let args = [
+        5i32.to_py_object(py).into_object(),
+        "z".to_py_object(py).into_object(),
+    ];
+    let kwnames = PyTuple::new(py, &["label".to_py_object(py).into_object()]);
+    let result = c.as_object().vectorcall(py, &args, Some(&kwnames)).unwrap();
+    assert_eq!(result.extract::<String>(py).unwrap(), "z10");
+
+    let v = CallableVarArgs::create_instance(py).unwrap();
+    py_assert!(py, v, "v() == (0, 0)");
+    py_assert!(py, v, "v(1, 2, 3) == (3, 0)");
+    py_assert!(py, v, "v(1, a=2, b=3) == (1, 2)");
+}
+
 py_class!(class SetItem |py| {
     data key: Cell<i32>;
     data val: Cell<i32>;
@@ -794,6 +1835,39 @@ fn contains_opt_ref() {
     py_run!(py, c, "assert None not in c");
 }
 
+py_class!(class ContainsObject |py| {
+    data items: Vec<i32>;
+    data iter_calls: Cell<usize>;
+
+    def __contains__(&self, item: PyObject) -> PyResult<bool> {
+        if item == py.None() {
+            return Err(PyErr::new::<exc::ValueError, _>(py, "None is not allowed"));
+        }
+        Ok(item.extract::<i32>(py).map(|v| self.items(py).contains(&v)).unwrap_or(false))
+    }
+
+    def __iter__(&self) -> PyResult<PyObject> {
+        self.iter_calls(py).set(self.iter_calls(py).get() + 1);
+        let list = self.items(py).to_py_object(py);
+        list.as_object().call_method(py, "__iter__", NoArgs, None)
+    }
+});
+
+#[test]
+fn contains_object() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let c = ContainsObject::create_instance(py, vec![1, 2], Cell::new(0)).unwrap();
+    py_run!(py, c, "assert 1 in c and 2 in c and 3 not in c");
+    py_run!(py, c, "assert 'abc' not in c");
+    py_expect_exception!(py, c, "None in c", ValueError);
+    // __contains__ takes precedence over iterating
+    assert_eq!(c.iter_calls(py).get(), 0);
+    py_run!(py, c, "assert list(c) == [1, 2]");
+    assert_eq!(c.iter_calls(py).get(), 1);
+}
+
 py_class!(class UnaryArithmetic |py| {
     def __neg__(&self) -> PyResult<&'static str> {
         Ok("neg")
@@ -824,6 +1898,64 @@ fn unary_arithmetic() {
     py_run!(py, c, "assert ~c == 'invert'");
 }
 
+py_class!(class IndexLike |py| {
+    data value: i64;
+    def __index__(&self) -> PyResult<i64> {
+        Ok(*self.value(py))
+    }
+});
+
+#[test]
+fn index() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let c = IndexLike::create_instance(py, 2).unwrap();
+    py_assert!(py, c, "[10, 11, 12][c] == 12");
+    py_assert!(py, c, "'abcd'[c:] == 'cd'");
+    py_assert!(py, c, "list(range(c)) == [0, 1]");
+    py_assert!(py, c, "hex(c) == '0x2'");
+    py_assert!(py, c, "__import__('operator').index(c) == 2");
+    let value = c.as_object().to_index(py).unwrap();
+    assert_eq!(value.extract::<i64>(py).unwrap(), 2);
+
+    let float = 2.0f64.to_py_object(py).into_object();
+    let err = float.to_index(py).unwrap_err();
+    assert!(err.matches(py, py.get_type::<exc::TypeError>()));
+}
+
+py_class!(class Rounding |py| {
+    data tenths: i64;
+    def __round__(&self, ndigits: Option<i32> = None) -> PyResult<String> {
+        Ok(format!("round {:?}", ndigits))
+    }
+    def __trunc__(&self) -> PyResult<i64> {
+        Ok(*self.tenths(py) / 10)
+    }
+    def __floor__(&self) -> PyResult<i64> {
+        Ok(self.tenths(py).div_euclid(10))
+    }
+    def __ceil__(&self) -> PyResult<i64> {
+        Ok(-(-*self.tenths(py)).div_euclid(10))
+    }
+});
+
+#[test]
+fn rounding() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let c = Rounding::create_instance(py, -25).unwrap();
+    py_assert!(py, c, "__import__('math').trunc(c) == -2");
+    #[cfg(feature = "python3-sys")]
+    {
+        py_assert!(py, c, "round(c) == 'round None'");
+        py_assert!(py, c, "round(c, 2) == 'round Some(2)'");
+        py_assert!(py, c, "__import__('math').floor(c) == -3");
+        py_assert!(py, c, "__import__('math').ceil(c) == -2");
+    }
+}
+
 py_class!(class BinaryArithmetic |py| {
     def __repr__(&self) -> PyResult<&'static str> {
         Ok("BA")
@@ -888,6 +2020,81 @@ fn binary_arithmetic() {
     py_run!(py, c, "assert 1 | c == '1 | BA'");
 }
 
+py_class!(class Meters |py| {
+    data value: i64;
+
+    def __add__(lhs, rhs) -> PyResult<PyObject> {
+        let value = |obj: &PyObject| -> Option<i64> {
+            match obj.cast_as::<Meters>(py) {
+                Ok(m) => Some(*m.value(py)),
+                Err(_) => obj.extract::<i64>(py).ok(),
+            }
+        };
+        match (value(lhs), value(rhs)) {
+            (Some(l), Some(r)) => Ok(Meters::create_instance(py, l + r)?.into_object()),
+            _ => Ok(py.NotImplemented()),
+        }
+    }
+});
+
+#[test]
+fn binary_arithmetic_not_implemented() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let m = Meters::create_instance(py, 2).unwrap();
+    let locals = PyDict::new(py);
+    locals.set_item(py, "m", &m).unwrap();
+    let sum = py.eval("m + 3", None, Some(&locals)).unwrap();
+    assert_eq!(*sum.cast_as::<Meters>(py).unwrap().value(py), 5);
+    py_assert!(py, m, "(m + m).__class__ is m.__class__");
+    py_assert!(py, m, "(1 + m).__class__ is m.__class__");
+
+    // NotImplemented makes Python fall back to the other operand
+    py_run!(
+        py,
+        m,
+        "class Feet(object):\n  def __radd__(self, other): return 'radd'\nassert m + Feet() == 'radd'"
+    );
+
+    // ... and raise TypeError if no operand supports the operation
+    py_expect_exception!(py, m, "m + 'a'", TypeError);
+    py_expect_exception!(py, m, "'a' + m", TypeError);
+}
+
+#[cfg(feature = "python3-sys")]
+py_class!(class MatrixMultiply |py| {
+    data value: Cell<i32>;
+
+    def __repr__(&self) -> PyResult<&'static str> {
+        Ok("MM")
+    }
+
+    def __matmul__(lhs, rhs) -> PyResult<String> {
+        Ok(format!("{:?} @ {:?}", lhs, rhs))
+    }
+
+    def __imatmul__(&self, other: i32) -> PyResult<Self> {
+        self.value(py).set(self.value(py).get() * other);
+        Ok(self.clone_ref(py))
+    }
+});
+
+#[test]
+#[cfg(feature = "python3-sys")]
+fn matrix_multiply() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let c = MatrixMultiply::create_instance(py, Cell::new(2)).unwrap();
+    py_run!(py, c, "assert c @ 1 == 'MM @ 1'");
+    py_run!(py, c, "assert 1 @ c == '1 @ MM'");
+    py_run!(py, c, "assert c @ c == 'MM @ MM'");
+
+    py_run!(py, c, "c @= 3");
+    assert_eq!(c.value(py).get(), 6);
+}
+
 py_class!(class RichComparisons |py| {
     def __repr__(&self) -> PyResult<&'static str> {
         Ok("RC")
@@ -1217,6 +2424,144 @@ fn context_manager() {
     assert!(c.exit_called(py).get());
 }
 
+py_class!(class PickleReduce |py| {
+    @module "test_class_pickle";
+    data value: i32;
+
+    def __new__(_cls, value: i32) -> PyResult<PickleReduce> {
+        PickleReduce::create_instance(py, value)
+    }
+
+    def get(&self) -> PyResult<i32> {
+        Ok(*self.value(py))
+    }
+
+    def __reduce__(&self) -> PyResult<(PyType, (i32,))> {
+        Ok((py.get_type::<PickleReduce>(), (*self.value(py),)))
+    }
+});
+
+py_class!(class PickleState |py| {
+    @module "test_class_pickle";
+    data value: Cell<i32>;
+
+    def __new__(_cls) -> PyResult<PickleState> {
+        PickleState::create_instance(py, Cell::new(0))
+    }
+
+    def get(&self) -> PyResult<i32> {
+        Ok(self.value(py).get())
+    }
+
+    def __getstate__(&self) -> PyResult<i32> {
+        Ok(self.value(py).get())
+    }
+
+    def __setstate__(&self, state: i32) -> PyResult<PyNone> {
+        self.value(py).set(state);
+        Ok(PyNone)
+    }
+});
+
+#[test]
+fn pickle() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let module = PyModule::new(py, "test_class_pickle").unwrap();
+    module.add_class::<PickleReduce>(py).unwrap();
+    module.add_class::<PickleState>(py).unwrap();
+    py.import("sys")
+        .unwrap()
+        .get(py, "modules")
+        .unwrap()
+        .set_item(py, "test_class_pickle", &module)
+        .unwrap();
+
+    let obj = PickleReduce::create_instance(py, 5).unwrap();
+    py_assert!(
+        py,
+        obj,
+        "__import__('pickle').loads(__import__('pickle').dumps(obj, 2)).get() == 5"
+    );
+
+    let obj = PickleState::create_instance(py, Cell::new(7)).unwrap();
+    py_assert!(
+        py,
+        obj,
+        "__import__('pickle').loads(__import__('pickle').dumps(obj, 2)).get() == 7"
+    );
+    py_assert!(py, obj, "__import__('copy').copy(obj).get() == 7");
+}
+
+py_class!(class SetName |py| {
+    data name: RefCell<Option<(String, String)>>;
+
+    def __set_name__(&self, owner: PyType, name: String) -> PyResult<PyNone> {
+        *self.name(py).borrow_mut() = Some((owner.name(py).into_owned(), name));
+        Ok(PyNone)
+    }
+});
+
+#[test]
+fn set_name() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let field = SetName::create_instance(py, RefCell::new(None)).unwrap();
+    py_run!(py, field, "type('Model', (object,), {'title': field})");
+    let has_set_name: bool = py
+        .eval("__import__('sys').version_info >= (3, 6)", None, None)
+        .unwrap()
+        .extract(py)
+        .unwrap();
+    // __set_name__ was added in Python 3.6.
+    if has_set_name {
+        assert_eq!(
+            *field.name(py).borrow(),
+            Some(("Model".to_string(), "title".to_string()))
+        );
+    } else {
+        assert!(field.name(py).borrow().is_none());
+    }
+}
+
+py_class!(class PluginBase |py| {
+    @subclassable;
+    def __init_subclass__(cls, tag: Option<String> = None) -> PyResult<PyNone> {
+        let tag = match tag {
+            Some(tag) => tag,
+            None => cls.name(py).to_lowercase(),
+        };
+        cls.as_object().setattr(py, "tag", tag)?;
+        Ok(PyNone)
+    }
+});
+
+#[test]
+fn init_subclass() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let base = py.get_type::<PluginBase>();
+    let has_init_subclass: bool = py
+        .eval("__import__('sys').version_info >= (3, 6)", None, None)
+        .unwrap()
+        .extract(py)
+        .unwrap();
+    // __init_subclass__ was added in Python 3.6.
+    if has_init_subclass {
+        py_run!(py, base, "class Csv(base): pass\nassert Csv.tag == 'csv'");
+        py_run!(
+            py,
+            base,
+            "class Json(base, tag='js'): pass\nassert Json.tag == 'js'"
+        );
+        py_expect_exception!(py, base, "type('Bad', (base,), {}, unknown=1)", TypeError);
+        py_assert!(py, base, "not hasattr(base, 'tag')");
+    }
+}
+
 py_class!(class Properties |py| {
     data value: Cell<i32>;
     data value_by_ref: RefCell<String>;
@@ -1305,6 +2650,114 @@ fn properties() {
     py_run!(py, c, "repr(c) == 'P(42, \"testing\" \"DELETED\")'");
 }
 
+py_class!(class AttributeFallback |py| {
+    data fallback: i32;
+
+    def __getattribute__(&self, name: PyString) -> PyResult<PyObject> {
+        match self.as_object().generic_getattr(py, &name) {
+            Err(ref e)
+                if e.matches(py, py.get_type::<exc::AttributeError>())
+                    && !name.to_string(py)?.starts_with('_') =>
+            {
+                Ok(self.fallback(py).to_py_object(py).into_object())
+            }
+            result => result,
+        }
+    }
+
+    def method(&self) -> PyResult<&'static str> {
+        Ok("method")
+    }
+});
+
+#[test]
+fn getattribute() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj = AttributeFallback::create_instance(py, 5).unwrap();
+    py_assert!(py, obj, "obj.method() == 'method'");
+    py_assert!(py, obj, "obj.anything == 5");
+    py_assert!(py, obj, "getattr(obj, 'other') == 5");
+    py_assert!(py, obj, "not hasattr(obj, '_private')");
+    py_expect_exception!(py, obj, "obj._private", AttributeError);
+}
+
+py_class!(class LazyItems |py| {
+    data values: RefCell<Vec<i32>>;
+
+    def items(&self) -> PyResult<PyObject> {
+        let mut index = 0;
+        py_generator_with_state(py, self.clone_ref(py), move |py, this: &LazyItems| {
+            let item = *this.values(py).borrow().get(index)?;
+            index += 1;
+            Some(Ok(item))
+        })
+    }
+
+    def push(&self, item: i32) -> PyResult<PyNone> {
+        self.values(py).borrow_mut().push(item);
+        Ok(PyNone)
+    }
+});
+
+#[test]
+fn method_returning_generator() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let obj = LazyItems::create_instance(py, RefCell::new(vec![1, 2])).unwrap();
+    py_run!(
+        py,
+        obj,
+        "it = obj.items()\n\
+         assert iter(it) is it\n\
+         assert next(it) == 1\n\
+         obj.push(3)\n\
+         assert list(it) == [2, 3]\n\
+         assert list(obj.items()) == [1, 2, 3]"
+    );
+    // The generator keeps the instance alive.
+    let it = obj
+        .as_object()
+        .call_method(py, "items", NoArgs, None)
+        .unwrap();
+    drop(obj);
+    assert_eq!(it.iter(py).unwrap().count(), 3);
+}
+
+py_class!(class LengthHint |py| {
+    data hint: Option<usize>;
+
+    def __length_hint__(&self) -> PyResult<PyObject> {
+        Ok(match *self.hint(py) {
+            Some(hint) => hint.to_py_object(py).into_object(),
+            None => py.NotImplemented(),
+        })
+    }
+});
+
+#[test]
+fn length_hint() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let known = LengthHint::create_instance(py, Some(5)).unwrap();
+    let unknown = LengthHint::create_instance(py, None).unwrap();
+    assert_eq!(known.as_object().length_hint(py, 10).unwrap(), 5);
+    assert_eq!(unknown.as_object().length_hint(py, 10).unwrap(), 10);
+    py_assert!(py, known, "known.__length_hint__() == 5");
+    #[cfg(feature = "python3-sys")]
+    {
+        py_assert!(py, known, "__import__('operator').length_hint(known) == 5");
+        py_assert!(
+            py,
+            unknown,
+            "__import__('operator').length_hint(unknown, 7) == 7"
+        );
+    }
+}
+
 py_class!(pub(crate) class ClassWithVisibility |py| {
   pub(crate) def __new__(_cls) -> PyResult<Self> {
     Self::create_instance(py)
@@ -0,0 +1,26 @@
+use cpython::*;
+
+// Runs in its own test binary, as finalization affects the whole process.
+// Keep this the only test in this file, so that no other test can run concurrently.
+#[test]
+fn test_finalize_and_reinitialize() {
+    for i in 0..2 {
+        {
+            let gil = Python::acquire_gil();
+            let py = gil.python();
+            let locals = PyDict::new(py);
+            locals.set_item(py, "i", i).unwrap();
+            let result = py.eval("i * 2", None, Some(&locals)).unwrap();
+            assert_eq!(result.extract::<i32>(py).unwrap(), i * 2);
+
+            // Objects dropped without the GIL are released before finalization.
+            let obj = PyList::new(py, &[]).into_object();
+            py.allow_threads(move || {
+                std::thread::spawn(move || drop(obj)).join().unwrap();
+            });
+        }
+        assert!(unsafe { finalize_python() });
+    }
+    // Finalizing an interpreter that is not initialized has no effect.
+    assert!(unsafe { finalize_python() });
+}
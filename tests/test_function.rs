@@ -1,4 +1,8 @@
-use cpython::{py_fn, NoArgs, ObjectProtocol, PyDict, PyNone, PyResult, Python};
+use cpython::{
+    annotate_function, exc, panic_message, py_closure, py_fn, py_generator, release_gil,
+    set_panic_handler, CompileMode, NoArgs, ObjectProtocol, PyClone, PyDict, PyErr, PyList, PyNone,
+    PyObject, PyResult, Python,
+};
 use std::sync::atomic;
 use std::sync::atomic::Ordering::Relaxed;
 
@@ -157,6 +161,42 @@ fn opt_args() {
     );
 }
 
+#[test]
+fn default_referencing_py() {
+    fn append(py: Python, item: i32, items: PyList) -> PyResult<PyList> {
+        items.append(py, item)?;
+        Ok(items)
+    }
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let d = PyDict::new(py);
+    d.set_item(
+        py,
+        "append",
+        py_fn!(py, append(item: i32, items: PyList = PyList::new(py, &[]))),
+    )
+    .unwrap();
+    d.set_item(
+        py,
+        "append_inline",
+        py_fn!(py, append_inline(item: i32, items: PyList = PyList::new(py, &[])) -> PyResult<PyList> {
+            append(py, item, items)
+        }),
+    )
+    .unwrap();
+    py.run(
+        "for f in (append, append_inline):\n\
+         \x20   assert f(1) == [1]\n\
+         \x20   assert f(2) == [2]  # the default is evaluated anew on each call\n\
+         \x20   l = [0]\n\
+         \x20   assert f(3, l) is l and l == [0, 3]",
+        None,
+        Some(&d),
+    )
+    .unwrap();
+}
+
 #[test]
 fn none_return() {
     static CALL_COUNT: atomic::AtomicUsize = atomic::AtomicUsize::new(0);
@@ -181,6 +221,401 @@ fn none_return() {
     assert_eq!(CALL_COUNT.load(Relaxed), 1);
 }
 
+#[test]
+fn generator() {
+    static PRODUCED: atomic::AtomicUsize = atomic::AtomicUsize::new(0);
+
+    fn count(py: Python) -> PyResult<PyObject> {
+        py_generator(
+            py,
+            (0..).map(|i: u32| {
+                PRODUCED.fetch_add(1, Relaxed);
+                Ok(i)
+            }),
+        )
+    }
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let d = PyDict::new(py);
+    d.set_item(py, "count", py_fn!(py, count())).unwrap();
+
+    py.run(
+        "total = 0\nfor i in count():\n    if i == 5: break\n    total += i",
+        None,
+        Some(&d),
+    )
+    .unwrap();
+    assert_eq!(
+        d.get_item(py, "total").unwrap().extract::<u32>(py).unwrap(),
+        10
+    );
+    assert_eq!(PRODUCED.load(Relaxed), 6);
+
+    py.run("it = iter(count())\nassert iter(it) is it", None, Some(&d))
+        .unwrap();
+}
+
+#[test]
+fn converted_return_values() {
+    fn vec(_py: Python, n: i32) -> PyResult<Vec<i32>> {
+        Ok((0..n).collect())
+    }
+    fn tuple(_py: Python) -> PyResult<(i32, String, Option<bool>)> {
+        Ok((1, "two".to_owned(), None))
+    }
+    fn object(py: Python) -> PyResult<PyObject> {
+        Ok(py.None())
+    }
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let d = PyDict::new(py);
+    d.set_item(py, "vec", py_fn!(py, vec(n: i32))).unwrap();
+    d.set_item(py, "tuple", py_fn!(py, tuple())).unwrap();
+    d.set_item(py, "object", py_fn!(py, object())).unwrap();
+    d.set_item(
+        py,
+        "inline",
+        py_fn!(py, inline(x: i32) -> PyResult<Vec<(i32, i32)>> { Ok(vec![(x, x * x)]) }),
+    )
+    .unwrap();
+    py.run(
+        "
+assert vec(3) == [0, 1, 2]
+assert tuple() == (1, 'two', None)
+assert object() is None
+assert inline(3) == [(3, 9)]
+",
+        None,
+        Some(&d),
+    )
+    .unwrap();
+}
+
+#[test]
+fn current_exception() {
+    fn inspect(py: Python) -> PyResult<String> {
+        match py.current_exception() {
+            Some(err) => {
+                assert!(!PyErr::occurred(py));
+                Ok(err.get_type(py).name(py).into_owned())
+            }
+            None => Ok("none".to_owned()),
+        }
+    }
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let d = PyDict::new(py);
+    d.set_item(py, "inspect", py_fn!(py, inspect())).unwrap();
+    py.run(
+        "
+def handler(inspect=inspect):
+    try:
+        raise KeyError('k')
+    except KeyError:
+        first = inspect()
+        # inspecting must not clear the handled exception
+        return first, inspect()
+assert inspect() == 'none'
+assert handler() == ('KeyError', 'KeyError')
+assert inspect() == 'none'
+",
+        None,
+        Some(&d),
+    )
+    .unwrap();
+    assert!(py.current_exception().is_none());
+}
+
+#[test]
+fn generator_stop_and_error() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let d = PyDict::new(py);
+    let items = vec![
+        Ok(1),
+        Ok(2),
+        Err(PyErr::new::<exc::ValueError, _>(py, "boom")),
+    ];
+    d.set_item(py, "gen", py_generator(py, items.into_iter()).unwrap())
+        .unwrap();
+    py.run(
+        "assert next(gen) == 1\nassert next(gen) == 2\n\
+         try:\n    next(gen)\nexcept ValueError:\n    pass\nelse:\n    assert False",
+        None,
+        Some(&d),
+    )
+    .unwrap();
+
+    let short = py_generator(py, vec![Ok("a")].into_iter()).unwrap();
+    d.set_item(py, "short", short).unwrap();
+    py.run("assert list(short) == ['a']", None, Some(&d))
+        .unwrap();
+}
+
+#[test]
+fn closure() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let mut calls = Vec::new();
+    let obj = py_closure!(py, move |py, args, kwargs| {
+        calls.push(args.len(py) + kwargs.map_or(0, |kwargs| kwargs.len(py)));
+        Ok(calls.clone())
+    });
+
+    assert_eq!(
+        obj.call(py, NoArgs, None)
+            .unwrap()
+            .extract::<Vec<usize>>(py)
+            .unwrap(),
+        vec![0]
+    );
+    let dict = PyDict::new(py);
+    dict.set_item(py, "key", 42).unwrap();
+    assert_eq!(
+        obj.call(py, (1, 2), Some(&dict))
+            .unwrap()
+            .extract::<Vec<usize>>(py)
+            .unwrap(),
+        vec![0, 3]
+    );
+}
+
+#[test]
+fn closure_error_and_drop() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let state = std::sync::Arc::new(());
+    let captured = state.clone();
+    let obj = py_closure!(py, move |py, _args, _kwargs| -> PyResult<PyNone> {
+        let _ = &captured;
+        Err(PyErr::new::<exc::ValueError, _>(py, "closure failed"))
+    });
+    let err = obj.call(py, NoArgs, None).unwrap_err();
+    assert!(err.matches(py, py.get_type::<exc::ValueError>()));
+
+    assert_eq!(std::sync::Arc::strong_count(&state), 2);
+    drop(obj);
+    assert_eq!(std::sync::Arc::strong_count(&state), 1);
+}
+
+#[test]
+fn allow_threads_in_py_fn() {
+    fn f(py: Python, x: i32) -> PyResult<i32> {
+        let factor = py.allow_threads(|| {
+            // Another thread can acquire the GIL while it is released.
+            let other = std::thread::spawn(|| {
+                let gil = Python::acquire_gil();
+                let py = gil.python();
+                py.eval("2", None, None)
+                    .unwrap()
+                    .extract::<i32>(py)
+                    .unwrap()
+            });
+            other.join().unwrap()
+        });
+        Ok(x * factor)
+    }
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let obj = py_fn!(py, f(x: i32));
+    assert_eq!(
+        obj.call(py, (21,), None)
+            .unwrap()
+            .extract::<i32>(py)
+            .unwrap(),
+        42
+    );
+}
+
+#[test]
+fn release_gil_in_py_fn() {
+    fn f(py: Python, x: i32) -> PyResult<i32> {
+        let factor = release_gil!(py, {
+            // Another thread can acquire the GIL while it is released.
+            let other = std::thread::spawn(|| {
+                let gil = Python::acquire_gil();
+                let py = gil.python();
+                py.eval("2", None, None)
+                    .unwrap()
+                    .extract::<i32>(py)
+                    .unwrap()
+            });
+            other.join().unwrap()
+        });
+        let divisor = release_gil!(
+            py,
+            {
+                if x == 0 {
+                    return Err("division by zero");
+                }
+                Ok(x)
+            },
+            |py, msg: &str| PyErr::new::<exc::ZeroDivisionError, _>(py, msg)
+        );
+        Ok(factor * 42 / divisor)
+    }
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let obj = py_fn!(py, f(x: i32));
+    assert_eq!(
+        obj.call(py, (21,), None)
+            .unwrap()
+            .extract::<i32>(py)
+            .unwrap(),
+        4
+    );
+    let err = obj.call(py, (0,), None).unwrap_err();
+    assert!(err.matches(py, py.get_type::<exc::ZeroDivisionError>()));
+}
+
+#[test]
+fn panic_handler() {
+    fn f(_py: Python, x: i32) -> PyResult<i32> {
+        if x < 0 {
+            panic!("negative value: {}", x);
+        }
+        Ok(x)
+    }
+
+    fn to_value_error(py: Python, payload: &(dyn std::any::Any + Send)) -> PyErr {
+        let msg = panic_message(payload).unwrap_or("no message").to_string();
+        PyErr::new::<exc::ValueError, _>(py, msg)
+    }
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let obj = py_fn!(py, f(x: i32));
+
+    let mut err = obj.call(py, (-1,), None).unwrap_err();
+    assert!(err.matches(py, py.get_type::<exc::RuntimeError>()));
+    assert_eq!(
+        err.instance(py).str(py).unwrap().to_string(py).unwrap(),
+        "Rust panic: negative value: -1"
+    );
+
+    set_panic_handler(Some(to_value_error));
+    let mut err = obj.call(py, (-2,), None).unwrap_err();
+    set_panic_handler(None);
+    assert!(err.matches(py, py.get_type::<exc::ValueError>()));
+    assert_eq!(
+        err.instance(py).str(py).unwrap().to_string(py).unwrap(),
+        "negative value: -2"
+    );
+    assert_eq!(
+        obj.call(py, (3,), None)
+            .unwrap()
+            .extract::<i32>(py)
+            .unwrap(),
+        3
+    );
+}
+
+#[test]
+fn annotations() {
+    fn multiply(_py: Python, lhs: i32, rhs: i32) -> PyResult<i32> {
+        Ok(lhs * rhs)
+    }
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let obj = py_fn!(py, multiply(lhs: i32, rhs: i32));
+
+    // built-in functions cannot carry annotations
+    assert!(obj.setattr(py, "__annotations__", PyDict::new(py)).is_err());
+
+    // but a Python wrapper can
+    let annotations = PyDict::new(py);
+    let int = py.eval("int", None, None).unwrap();
+    annotations.set_item(py, "lhs", &int).unwrap();
+    annotations.set_item(py, "rhs", &int).unwrap();
+    annotations.set_item(py, "return", &int).unwrap();
+    let wrapper = annotate_function(py, obj.clone_ref(py), &annotations).unwrap();
+    assert!(wrapper.as_ptr() != obj.as_ptr());
+    let d = PyDict::new(py);
+    d.set_item(py, "multiply", wrapper).unwrap();
+    d.set_item(py, "original", &obj).unwrap();
+    // each call creates a new wrapper
+    let untyped = annotate_function(py, obj, &PyDict::new(py)).unwrap();
+    d.set_item(py, "untyped", untyped).unwrap();
+    py.run(
+        "
+assert multiply(6, 7) == 42
+assert multiply(lhs=6, rhs=7) == 42
+assert multiply.__name__ == 'multiply'
+assert multiply.__annotations__ == {'lhs': int, 'rhs': int, 'return': int}
+assert untyped(6, 7) == 42 and untyped.__annotations__ == {}
+assert not hasattr(original, '__annotations__')
+",
+        None,
+        Some(&d),
+    )
+    .unwrap();
+    if py
+        .eval("__import__('sys').version_info >= (3, 6)", None, None)
+        .unwrap()
+        .extract::<bool>(py)
+        .unwrap()
+    {
+        py.run(
+            "import inspect\nassert str(inspect.signature(multiply)) == '(lhs: int, rhs: int) -> int'",
+            None,
+            Some(&d),
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn current_frame() {
+    fn call_site(py: Python) -> PyResult<(String, String, u32, i32, String)> {
+        let frame = py.current_frame().unwrap();
+        let x: i32 = frame.locals(py)?.get_item(py, "x")?.extract(py)?;
+        let caller = frame.back(py)?.unwrap();
+        Ok((
+            frame.filename(py)?,
+            frame.function_name(py)?,
+            frame.lineno(py),
+            x,
+            caller.function_name(py)?,
+        ))
+    }
+
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    assert!(py.current_frame().is_none());
+
+    let d = PyDict::new(py);
+    d.set_item(py, "call_site", py_fn!(py, call_site()))
+        .unwrap();
+    let code = py
+        .compile(
+            "def outer():\n    return inner(21)\n\ndef inner(x):\n    return call_site()\n\nresult = outer()\n",
+            "<frames>",
+            CompileMode::Exec,
+        )
+        .unwrap();
+    code.run(py, Some(&d), None).unwrap();
+    let result: (String, String, u32, i32, String) =
+        d.get_item(py, "result").unwrap().extract(py).unwrap();
+    assert_eq!(
+        result,
+        (
+            "<frames>".to_owned(),
+            "inner".to_owned(),
+            5,
+            21,
+            "outer".to_owned()
+        )
+    );
+}
+
 /* TODO: reimplement flexible sig support
 #[test]
 fn flexible_sig() {
@@ -1,4 +1,4 @@
-use cpython::{py_class, GILGuard, Python};
+use cpython::{exc, py_class, GILGuard, Python};
 
 py_class!(class Owner |py| {
     @shared data string: String;
@@ -146,3 +146,25 @@ fn test_borrow_while_borrow_mut() {
     let _mut_ref = owner.string(py).borrow_mut();
     owner.string(py).borrow();
 }
+
+#[test]
+fn test_borrow_mut_checked_while_borrow() {
+    let (gil, owner) = prepare_env();
+    let py = gil.python();
+    {
+        let _ref = owner.string(py).borrow();
+        let err = owner.string(py).borrow_mut_checked().unwrap_err();
+        assert!(err.matches(py, py.get_type::<exc::RuntimeError>()));
+    }
+    owner.string(py).borrow_mut_checked().unwrap().push('!');
+    assert_eq!(*owner.string(py).borrow(), "new!");
+}
+
+#[test]
+fn test_borrow_checked_while_borrow_mut() {
+    let (gil, owner) = prepare_env();
+    let py = gil.python();
+    let _mut_ref = owner.string(py).borrow_mut();
+    let err = owner.string(py).borrow_checked().unwrap_err();
+    assert!(err.matches(py, py.get_type::<exc::RuntimeError>()));
+}